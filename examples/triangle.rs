@@ -49,7 +49,7 @@ fn main() {
 
     let root = {
         scoped_timer!("root");
-        xgpu::Root::new(&xgpu::RootCreateInfo {}).unwrap()
+        xgpu::Root::new(&xgpu::RootCreateInfo::default()).unwrap()
     };
 
     let surface = {
@@ -65,15 +65,19 @@ fn main() {
 
     let device = {
         scoped_timer!("device");
-        root.devices().first().unwrap().clone()
+        xgpu::adapter::AdapterSelector {
+            requirements: xgpu::adapter::AdapterRequirements {
+                surface: Some(&surface),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .select(&root)
+        .into_device()
+        .unwrap()
     };
     // dbg!(&device);
 
-    device
-        .supports_surface(surface.clone())
-        .then_some(())
-        .unwrap();
-
     let capabilities = device.get_surface_capabilities(surface.clone()).unwrap();
     // dbg!(&capabilities);
 
@@ -114,8 +118,11 @@ fn main() {
                 format: selected_format.format,
                 colorspace: selected_format.colorspace,
                 extent: capabilities.current_extent(),
+                usage: xgpu::ImageUsageFlags::empty(),
                 composite_alpha: xgpu::CompositeAlphaMode::Opaque,
                 present_mode: selected_present_mode.clone(),
+                old_swapchain: None,
+                label: Some("swapchain"),
             },
         )
         .unwrap()
@@ -133,6 +140,7 @@ fn main() {
                     image.clone(),
                     xgpu::ImageViewCreateInfo {
                         format: selected_format.format,
+                        label: None,
                     },
                 )
                 .unwrap()
@@ -145,10 +153,19 @@ fn main() {
 
         let attachments = &[xgpu::AttachmentDescription {
             format: selected_format.format,
+            load_op: xgpu::AttachmentLoadOp::Clear,
+            store_op: xgpu::AttachmentStoreOp::Store,
+            stencil_load_op: xgpu::AttachmentLoadOp::DontCare,
+            stencil_store_op: xgpu::AttachmentStoreOp::DontCare,
+            initial_layout: xgpu::ImageLayout::Undefined,
+            final_layout: xgpu::ImageLayout::PresentSrc,
         }];
 
         let subpasses = &[xgpu::SubpassDescription {
-            color_attachments: &[xgpu::AttachmentReference { attachment: 0 }],
+            color_attachments: &[xgpu::AttachmentReference {
+                attachment: 0,
+                layout: xgpu::ImageLayout::ColorAttachment,
+            }],
             ..Default::default()
         }];
 
@@ -157,6 +174,8 @@ fn main() {
             xgpu::RenderPassCreateInfo {
                 attachments,
                 subpasses,
+                dependencies: &[],
+                label: Some("triangle render pass"),
             },
         )
         .unwrap()
@@ -164,7 +183,8 @@ fn main() {
 
     let pipeline_layout = {
         scoped_timer!("pipeline_layout");
-        xgpu::PipelineLayout::new(context.clone(), xgpu::PipelineLayoutCreateInfo {}).unwrap()
+        xgpu::PipelineLayout::new(context.clone(), xgpu::PipelineLayoutCreateInfo::default())
+            .unwrap()
     };
 
     let vertex_shader = {
@@ -181,8 +201,8 @@ fn main() {
         scoped_timer!("pipeline");
 
         let shaders = xgpu::ShaderStages {
-            vertex: Some(vertex_shader.clone()),
-            fragment: Some(fragment_shader.clone()),
+            vertex: Some(vertex_shader.clone().into()),
+            fragment: Some(fragment_shader.clone().into()),
             ..Default::default()
         };
 
@@ -202,12 +222,17 @@ fn main() {
 
         let create_info = xgpu::GraphicsPipelineCreateInfo {
             shaders,
+            vertex_input: xgpu::VertexInputState::default(),
             topology: xgpu::PrimitiveTopology::TriangleList,
             rasterization,
             blend,
             layout: pipeline_layout.clone(),
-            render_pass: render_pass.clone(),
-            subpass: 0,
+            target: xgpu::PipelineTarget::RenderPass {
+                render_pass: render_pass.clone(),
+                subpass: 0,
+            },
+            cache: None,
+            label: Some("triangle pipeline"),
         };
 
         xgpu::GraphicsPipeline::new(context.clone(), create_info).unwrap()
@@ -224,6 +249,7 @@ fn main() {
                         render_pass: render_pass.clone(),
                         extent: capabilities.current_extent(),
                         attachments: &[view.clone()],
+                        label: None,
                     },
                 )
                 .unwrap()
@@ -236,8 +262,10 @@ fn main() {
         xgpu::CommandPool::new(
             context.clone(),
             xgpu::CommandPoolCreateInfo {
+                queue_family_index: queue.family_index(),
                 transient: false,
                 reset: true,
+                label: None,
             },
         )
         .unwrap()
@@ -250,6 +278,7 @@ fn main() {
             xgpu::CommandBufferAllocateInfo {
                 // level: xgpu::CommandBufferLevel::Primary,
                 // count: 1,
+                label: None,
             },
         )
         .unwrap()
@@ -257,7 +286,14 @@ fn main() {
 
     let fence = {
         scoped_timer!("fence");
-        xgpu::Fence::new(context.clone(), xgpu::FenceCreateInfo { signaled: false }).unwrap()
+        xgpu::Fence::new(
+            context.clone(),
+            xgpu::FenceCreateInfo {
+                signaled: false,
+                label: None,
+            },
+        )
+        .unwrap()
     };
 
     //
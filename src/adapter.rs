@@ -0,0 +1,250 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::prelude::*;
+use crate::{
+    Device, DeviceFeatureFlags, DeviceLimits, DeviceType, Format, FormatFeatureFlags, Root, Surface,
+};
+use std::fmt::{Display, Formatter};
+
+/// Forces a device by its index in [`ApiRoot::devices`] or a case-insensitive part of its name.
+pub const ADAPTER_OVERRIDE_VAR: &str = "XGPU_ADAPTER";
+
+/// How devices meeting the requirements are ranked.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum AdapterPolicy {
+    /// Discrete GPUs, then integrated, virtual and CPU devices.
+    #[default]
+    PreferDiscrete,
+    /// Integrated GPUs first, which usually draw less power.
+    PreferIntegrated,
+    /// The device with the most dedicated memory.
+    MostMemory,
+}
+
+impl AdapterPolicy {
+    /// Higher is better, ties going to the device enumerated first.
+    fn score(self, device: &Device) -> (u64, u64) {
+        let memory = device.dedicated_memory();
+
+        let rank = |preferred: DeviceType| match device.properties().device_type() {
+            device_type if device_type == preferred => 4,
+            DeviceType::DiscreteGpu | DeviceType::IntegratedGpu => 3,
+            DeviceType::VirtualGpu => 2,
+            DeviceType::Cpu => 0,
+            _ => 1,
+        };
+
+        match self {
+            AdapterPolicy::PreferDiscrete => (rank(DeviceType::DiscreteGpu), memory),
+            AdapterPolicy::PreferIntegrated => (rank(DeviceType::IntegratedGpu), memory),
+            AdapterPolicy::MostMemory => (memory, rank(DeviceType::DiscreteGpu)),
+        }
+    }
+}
+
+/// What a device must support to be selected.
+#[derive(Debug, Clone, Default)]
+pub struct AdapterRequirements<'a> {
+    /// A surface the device must be able to present to.
+    pub surface: Option<&'a Surface>,
+    pub features: DeviceFeatureFlags,
+    /// Formats and the features each must support with optimal tiling.
+    pub formats: &'a [(Format, FormatFeatureFlags)],
+    /// Minimum limits, 0 meaning no minimum.
+    pub limits: DeviceLimits,
+}
+
+impl AdapterRequirements<'_> {
+    /// Every requirement the device doesn't meet.
+    pub fn check(&self, device: &Device) -> Vec<RejectionReason> {
+        let mut reasons = Vec::new();
+
+        if let Some(surface) = self.surface {
+            if !device.supports_surface(surface.clone()) {
+                reasons.push(RejectionReason::SurfaceUnsupported);
+            }
+        }
+
+        let missing = self.features - device.features();
+        if !missing.is_empty() {
+            reasons.push(RejectionReason::MissingFeatures(missing));
+        }
+
+        for &(format, required) in self.formats {
+            let missing = required - device.format_capabilities(format).optimal_tiling;
+            if !missing.is_empty() {
+                reasons.push(RejectionReason::UnsupportedFormat { format, missing });
+            }
+        }
+
+        let limits = device.properties().limits();
+
+        macro_rules! check_limits {
+            ($($limit:ident),*) => {
+                $(
+                    if limits.$limit < self.limits.$limit {
+                        reasons.push(RejectionReason::LimitTooLow {
+                            limit: stringify!($limit),
+                            required: self.limits.$limit,
+                            actual: limits.$limit,
+                        });
+                    }
+                )*
+            };
+        }
+
+        check_limits!(
+            max_image_dimension_2d,
+            max_image_array_layers,
+            max_color_attachments,
+            max_bound_descriptor_sets,
+            max_push_constants_size,
+            max_vertex_input_attributes,
+            max_compute_work_group_invocations
+        );
+
+        reasons
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectionReason {
+    SurfaceUnsupported,
+    MissingFeatures(DeviceFeatureFlags),
+    UnsupportedFormat {
+        format: Format,
+        missing: FormatFeatureFlags,
+    },
+    LimitTooLow {
+        limit: &'static str,
+        required: u32,
+        actual: u32,
+    },
+    /// Another device was forced with [`ADAPTER_OVERRIDE_VAR`].
+    Overridden,
+}
+
+impl Display for RejectionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RejectionReason::SurfaceUnsupported => write!(f, "can't present to the surface"),
+            RejectionReason::MissingFeatures(features) => {
+                write!(f, "missing features {features:?}")
+            }
+            RejectionReason::UnsupportedFormat { format, missing } => {
+                write!(f, "{format:?} doesn't support {missing:?}")
+            }
+            RejectionReason::LimitTooLow {
+                limit,
+                required,
+                actual,
+            } => write!(f, "{limit} is {actual}, {required} required"),
+            RejectionReason::Overridden => {
+                write!(f, "{ADAPTER_OVERRIDE_VAR} selects another device")
+            }
+        }
+    }
+}
+
+/// A device that wasn't selected and every reason why.
+#[derive(Debug, Clone)]
+pub struct Rejection {
+    /// Index of the device in [`ApiRoot::devices`].
+    pub index: usize,
+    pub device: Device,
+    pub reasons: Vec<RejectionReason>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdapterSelection {
+    /// The chosen device and its index in [`ApiRoot::devices`].
+    pub selected: Option<(usize, Device)>,
+    pub rejected: Vec<Rejection>,
+}
+
+impl AdapterSelection {
+    /// The chosen device, or an error if every device was rejected.
+    pub fn into_device(self) -> crate::Result<Device> {
+        self.selected
+            .map(|(_, device)| device)
+            .ok_or(crate::Error::Unsupported(
+                "no device meets the adapter requirements",
+            ))
+    }
+}
+
+/// Picks the best device meeting a set of requirements.
+#[derive(Debug, Clone, Default)]
+pub struct AdapterSelector<'a> {
+    pub requirements: AdapterRequirements<'a>,
+    pub policy: AdapterPolicy,
+    /// Ignores [`ADAPTER_OVERRIDE_VAR`].
+    pub ignore_override: bool,
+}
+
+impl AdapterSelector<'_> {
+    pub fn select(&self, root: &Root) -> AdapterSelection {
+        let devices = root.devices();
+
+        let forced = match self.ignore_override {
+            true => None,
+            false => override_index(devices),
+        };
+
+        let mut best: Option<(usize, (u64, u64))> = None;
+        let mut rejected = Vec::new();
+
+        for (index, device) in devices.iter().enumerate() {
+            let mut reasons = self.requirements.check(device);
+
+            if forced.is_some_and(|forced| forced != index) {
+                reasons.push(RejectionReason::Overridden);
+            }
+
+            if !reasons.is_empty() {
+                rejected.push(Rejection {
+                    index,
+                    device: device.clone(),
+                    reasons,
+                });
+                continue;
+            }
+
+            let score = self.policy.score(device);
+            if best.map_or(true, |(_, best)| score > best) {
+                best = Some((index, score));
+            }
+        }
+
+        AdapterSelection {
+            selected: best.map(|(index, _)| (index, devices[index].clone())),
+            rejected,
+        }
+    }
+}
+
+fn override_index(devices: &[Device]) -> Option<usize> {
+    let value = std::env::var(ADAPTER_OVERRIDE_VAR).ok()?;
+    let value = value.trim();
+
+    if value.is_empty() {
+        return None;
+    }
+
+    let index = match value.parse::<usize>() {
+        Ok(index) => (index < devices.len()).then_some(index),
+        Err(_) => {
+            let name = value.to_lowercase();
+            devices
+                .iter()
+                .position(|device| device.properties().name().to_lowercase().contains(&name))
+        }
+    };
+
+    if index.is_none() {
+        log::warn!("{ADAPTER_OVERRIDE_VAR}={value} matches no device, ignoring it");
+    }
+
+    index
+}
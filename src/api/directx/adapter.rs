@@ -4,14 +4,15 @@
 use crate::api::directx::{DirectXApi, DirectXObject};
 use crate::api::traits::GraphicsApi;
 use crate::prelude::{DeviceProperties, SurfaceCapabilities};
-use crate::{Colorspace, Extent2D, Format, PresentMode, SurfaceFormat, Vendor};
+use crate::{Colorspace, DeviceType, Extent2D, Format, PresentMode, SurfaceFormat, Vendor};
 use std::any::type_name;
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use windows::{
-    core::*, Win32::Foundation::*, Win32::Graphics::Dxgi::*, Win32::UI::WindowsAndMessaging::*,
+    core::*, Win32::Foundation::*, Win32::Graphics::Direct3D::*, Win32::Graphics::Direct3D12::*,
+    Win32::Graphics::Dxgi::*, Win32::UI::WindowsAndMessaging::*,
 };
 
 #[derive(Debug)]
@@ -44,19 +45,67 @@ impl Debug for DirectXAdapter {
 
 impl DirectXAdapter {
     pub(crate) fn new(adapter: IDXGIAdapter1, desc: DXGI_ADAPTER_DESC1) -> Self {
+        let device_type = adapter_device_type(&adapter, &desc);
         let data = Arc::new(DirectXAdapterData {
-            description: desc.into(),
+            description: DirectXAdapterDescription::new(desc, device_type),
         });
 
         Self { adapter, data }
     }
 }
 
+/// Software adapters such as WARP are reported as CPUs, and adapters sharing memory with the
+/// host as integrated GPUs.
+fn adapter_device_type(adapter: &IDXGIAdapter1, desc: &DXGI_ADAPTER_DESC1) -> DeviceType {
+    if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0 {
+        return DeviceType::Cpu;
+    }
+
+    // the memory architecture is only reported by a device
+    let mut device: Option<ID3D12Device> = None;
+    if unsafe { D3D12CreateDevice(adapter, D3D_FEATURE_LEVEL_11_0, &mut device) }.is_err() {
+        return DeviceType::Other;
+    }
+
+    let Some(device) = device else {
+        return DeviceType::Other;
+    };
+
+    let mut architecture = D3D12_FEATURE_DATA_ARCHITECTURE::default();
+    let result = unsafe {
+        device.CheckFeatureSupport(
+            D3D12_FEATURE_ARCHITECTURE,
+            &mut architecture as *mut _ as *mut _,
+            std::mem::size_of_val(&architecture) as u32,
+        )
+    };
+
+    match result {
+        Ok(()) if architecture.UMA.as_bool() => DeviceType::IntegratedGpu,
+        Ok(()) => DeviceType::DiscreteGpu,
+        Err(_) => DeviceType::Other,
+    }
+}
+
 impl crate::api::traits::Device<DirectXApi> for DirectXAdapter {
     fn properties(&self) -> &directx_type!(DeviceProperties) {
         &self.data.description
     }
 
+    fn features(&self) -> crate::DeviceFeatureFlags {
+        // everything feature level 11_0, the minimum for Direct3D 12, guarantees
+        crate::DeviceFeatureFlags::GEOMETRY_SHADER
+            | crate::DeviceFeatureFlags::TESSELLATION_SHADER
+            | crate::DeviceFeatureFlags::SAMPLER_ANISOTROPY
+            | crate::DeviceFeatureFlags::FILL_MODE_NON_SOLID
+            | crate::DeviceFeatureFlags::MULTI_DRAW_INDIRECT
+            | crate::DeviceFeatureFlags::TEXTURE_COMPRESSION_BC
+    }
+
+    fn dedicated_memory(&self) -> u64 {
+        self.data.description.description.DedicatedVideoMemory as u64
+    }
+
     fn supports_surface(&self, surface: directx_type!(Surface)) -> bool {
         // actually implement this
         true
@@ -103,6 +152,12 @@ impl crate::api::traits::Device<DirectXApi> for DirectXAdapter {
             PresentMode::Immediate,
         ])
     }
+
+    fn format_capabilities(&self, format: Format) -> crate::FormatCapabilities {
+        // needs CheckFeatureSupport on a device rather than the adapter, so no support is
+        // reported until devices are queried
+        crate::FormatCapabilities::default()
+    }
 }
 
 const BGRA8_UNORM: SurfaceFormat = SurfaceFormat {
@@ -143,6 +198,7 @@ const RGB10A2_SRGB: SurfaceFormat = SurfaceFormat {
 pub struct DirectXAdapterDescription {
     description: DXGI_ADAPTER_DESC1,
     name: String,
+    device_type: DeviceType,
 }
 
 impl Debug for DirectXAdapterDescription {
@@ -155,8 +211,8 @@ impl Debug for DirectXAdapterDescription {
     }
 }
 
-impl From<DXGI_ADAPTER_DESC1> for DirectXAdapterDescription {
-    fn from(desc: DXGI_ADAPTER_DESC1) -> Self {
+impl DirectXAdapterDescription {
+    fn new(desc: DXGI_ADAPTER_DESC1, device_type: DeviceType) -> Self {
         let name = String::from_utf16(&desc.Description)
             .unwrap()
             .trim_end_matches(0 as char)
@@ -164,6 +220,7 @@ impl From<DXGI_ADAPTER_DESC1> for DirectXAdapterDescription {
         Self {
             description: desc,
             name,
+            device_type,
         }
     }
 }
@@ -174,12 +231,69 @@ impl crate::api::traits::DeviceProperties<DirectXApi> for DirectXAdapterDescript
     }
 
     fn device_type(&self) -> crate::DeviceType {
-        todo!()
+        self.device_type
     }
 
     fn vendor(&self) -> Vendor {
         self.description.VendorId.into()
     }
+
+    fn vendor_id(&self) -> u32 {
+        self.description.VendorId
+    }
+
+    fn device_id(&self) -> u32 {
+        self.description.DeviceId
+    }
+
+    fn driver_version(&self) -> crate::DriverVersion {
+        // the adapter description doesn't carry the driver version
+        crate::DriverVersion::unknown()
+    }
+
+    fn api_version(&self) -> crate::ApiVersion {
+        crate::ApiVersion {
+            major: 12,
+            minor: 0,
+            patch: 0,
+        }
+    }
+
+    fn limits(&self) -> crate::DeviceLimits {
+        // the limits Direct3D 12 requires of every device
+        crate::DeviceLimits {
+            max_image_dimension_2d: 16384,
+            max_image_array_layers: 2048,
+            max_color_attachments: 8,
+            max_bound_descriptor_sets: 64,
+            max_push_constants_size: 256,
+            max_vertex_input_attributes: 32,
+            max_compute_work_group_invocations: 1024,
+        }
+    }
+
+    fn pipeline_cache_uuid(&self) -> [u8; 16] {
+        // without a driver version, caches are keyed on the hardware alone
+        let desc = &self.description;
+        let mut uuid = [0; 16];
+        for (bytes, value) in uuid.chunks_exact_mut(4).zip([
+            desc.VendorId,
+            desc.DeviceId,
+            desc.SubSysId,
+            desc.Revision,
+        ]) {
+            bytes.copy_from_slice(&value.to_le_bytes());
+        }
+        uuid
+    }
+
+    fn driver_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn driver_info(&self) -> Option<&str> {
+        None
+    }
 }
 
 /*
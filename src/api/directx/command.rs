@@ -2,7 +2,10 @@
 // All rights reserved.
 
 use crate::api::directx::*;
-use crate::{CommandBufferAllocateInfo, CommandPoolCreateInfo};
+use crate::{
+    CommandBufferAllocateInfo, CommandPoolCreateInfo, DependencyInfo, RenderPassBeginInfo,
+    RenderingInfo,
+};
 use std::fmt::Formatter;
 use std::sync::Arc;
 
@@ -120,4 +123,87 @@ impl crate::api::traits::CommandBuffer<DirectXApi> for DirectXCommandList {
 
         Ok(Self { list, data })
     }
+
+    fn begin(&self) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "command recording isn't implemented for DirectX",
+        ))
+    }
+
+    fn end(&self) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "command recording isn't implemented for DirectX",
+        ))
+    }
+
+    fn pipeline_barrier(&self, dependency_info: &DependencyInfo) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "pipeline barriers aren't implemented for DirectX",
+        ))
+    }
+
+    fn begin_rendering(&self, rendering_info: &RenderingInfo) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "dynamic rendering isn't implemented for DirectX",
+        ))
+    }
+
+    fn end_rendering(&self) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "dynamic rendering isn't implemented for DirectX",
+        ))
+    }
+
+    fn begin_render_pass(&self, begin_info: &RenderPassBeginInfo) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "render passes aren't implemented for DirectX",
+        ))
+    }
+
+    fn next_subpass(&self) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "render passes aren't implemented for DirectX",
+        ))
+    }
+
+    fn end_render_pass(&self) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "render passes aren't implemented for DirectX",
+        ))
+    }
+
+    fn copy_buffer(
+        &self,
+        src: &directx_type!(Buffer),
+        dst: &directx_type!(Buffer),
+        regions: &[crate::BufferCopy],
+    ) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "copy commands aren't implemented for DirectX",
+        ))
+    }
+
+    fn copy_buffer_to_image(
+        &self,
+        src: &directx_type!(Buffer),
+        dst: &directx_type!(Image),
+        layout: crate::ImageLayout,
+        regions: &[crate::BufferImageCopy],
+    ) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "copy commands aren't implemented for DirectX",
+        ))
+    }
+
+    fn copy_image_to_buffer(
+        &self,
+        src: &directx_type!(Image),
+        layout: crate::ImageLayout,
+        dst: &directx_type!(Buffer),
+        regions: &[crate::BufferImageCopy],
+    ) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "copy commands aren't implemented for DirectX",
+        ))
+    }
 }
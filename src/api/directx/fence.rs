@@ -57,6 +57,18 @@ impl crate::api::traits::Fence<DirectXApi> for DirectXFence {
         Ok(Self { fence, data })
     }
 
+    fn wait(&self, timeout: Option<std::time::Duration>) -> crate::Result<bool> {
+        Err(crate::Error::Unsupported(
+            "waiting for fences isn't implemented for DirectX",
+        ))
+    }
+
+    fn reset(&self) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "resetting fences isn't implemented for DirectX",
+        ))
+    }
+
     //fn signal(&self, context: directx_type!(Context), value: u64) -> crate::Result<()> {
     //    unsafe {
     //        context
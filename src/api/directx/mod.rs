@@ -76,6 +76,7 @@ impl crate::api::traits::GraphicsApi for DirectXApi {
 }
 
 mod constants {
+    use crate::api::traits::constants::unsupported_format;
     use windows::Win32::Graphics::Direct3D12::*;
     use windows::Win32::Graphics::Dxgi::Common::*;
 
@@ -150,6 +151,82 @@ mod constants {
         const R32G32B32A32_SFLOAT: i32 = DXGI_FORMAT_R32G32B32A32_FLOAT.0;
         const R10G10B10A2_UINT: i32 = DXGI_FORMAT_R10G10B10A2_UINT.0;
         const R10G10B10A2_UNORM: i32 = DXGI_FORMAT_R10G10B10A2_UNORM.0;
+
+        const D16_UNORM: i32 = DXGI_FORMAT_D16_UNORM.0;
+        const D32_SFLOAT: i32 = DXGI_FORMAT_D32_FLOAT.0;
+        const D24_UNORM_S8_UINT: i32 = DXGI_FORMAT_D24_UNORM_S8_UINT.0;
+        const D32_SFLOAT_S8_UINT: i32 = DXGI_FORMAT_D32_FLOAT_S8X24_UINT.0;
+
+        const B10G11R11_UFLOAT: i32 = DXGI_FORMAT_R11G11B10_FLOAT.0;
+        const E5B9G9R9_UFLOAT: i32 = DXGI_FORMAT_R9G9B9E5_SHAREDEXP.0;
+
+        const BC1_RGBA_UNORM: i32 = DXGI_FORMAT_BC1_UNORM.0;
+        const BC1_RGBA_UNORM_SRGB: i32 = DXGI_FORMAT_BC1_UNORM_SRGB.0;
+        const BC2_UNORM: i32 = DXGI_FORMAT_BC2_UNORM.0;
+        const BC2_UNORM_SRGB: i32 = DXGI_FORMAT_BC2_UNORM_SRGB.0;
+        const BC3_UNORM: i32 = DXGI_FORMAT_BC3_UNORM.0;
+        const BC3_UNORM_SRGB: i32 = DXGI_FORMAT_BC3_UNORM_SRGB.0;
+        const BC4_UNORM: i32 = DXGI_FORMAT_BC4_UNORM.0;
+        const BC4_SNORM: i32 = DXGI_FORMAT_BC4_SNORM.0;
+        const BC5_UNORM: i32 = DXGI_FORMAT_BC5_UNORM.0;
+        const BC5_SNORM: i32 = DXGI_FORMAT_BC5_SNORM.0;
+        const BC6H_UFLOAT: i32 = DXGI_FORMAT_BC6H_UF16.0;
+        const BC6H_SFLOAT: i32 = DXGI_FORMAT_BC6H_SF16.0;
+        const BC7_UNORM: i32 = DXGI_FORMAT_BC7_UNORM.0;
+        const BC7_UNORM_SRGB: i32 = DXGI_FORMAT_BC7_UNORM_SRGB.0;
+
+        const ETC2_R8G8B8_UNORM: i32 = unsupported_format(0);
+        const ETC2_R8G8B8_UNORM_SRGB: i32 = unsupported_format(1);
+        const ETC2_R8G8B8A1_UNORM: i32 = unsupported_format(2);
+        const ETC2_R8G8B8A1_UNORM_SRGB: i32 = unsupported_format(3);
+        const ETC2_R8G8B8A8_UNORM: i32 = unsupported_format(4);
+        const ETC2_R8G8B8A8_UNORM_SRGB: i32 = unsupported_format(5);
+        const EAC_R11_UNORM: i32 = unsupported_format(6);
+        const EAC_R11_SNORM: i32 = unsupported_format(7);
+        const EAC_R11G11_UNORM: i32 = unsupported_format(8);
+        const EAC_R11G11_SNORM: i32 = unsupported_format(9);
+
+        const ASTC_4x4_UNORM: i32 = unsupported_format(10);
+        const ASTC_4x4_UNORM_SRGB: i32 = unsupported_format(11);
+
+        const ASTC_5x4_UNORM: i32 = unsupported_format(12);
+        const ASTC_5x4_UNORM_SRGB: i32 = unsupported_format(13);
+
+        const ASTC_5x5_UNORM: i32 = unsupported_format(14);
+        const ASTC_5x5_UNORM_SRGB: i32 = unsupported_format(15);
+
+        const ASTC_6x5_UNORM: i32 = unsupported_format(16);
+        const ASTC_6x5_UNORM_SRGB: i32 = unsupported_format(17);
+
+        const ASTC_6x6_UNORM: i32 = unsupported_format(18);
+        const ASTC_6x6_UNORM_SRGB: i32 = unsupported_format(19);
+
+        const ASTC_8x5_UNORM: i32 = unsupported_format(20);
+        const ASTC_8x5_UNORM_SRGB: i32 = unsupported_format(21);
+
+        const ASTC_8x6_UNORM: i32 = unsupported_format(22);
+        const ASTC_8x6_UNORM_SRGB: i32 = unsupported_format(23);
+
+        const ASTC_8x8_UNORM: i32 = unsupported_format(24);
+        const ASTC_8x8_UNORM_SRGB: i32 = unsupported_format(25);
+
+        const ASTC_10x5_UNORM: i32 = unsupported_format(26);
+        const ASTC_10x5_UNORM_SRGB: i32 = unsupported_format(27);
+
+        const ASTC_10x6_UNORM: i32 = unsupported_format(28);
+        const ASTC_10x6_UNORM_SRGB: i32 = unsupported_format(29);
+
+        const ASTC_10x8_UNORM: i32 = unsupported_format(30);
+        const ASTC_10x8_UNORM_SRGB: i32 = unsupported_format(31);
+
+        const ASTC_10x10_UNORM: i32 = unsupported_format(32);
+        const ASTC_10x10_UNORM_SRGB: i32 = unsupported_format(33);
+
+        const ASTC_12x10_UNORM: i32 = unsupported_format(34);
+        const ASTC_12x10_UNORM_SRGB: i32 = unsupported_format(35);
+
+        const ASTC_12x12_UNORM: i32 = unsupported_format(36);
+        const ASTC_12x12_UNORM_SRGB: i32 = unsupported_format(37);
     }
 
     pub struct DirectXPresentModes;
@@ -193,27 +270,34 @@ mod constants {
     }
 }
 
-impl Into<DXGI_FORMAT> for crate::Format {
-    fn into(self) -> DXGI_FORMAT {
-        unsafe { std::mem::transmute(self) }
+impl TryFrom<crate::Format> for DXGI_FORMAT {
+    type Error = crate::Error;
+
+    fn try_from(format: crate::Format) -> crate::Result<Self> {
+        match format.is_supported() {
+            true => Ok(DXGI_FORMAT(format.raw())),
+            false => Err(crate::Error::Unsupported(
+                "format is not supported by the DirectX backend",
+            )),
+        }
     }
 }
 
 impl Into<DXGI_ALPHA_MODE> for crate::CompositeAlphaMode {
     fn into(self) -> DXGI_ALPHA_MODE {
-        unsafe { std::mem::transmute(self) }
+        DXGI_ALPHA_MODE(self as i32)
     }
 }
 
 impl Into<D3D12_BLEND_OP> for crate::BlendOp {
     fn into(self) -> D3D12_BLEND_OP {
-        unsafe { std::mem::transmute(self) }
+        D3D12_BLEND_OP(self as i32)
     }
 }
 
 impl Into<D3D12_BLEND> for crate::BlendFactor {
     fn into(self) -> D3D12_BLEND {
-        unsafe { std::mem::transmute(self) }
+        D3D12_BLEND(self as i32)
     }
 }
 
@@ -33,11 +33,15 @@ impl crate::api::traits::GraphicsPipeline<DirectXApi> for DirectXGraphicsPipelin
         context: directx_type!(Context),
         create_info: GraphicsPipelineCreateInfo,
     ) -> crate::Result<Self> {
-        let vs = create_info.shaders.vertex.map_into().unwrap_or_default();
-        let ps = create_info.shaders.fragment.map_into().unwrap_or_default();
-        let hs = create_info.shaders.tess_ctrl.map_into().unwrap_or_default();
-        let ds = create_info.shaders.tess_eval.map_into().unwrap_or_default();
-        let gs = create_info.shaders.geometry.map_into().unwrap_or_default();
+        let shader = |stage: Option<ShaderStage>| -> Option<D3D12_SHADER_BYTECODE> {
+            stage.map(|stage| stage.shader.into())
+        };
+
+        let vs = shader(create_info.shaders.vertex).unwrap_or_default();
+        let ps = shader(create_info.shaders.fragment).unwrap_or_default();
+        let hs = shader(create_info.shaders.tess_ctrl).unwrap_or_default();
+        let ds = shader(create_info.shaders.tess_eval).unwrap_or_default();
+        let gs = shader(create_info.shaders.geometry).unwrap_or_default();
 
         let input_layout = D3D12_INPUT_LAYOUT_DESC {
             pInputElementDescs: std::ptr::null(),
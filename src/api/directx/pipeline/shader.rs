@@ -46,11 +46,23 @@ impl std::fmt::Debug for DirectXShader {
     }
 }
 
+impl PartialEq for DirectXShader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+}
+
 impl crate::api::traits::Shader<DirectXApi> for DirectXShader {
     fn from_code(context: directx_type!(Context), code: ShaderCode) -> crate::Result<Self> {
         let code = match code {
             ShaderCode::Static(code) => Code::Static(code),
             ShaderCode::Dynamic(code) => Code::Dynamic(code.to_vec()),
+            #[cfg(feature = "naga")]
+            _ => {
+                return Err(crate::Error::Unsupported(
+                    "shader source compilation is only available for SPIR-V backends",
+                ))
+            }
         };
 
         let data = Arc::new(ShaderData {
@@ -60,4 +72,8 @@ impl crate::api::traits::Shader<DirectXApi> for DirectXShader {
 
         Ok(Self { data })
     }
+
+    fn reflection(&self) -> Option<&crate::spirv::ShaderReflection> {
+        None
+    }
 }
@@ -45,4 +45,35 @@ impl DirectXCommandQueue {
     }
 }
 
-impl crate::api::traits::Queue<DirectXApi> for DirectXCommandQueue {}
+impl crate::api::traits::Queue<DirectXApi> for DirectXCommandQueue {
+    fn family_index(&self) -> u32 {
+        // only a direct queue is created
+        0
+    }
+
+    fn flags(&self) -> crate::QueueFlags {
+        crate::QueueFlags::all()
+    }
+
+    fn submit(
+        &self,
+        submit_info: &crate::SubmitInfo,
+        fence: Option<&<DirectXApi as GraphicsApi>::Fence>,
+    ) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "queue submission isn't implemented for DirectX",
+        ))
+    }
+
+    fn present(&self, present_info: &crate::PresentInfo) -> crate::Result<bool> {
+        Err(crate::Error::Unsupported(
+            "presentation isn't implemented for DirectX",
+        ))
+    }
+
+    fn wait_idle(&self) -> crate::Result<()> {
+        Err(crate::Error::Unsupported(
+            "waiting for queues isn't implemented for DirectX",
+        ))
+    }
+}
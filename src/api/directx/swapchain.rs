@@ -41,7 +41,7 @@ impl crate::api::traits::Swapchain<DirectXApi> for DirectXSwapchain {
         let swapchain_desc = DXGI_SWAP_CHAIN_DESC1 {
             Width: create_info.extent.width,
             Height: create_info.extent.height,
-            Format: create_info.format.into(),
+            Format: create_info.format.try_into()?,
             Stereo: FALSE,
             SampleDesc: DXGI_SAMPLE_DESC {
                 Count: 1,
@@ -23,6 +23,14 @@ pub trait ColorSpaceConstants {
     const HDR10_HLG: i32;
 }
 
+/// The value of a format a backend can't represent.
+///
+/// Format values must be distinct, so each unsupported format takes its own `index`.
+pub const fn unsupported_format(index: i32) -> i32 {
+    i32::MIN + index
+}
+
+#[allow(non_upper_case_globals)]
 pub trait DataFormatConstants {
     const R8_UINT: i32;
     const R8_SINT: i32;
@@ -77,6 +85,82 @@ pub trait DataFormatConstants {
 
     const R10G10B10A2_UINT: i32;
     const R10G10B10A2_UNORM: i32;
+
+    const D16_UNORM: i32;
+    const D32_SFLOAT: i32;
+    const D24_UNORM_S8_UINT: i32;
+    const D32_SFLOAT_S8_UINT: i32;
+
+    const B10G11R11_UFLOAT: i32;
+    const E5B9G9R9_UFLOAT: i32;
+
+    const BC1_RGBA_UNORM: i32;
+    const BC1_RGBA_UNORM_SRGB: i32;
+    const BC2_UNORM: i32;
+    const BC2_UNORM_SRGB: i32;
+    const BC3_UNORM: i32;
+    const BC3_UNORM_SRGB: i32;
+    const BC4_UNORM: i32;
+    const BC4_SNORM: i32;
+    const BC5_UNORM: i32;
+    const BC5_SNORM: i32;
+    const BC6H_UFLOAT: i32;
+    const BC6H_SFLOAT: i32;
+    const BC7_UNORM: i32;
+    const BC7_UNORM_SRGB: i32;
+
+    const ETC2_R8G8B8_UNORM: i32;
+    const ETC2_R8G8B8_UNORM_SRGB: i32;
+    const ETC2_R8G8B8A1_UNORM: i32;
+    const ETC2_R8G8B8A1_UNORM_SRGB: i32;
+    const ETC2_R8G8B8A8_UNORM: i32;
+    const ETC2_R8G8B8A8_UNORM_SRGB: i32;
+    const EAC_R11_UNORM: i32;
+    const EAC_R11_SNORM: i32;
+    const EAC_R11G11_UNORM: i32;
+    const EAC_R11G11_SNORM: i32;
+
+    const ASTC_4x4_UNORM: i32;
+    const ASTC_4x4_UNORM_SRGB: i32;
+
+    const ASTC_5x4_UNORM: i32;
+    const ASTC_5x4_UNORM_SRGB: i32;
+
+    const ASTC_5x5_UNORM: i32;
+    const ASTC_5x5_UNORM_SRGB: i32;
+
+    const ASTC_6x5_UNORM: i32;
+    const ASTC_6x5_UNORM_SRGB: i32;
+
+    const ASTC_6x6_UNORM: i32;
+    const ASTC_6x6_UNORM_SRGB: i32;
+
+    const ASTC_8x5_UNORM: i32;
+    const ASTC_8x5_UNORM_SRGB: i32;
+
+    const ASTC_8x6_UNORM: i32;
+    const ASTC_8x6_UNORM_SRGB: i32;
+
+    const ASTC_8x8_UNORM: i32;
+    const ASTC_8x8_UNORM_SRGB: i32;
+
+    const ASTC_10x5_UNORM: i32;
+    const ASTC_10x5_UNORM_SRGB: i32;
+
+    const ASTC_10x6_UNORM: i32;
+    const ASTC_10x6_UNORM_SRGB: i32;
+
+    const ASTC_10x8_UNORM: i32;
+    const ASTC_10x8_UNORM_SRGB: i32;
+
+    const ASTC_10x10_UNORM: i32;
+    const ASTC_10x10_UNORM_SRGB: i32;
+
+    const ASTC_12x10_UNORM: i32;
+    const ASTC_12x10_UNORM_SRGB: i32;
+
+    const ASTC_12x12_UNORM: i32;
+    const ASTC_12x12_UNORM_SRGB: i32;
 }
 
 pub trait PresentModeConstants {
@@ -135,6 +219,134 @@ pub trait FrontFaceConstants {
     const COUNTER_CLOCKWISE: i32;
 }
 
+pub trait DescriptorTypeConstants {
+    const SAMPLER: i32;
+    const COMBINED_IMAGE_SAMPLER: i32;
+    const SAMPLED_IMAGE: i32;
+    const STORAGE_IMAGE: i32;
+    const UNIFORM_TEXEL_BUFFER: i32;
+    const STORAGE_TEXEL_BUFFER: i32;
+    const UNIFORM_BUFFER: i32;
+    const STORAGE_BUFFER: i32;
+    const INPUT_ATTACHMENT: i32;
+    const ACCELERATION_STRUCTURE: i32;
+}
+
+pub trait ShaderStageConstants {
+    const VERTEX: i32;
+    const TESSELLATION_CONTROL: i32;
+    const TESSELLATION_EVALUATION: i32;
+    const GEOMETRY: i32;
+    const FRAGMENT: i32;
+    const COMPUTE: i32;
+}
+
+pub trait ImageLayoutConstants {
+    const UNDEFINED: i32;
+    const GENERAL: i32;
+    const COLOR_ATTACHMENT: i32;
+    const DEPTH_STENCIL_ATTACHMENT: i32;
+    const DEPTH_STENCIL_READ_ONLY: i32;
+    const SHADER_READ_ONLY: i32;
+    const TRANSFER_SRC: i32;
+    const TRANSFER_DST: i32;
+    const PREINITIALIZED: i32;
+    const PRESENT_SRC: i32;
+}
+
+pub trait AccessConstants {
+    const INDIRECT_COMMAND_READ: u64;
+    const INDEX_READ: u64;
+    const VERTEX_ATTRIBUTE_READ: u64;
+    const UNIFORM_READ: u64;
+    const INPUT_ATTACHMENT_READ: u64;
+    const SHADER_READ: u64;
+    const SHADER_WRITE: u64;
+    const COLOR_ATTACHMENT_READ: u64;
+    const COLOR_ATTACHMENT_WRITE: u64;
+    const DEPTH_STENCIL_ATTACHMENT_READ: u64;
+    const DEPTH_STENCIL_ATTACHMENT_WRITE: u64;
+    const TRANSFER_READ: u64;
+    const TRANSFER_WRITE: u64;
+    const HOST_READ: u64;
+    const HOST_WRITE: u64;
+    const MEMORY_READ: u64;
+    const MEMORY_WRITE: u64;
+}
+
+pub trait PipelineStageConstants {
+    const TOP_OF_PIPE: u64;
+    const DRAW_INDIRECT: u64;
+    const VERTEX_INPUT: u64;
+    const VERTEX_SHADER: u64;
+    const TESSELLATION_CONTROL_SHADER: u64;
+    const TESSELLATION_EVALUATION_SHADER: u64;
+    const GEOMETRY_SHADER: u64;
+    const FRAGMENT_SHADER: u64;
+    const EARLY_FRAGMENT_TESTS: u64;
+    const LATE_FRAGMENT_TESTS: u64;
+    const COLOR_ATTACHMENT_OUTPUT: u64;
+    const COMPUTE_SHADER: u64;
+    const TRANSFER: u64;
+    const BOTTOM_OF_PIPE: u64;
+    const HOST: u64;
+    const ALL_GRAPHICS: u64;
+    const ALL_COMMANDS: u64;
+}
+
+pub trait ImageAspectConstants {
+    const COLOR: i32;
+    const DEPTH: i32;
+    const STENCIL: i32;
+}
+
+pub trait BufferUsageConstants {
+    const TRANSFER_SRC: i32;
+    const TRANSFER_DST: i32;
+    const UNIFORM: i32;
+    const STORAGE: i32;
+    const INDEX: i32;
+    const VERTEX: i32;
+    const INDIRECT: i32;
+}
+
+pub trait ImageUsageConstants {
+    const TRANSFER_SRC: i32;
+    const TRANSFER_DST: i32;
+    const SAMPLED: i32;
+    const STORAGE: i32;
+    const COLOR_ATTACHMENT: i32;
+    const DEPTH_STENCIL_ATTACHMENT: i32;
+}
+
+pub trait FormatFeatureConstants {
+    const SAMPLED: i32;
+    const FILTERABLE: i32;
+    const COLOR_ATTACHMENT: i32;
+    const BLENDABLE: i32;
+    const DEPTH_STENCIL_ATTACHMENT: i32;
+    const STORAGE: i32;
+    const VERTEX_BUFFER: i32;
+    const BLIT_SRC: i32;
+    const BLIT_DST: i32;
+}
+
+pub trait AttachmentLoadOpConstants {
+    const LOAD: i32;
+    const CLEAR: i32;
+    const DONT_CARE: i32;
+}
+
+pub trait AttachmentStoreOpConstants {
+    const STORE: i32;
+    const DONT_CARE: i32;
+}
+
+pub trait VertexInputRateConstants {
+    const VERTEX: i32;
+    const INSTANCE: i32;
+}
+
 pub trait PrimitiveTopologyConstants {
     const POINT_LIST: i32;
     const LINE_LIST: i32;
@@ -25,6 +25,8 @@ pub trait GraphicsApi: Sized {
     type CommandPool: CommandPool<Self>;
     type CommandBuffer: CommandBuffer<Self>;
 
+    type Buffer: Buffer<Self>;
+
     type Swapchain: Swapchain<Self>;
     type Image: Image<Self>;
     type ImageView: ImageView<Self>;
@@ -35,6 +37,7 @@ pub trait GraphicsApi: Sized {
     type Framebuffer: Framebuffer<Self>;
 
     type PipelineLayout: PipelineLayout<Self>;
+    type PipelineCache: PipelineCache<Self>;
 
     // type VertexInputState: VertexInputStateCreateInfo<Self>;
     // type InputAssemblyState: InputAssemblyStateCreateInfo<Self>;
@@ -56,6 +59,18 @@ pub trait GraphicsApi: Sized {
     type CullModeConstants: constants::CullModeConstants;
     type FrontFaceConstants: constants::FrontFaceConstants;
     type PrimitiveTopologyConstants: constants::PrimitiveTopologyConstants;
+    type DescriptorTypeConstants: constants::DescriptorTypeConstants;
+    type ShaderStageConstants: constants::ShaderStageConstants;
+    type VertexInputRateConstants: constants::VertexInputRateConstants;
+    type ImageLayoutConstants: constants::ImageLayoutConstants;
+    type AccessConstants: constants::AccessConstants;
+    type PipelineStageConstants: constants::PipelineStageConstants;
+    type ImageAspectConstants: constants::ImageAspectConstants;
+    type BufferUsageConstants: constants::BufferUsageConstants;
+    type ImageUsageConstants: constants::ImageUsageConstants;
+    type AttachmentLoadOpConstants: constants::AttachmentLoadOpConstants;
+    type AttachmentStoreOpConstants: constants::AttachmentStoreOpConstants;
+    type FormatFeatureConstants: constants::FormatFeatureConstants;
 }
 
 pub trait ApiRoot<A: GraphicsApi>: Sized + Debug + Clone {
@@ -65,8 +80,10 @@ pub trait ApiRoot<A: GraphicsApi>: Sized + Debug + Clone {
 
 pub trait Device<A: GraphicsApi>: Sized + Debug + Clone {
     fn properties(&self) -> &A::DeviceProperties;
-    // fn features(&self) -> &A::DeviceFeatures;
-    //
+    fn features(&self) -> DeviceFeatureFlags;
+
+    /// Bytes of memory local to the device, which is shared system memory for integrated GPUs.
+    fn dedicated_memory(&self) -> u64;
 
     fn supports_surface(&self, surface: A::Surface) -> bool;
 
@@ -78,12 +95,53 @@ pub trait Device<A: GraphicsApi>: Sized + Debug + Clone {
     fn get_surface_formats(&self, surface: A::Surface) -> crate::Result<Vec<SurfaceFormat>>;
 
     fn get_surface_present_modes(&self, surface: A::Surface) -> crate::Result<Vec<PresentMode>>;
+
+    /// What the device supports for a format, empty for formats the backend can't represent.
+    fn format_capabilities(&self, format: Format) -> FormatCapabilities;
+
+    /// The first of `candidates` the device supports with every feature in `required`.
+    ///
+    /// `VERTEX_BUFFER` is looked up in the buffer features and every other feature in the
+    /// optimal tiling features. Formats the device doesn't support at all are skipped, even when
+    /// `required` is empty.
+    fn first_supported_format(
+        &self,
+        candidates: &[Format],
+        required: FormatFeatureFlags,
+    ) -> Option<Format> {
+        let buffer = required & FormatFeatureFlags::VERTEX_BUFFER;
+        let image = required - buffer;
+
+        candidates.iter().copied().find(|&format| {
+            let capabilities = self.format_capabilities(format);
+
+            capabilities != FormatCapabilities::default()
+                && capabilities.optimal_tiling.contains(image)
+                && capabilities.buffer.contains(buffer)
+        })
+    }
 }
 
 pub trait DeviceProperties<A: GraphicsApi>: Sized + Debug {
     fn name(&self) -> &str;
     fn device_type(&self) -> DeviceType;
     fn vendor(&self) -> Vendor;
+
+    /// The PCI vendor ID, or a Khronos vendor ID for devices without one.
+    fn vendor_id(&self) -> u32;
+    fn device_id(&self) -> u32;
+    fn driver_version(&self) -> DriverVersion;
+    fn api_version(&self) -> ApiVersion;
+    fn limits(&self) -> DeviceLimits;
+
+    /// Identifies pipeline cache data the device can load.
+    fn pipeline_cache_uuid(&self) -> [u8; 16];
+
+    /// The driver's name, if the backend reports one.
+    fn driver_name(&self) -> Option<&str>;
+
+    /// Free-form driver build information, if the backend reports any.
+    fn driver_info(&self) -> Option<&str>;
 }
 
 #[cfg(not(feature = "directx_api"))]
@@ -108,11 +166,29 @@ pub trait Context<A: GraphicsApi>: Sized + Debug + Clone {
     fn new(root: A::Root, device: A::Device, create_info: ContextCreateInfo)
         -> crate::Result<Self>;
 
+    /// The graphics queue, followed by a transfer queue without graphics support when the
+    /// device has one.
     fn queues(&self) -> &[<A as GraphicsApi>::Queue];
 }
 
 // #[cfg(not(feature = "directx"))]
-pub trait Queue<A: GraphicsApi>: Sized + Debug + Clone {}
+pub trait Queue<A: GraphicsApi>: Sized + Debug + Clone {
+    /// Index of the queue family the queue belongs to.
+    fn family_index(&self) -> u32;
+
+    fn flags(&self) -> QueueFlags;
+
+    /// Submits command buffers, signaling `fence` once they complete.
+    fn submit(&self, submit_info: &SubmitInfo, fence: Option<&A::Fence>) -> crate::Result<()>;
+
+    /// Queues an image for presentation, returning whether the swapchain is suboptimal.
+    ///
+    /// Fails with `Error::SwapchainOutOfDate` when the swapchain must be recreated.
+    fn present(&self, present_info: &PresentInfo) -> crate::Result<bool>;
+
+    /// Blocks until all work submitted to the queue has completed.
+    fn wait_idle(&self) -> crate::Result<()>;
+}
 
 pub trait CommandPool<A: GraphicsApi>: Sized + Debug + Clone {
     fn new(context: A::Context, create_info: CommandPoolCreateInfo) -> crate::Result<Self>;
@@ -125,6 +201,54 @@ pub trait CommandBuffer<A: GraphicsApi>: Sized + Debug + Clone {
         pool: A::CommandPool,
         create_info: CommandBufferAllocateInfo,
     ) -> crate::Result<Self>;
+
+    /// Starts recording, discarding anything previously recorded.
+    fn begin(&self) -> crate::Result<()>;
+
+    fn end(&self) -> crate::Result<()>;
+
+    /// Records every barrier in `dependency_info` as a single command.
+    fn pipeline_barrier(&self, dependency_info: &DependencyInfo) -> crate::Result<()>;
+
+    /// Begins rendering directly into image views, without a render pass or framebuffer.
+    ///
+    /// Pipelines used until `end_rendering` must be created with `PipelineTarget::Dynamic`.
+    fn begin_rendering(&self, rendering_info: &RenderingInfo) -> crate::Result<()>;
+
+    fn end_rendering(&self) -> crate::Result<()>;
+
+    /// Begins the first subpass of the framebuffer's render pass.
+    fn begin_render_pass(&self, begin_info: &RenderPassBeginInfo) -> crate::Result<()>;
+
+    /// Advances to the next subpass of the current render pass.
+    fn next_subpass(&self) -> crate::Result<()>;
+
+    fn end_render_pass(&self) -> crate::Result<()>;
+
+    fn copy_buffer(
+        &self,
+        src: &A::Buffer,
+        dst: &A::Buffer,
+        regions: &[BufferCopy],
+    ) -> crate::Result<()>;
+
+    /// Copies buffer contents into an image, which must be in `layout`.
+    fn copy_buffer_to_image(
+        &self,
+        src: &A::Buffer,
+        dst: &A::Image,
+        layout: ImageLayout,
+        regions: &[BufferImageCopy],
+    ) -> crate::Result<()>;
+
+    /// Copies image contents into a buffer. The image must be in `layout`.
+    fn copy_image_to_buffer(
+        &self,
+        src: &A::Image,
+        layout: ImageLayout,
+        dst: &A::Buffer,
+        regions: &[BufferImageCopy],
+    ) -> crate::Result<()>;
     //
     // fn record<T, F>(&mut self, f: F) -> crate::Result<T>
     // where
@@ -150,6 +274,8 @@ pub trait Swapchain<A: GraphicsApi>: Sized + Debug + Clone {
 
     fn images(&self) -> &[A::Image];
 
+    /// Fails with `Error::SwapchainOutOfDate` when the swapchain must be recreated, and with
+    /// `Error::Timeout` if no image became available in time.
     fn acquire_next_image(
         &self,
         timeout: Option<Duration>,
@@ -158,7 +284,30 @@ pub trait Swapchain<A: GraphicsApi>: Sized + Debug + Clone {
     ) -> crate::Result<u32>;
 }
 
-pub trait Image<A: GraphicsApi>: Sized + Debug + Clone {}
+pub trait Buffer<A: GraphicsApi>: Sized + Debug + Clone + PartialEq {
+    fn new(context: A::Context, create_info: BufferCreateInfo) -> crate::Result<Self>;
+
+    fn size(&self) -> u64;
+
+    /// Copies `data` into the buffer at `offset`.
+    ///
+    /// Fails with `Error::Unsupported` unless the buffer's memory is host visible.
+    fn write(&self, offset: u64, data: &[u8]) -> crate::Result<()>;
+
+    /// Copies the buffer's contents at `offset` into `data`.
+    ///
+    /// Fails with `Error::Unsupported` unless the buffer's memory is host visible.
+    fn read(&self, offset: u64, data: &mut [u8]) -> crate::Result<()>;
+}
+
+pub trait Image<A: GraphicsApi>: Sized + Debug + Clone + PartialEq {
+    fn new(context: A::Context, create_info: ImageCreateInfo) -> crate::Result<Self>;
+
+    fn format(&self) -> Format;
+    fn extent(&self) -> Extent2D;
+    fn mip_levels(&self) -> u32;
+    fn array_layers(&self) -> u32;
+}
 
 pub trait ImageView<A: GraphicsApi>: Sized + Debug + Clone {
     fn new(
@@ -181,10 +330,21 @@ pub trait PipelineLayout<A: GraphicsApi>: Sized + Debug + Clone {
     fn new(context: A::Context, create_info: PipelineLayoutCreateInfo) -> crate::Result<Self>;
 }
 
-pub trait Shader<A: GraphicsApi>: Sized + Debug + Clone {
+pub trait PipelineCache<A: GraphicsApi>: Sized + Debug + Clone {
+    fn new(context: A::Context, create_info: PipelineCacheCreateInfo) -> crate::Result<Self>;
+
+    /// Serializes the cache so it can be passed back as `initial_data` on a later run.
+    fn data(&self) -> crate::Result<Vec<u8>>;
+}
+
+/// Shaders compare equal when they refer to the same api object.
+pub trait Shader<A: GraphicsApi>: Sized + Debug + Clone + PartialEq {
     // fn new<'a>(context: A::Context, code: &A::ShaderCode<'a>) -> crate::Result<Self>;
 
     fn from_code(context: A::Context, code: ShaderCode) -> crate::Result<Self>;
+
+    /// Resources declared by the shader, if its code is SPIR-V.
+    fn reflection(&self) -> Option<&crate::spirv::ShaderReflection>;
 }
 
 // pub trait VertexInputStateCreateInfo<A: GraphicsApi>: Sized + Debug + Default {}
@@ -193,10 +353,29 @@ pub trait Shader<A: GraphicsApi>: Sized + Debug + Clone {
 //
 pub trait GraphicsPipeline<A: GraphicsApi>: Sized + Debug + Clone {
     fn new(context: A::Context, create_info: GraphicsPipelineCreateInfo) -> crate::Result<Self>;
+
+    /// Creates several pipelines at once, returning a result for each create info in order.
+    ///
+    /// A pipeline that fails to build does not prevent the rest of the batch from being created.
+    fn new_batch(
+        context: A::Context,
+        create_infos: &[GraphicsPipelineCreateInfo],
+    ) -> Vec<crate::Result<Self>> {
+        create_infos
+            .iter()
+            .cloned()
+            .map(|create_info| Self::new(context.clone(), create_info))
+            .collect()
+    }
 }
 
 pub trait Fence<A: GraphicsApi>: Sized + Debug + Clone {
     fn new(context: A::Context, create_info: FenceCreateInfo) -> crate::Result<Self>;
+
+    /// Blocks until the fence is signaled, returning `false` if `timeout` elapsed first.
+    fn wait(&self, timeout: Option<Duration>) -> crate::Result<bool>;
+
+    fn reset(&self) -> crate::Result<()>;
 }
 
 pub trait Semaphore<A: GraphicsApi>: Sized + Debug + Clone {
@@ -0,0 +1,149 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::api::vulkan::*;
+use crate::prelude::GraphicsApi;
+use crate::{BufferCreateInfo, MemoryLocation};
+use std::fmt::{Debug, Formatter};
+use vulkan_sys::*;
+
+struct VulkanBufferOwnership {
+    handle: VkBuffer,
+    size: u64,
+    location: MemoryLocation,
+    // destroyed after the buffer
+    memory: VulkanMemory,
+    device: VulkanDevice,
+}
+
+impl Drop for VulkanBufferOwnership {
+    fn drop(&mut self) {
+        wrapper::destroy_buffer(vkDestroyBuffer, self.device.handle(), self.handle, None);
+    }
+}
+
+#[derive(Clone)]
+pub struct VulkanBuffer {
+    handle: VkBuffer,
+    ownership: Ownership<VulkanBufferOwnership>,
+}
+
+impl Debug for VulkanBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(std::any::type_name::<Self>())
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+impl PartialEq for VulkanBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl VulkanObject for VulkanBuffer {
+    type Handle = VkBuffer;
+
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_BUFFER;
+
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+impl VulkanDeviceObject for VulkanBuffer {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
+    }
+}
+
+impl crate::api::traits::Buffer<VulkanApi> for VulkanBuffer {
+    fn new(
+        context: <VulkanApi as GraphicsApi>::Context,
+        create_info: BufferCreateInfo,
+    ) -> crate::Result<Self> {
+        let info = VkBufferCreateInfo {
+            sType: VK_STRUCTURE_TYPE_BUFFER_CREATE_INFO,
+            pNext: std::ptr::null(),
+            flags: 0,
+            size: create_info.size,
+            usage: create_info.usage.into(),
+            sharingMode: VK_SHARING_MODE_EXCLUSIVE,
+            queueFamilyIndexCount: 0,
+            pQueueFamilyIndices: std::ptr::null(),
+        };
+
+        let handle = wrapper::create_buffer(vkCreateBuffer, context.handle(), &info, None)?;
+
+        let requirements = wrapper::get_buffer_memory_requirements(
+            vkGetBufferMemoryRequirements,
+            context.handle(),
+            handle,
+        );
+
+        let memory = VulkanMemory::allocate(&context, &requirements, create_info.location)
+            .and_then(|memory| {
+                wrapper::bind_buffer_memory(
+                    vkBindBufferMemory,
+                    context.handle(),
+                    handle,
+                    memory.handle(),
+                    0,
+                )?;
+                Ok(memory)
+            });
+
+        let memory = match memory {
+            Ok(memory) => memory,
+            Err(error) => {
+                wrapper::destroy_buffer(vkDestroyBuffer, context.handle(), handle, None);
+                return Err(error);
+            }
+        };
+
+        let ownership = Ownership::new(VulkanBufferOwnership {
+            handle,
+            size: create_info.size,
+            location: create_info.location,
+            memory,
+            device: context,
+        });
+
+        let buffer = Self { handle, ownership };
+
+        if let Some(label) = create_info.label {
+            buffer.set_debug_name(label)?;
+        }
+
+        Ok(buffer)
+    }
+
+    fn size(&self) -> u64 {
+        self.ownership.size
+    }
+
+    fn write(&self, offset: u64, data: &[u8]) -> crate::Result<()> {
+        if self.ownership.location == MemoryLocation::DeviceLocal {
+            return Err(crate::Error::Unsupported(
+                "buffer memory is not host visible",
+            ));
+        }
+
+        assert!(offset + data.len() as u64 <= self.ownership.size);
+
+        self.ownership.memory.write(offset, data)
+    }
+
+    fn read(&self, offset: u64, data: &mut [u8]) -> crate::Result<()> {
+        if self.ownership.location == MemoryLocation::DeviceLocal {
+            return Err(crate::Error::Unsupported(
+                "buffer memory is not host visible",
+            ));
+        }
+
+        assert!(offset + data.len() as u64 <= self.ownership.size);
+
+        self.ownership.memory.read(offset, data)
+    }
+}
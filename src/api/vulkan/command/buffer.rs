@@ -3,7 +3,11 @@
 
 use crate::api::vulkan::{Ownership, VulkanApi, VulkanDevice, VulkanDeviceObject, VulkanObject};
 use crate::prelude::GraphicsApi;
-use crate::{CommandBufferAllocateInfo, RenderPassBeginInfo};
+use crate::{
+    BufferBarrier, BufferCopy, BufferImageCopy, CommandBufferAllocateInfo, DependencyInfo,
+    ImageBarrier, ImageLayout, MemoryBarrier, RenderPassBeginInfo, RenderingAttachment,
+    RenderingInfo,
+};
 use std::fmt::{Debug, Formatter};
 pub use vulkan_sys::*;
 
@@ -26,9 +30,17 @@ impl Debug for VulkanCommandBuffer {
     }
 }
 
+impl VulkanDeviceObject for VulkanCommandBuffer {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
+    }
+}
+
 impl VulkanObject for VulkanCommandBuffer {
     type Handle = VkCommandBuffer;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_COMMAND_BUFFER;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -64,7 +76,195 @@ impl crate::api::traits::CommandBuffer<VulkanApi> for VulkanCommandBuffer {
             device: device.clone(),
         });
 
-        Ok(Self { handle, ownership })
+        let buffer = Self { handle, ownership };
+
+        if let Some(label) = create_info.label {
+            buffer.set_debug_name(label)?;
+        }
+
+        Ok(buffer)
+    }
+
+    fn begin(&self) -> crate::Result<()> {
+        let begin_info = VkCommandBufferBeginInfo {
+            sType: VK_STRUCTURE_TYPE_COMMAND_BUFFER_BEGIN_INFO,
+            pNext: std::ptr::null(),
+            flags: VK_COMMAND_BUFFER_USAGE_ONE_TIME_SUBMIT_BIT as _,
+            pInheritanceInfo: std::ptr::null(),
+        };
+
+        Ok(wrapper::begin_command_buffer(
+            vkBeginCommandBuffer,
+            self.handle,
+            &begin_info,
+        )?)
+    }
+
+    fn end(&self) -> crate::Result<()> {
+        Ok(wrapper::end_command_buffer(
+            vkEndCommandBuffer,
+            self.handle,
+        )?)
+    }
+
+    fn pipeline_barrier(&self, dependency_info: &DependencyInfo) -> crate::Result<()> {
+        match self.device().cmd_pipeline_barrier2() {
+            Some(cmd_pipeline_barrier2) => {
+                self.pipeline_barrier2(cmd_pipeline_barrier2, dependency_info)
+            }
+            None => self.pipeline_barrier1(dependency_info),
+        }
+
+        Ok(())
+    }
+
+    fn begin_rendering(&self, rendering_info: &RenderingInfo) -> crate::Result<()> {
+        let functions = self.device().dynamic_rendering()?;
+
+        let color_attachments: Vec<VkRenderingAttachmentInfo> = rendering_info
+            .color_attachments
+            .iter()
+            .map(Into::into)
+            .collect();
+
+        let depth_attachment: Option<VkRenderingAttachmentInfo> =
+            rendering_info.depth_attachment.as_ref().map(Into::into);
+
+        let stencil_attachment: Option<VkRenderingAttachmentInfo> =
+            rendering_info.stencil_attachment.as_ref().map(Into::into);
+
+        let native = VkRenderingInfo {
+            sType: VK_STRUCTURE_TYPE_RENDERING_INFO,
+            pNext: std::ptr::null(),
+            flags: 0,
+            renderArea: rendering_info.render_area.into(),
+            layerCount: 1,
+            viewMask: 0,
+            colorAttachmentCount: color_attachments.len() as u32,
+            pColorAttachments: color_attachments.as_ptr(),
+            pDepthAttachment: depth_attachment
+                .as_ref()
+                .map_or(std::ptr::null(), |attachment| attachment as *const _),
+            pStencilAttachment: stencil_attachment
+                .as_ref()
+                .map_or(std::ptr::null(), |attachment| attachment as *const _),
+        };
+
+        unsafe { (functions.cmd_begin_rendering)(self.handle, &native) };
+
+        Ok(())
+    }
+
+    fn end_rendering(&self) -> crate::Result<()> {
+        let functions = self.device().dynamic_rendering()?;
+        unsafe { (functions.cmd_end_rendering)(self.handle) };
+
+        Ok(())
+    }
+
+    fn begin_render_pass(&self, begin_info: &RenderPassBeginInfo) -> crate::Result<()> {
+        let clear_values: Vec<VkClearValue> = begin_info
+            .clear_values
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect();
+
+        let native = VkRenderPassBeginInfo {
+            sType: VK_STRUCTURE_TYPE_RENDER_PASS_BEGIN_INFO,
+            pNext: std::ptr::null(),
+            renderPass: begin_info.framebuffer.render_pass().handle(),
+            framebuffer: begin_info.framebuffer.handle(),
+            renderArea: begin_info.render_area.into(),
+            clearValueCount: clear_values.len() as u32,
+            pClearValues: clear_values.as_ptr(),
+        };
+
+        wrapper::cmd_begin_render_pass(
+            vkCmdBeginRenderPass,
+            self.handle,
+            &native,
+            VK_SUBPASS_CONTENTS_INLINE,
+        );
+
+        Ok(())
+    }
+
+    fn next_subpass(&self) -> crate::Result<()> {
+        wrapper::cmd_next_subpass(vkCmdNextSubpass, self.handle, VK_SUBPASS_CONTENTS_INLINE);
+        Ok(())
+    }
+
+    fn end_render_pass(&self) -> crate::Result<()> {
+        wrapper::cmd_end_render_pass(vkCmdEndRenderPass, self.handle);
+        Ok(())
+    }
+
+    fn copy_buffer(
+        &self,
+        src: &<VulkanApi as GraphicsApi>::Buffer,
+        dst: &<VulkanApi as GraphicsApi>::Buffer,
+        regions: &[BufferCopy],
+    ) -> crate::Result<()> {
+        let regions: Vec<VkBufferCopy> = regions.iter().map(Into::into).collect();
+
+        unsafe {
+            vkCmdCopyBuffer(
+                self.handle,
+                src.handle(),
+                dst.handle(),
+                regions.len() as u32,
+                regions.as_ptr(),
+            )
+        }
+
+        Ok(())
+    }
+
+    fn copy_buffer_to_image(
+        &self,
+        src: &<VulkanApi as GraphicsApi>::Buffer,
+        dst: &<VulkanApi as GraphicsApi>::Image,
+        layout: ImageLayout,
+        regions: &[BufferImageCopy],
+    ) -> crate::Result<()> {
+        let regions: Vec<VkBufferImageCopy> = regions.iter().map(Into::into).collect();
+
+        unsafe {
+            vkCmdCopyBufferToImage(
+                self.handle,
+                src.handle(),
+                dst.handle(),
+                layout.into(),
+                regions.len() as u32,
+                regions.as_ptr(),
+            )
+        }
+
+        Ok(())
+    }
+
+    fn copy_image_to_buffer(
+        &self,
+        src: &<VulkanApi as GraphicsApi>::Image,
+        layout: ImageLayout,
+        dst: &<VulkanApi as GraphicsApi>::Buffer,
+        regions: &[BufferImageCopy],
+    ) -> crate::Result<()> {
+        let regions: Vec<VkBufferImageCopy> = regions.iter().map(Into::into).collect();
+
+        unsafe {
+            vkCmdCopyImageToBuffer(
+                self.handle,
+                src.handle(),
+                layout.into(),
+                dst.handle(),
+                regions.len() as u32,
+                regions.as_ptr(),
+            )
+        }
+
+        Ok(())
     }
 
     // fn allocate(
@@ -126,6 +326,278 @@ impl crate::api::traits::CommandBuffer<VulkanApi> for VulkanCommandBuffer {
     // }
 }
 
+/*
+   Barriers
+*/
+
+impl VulkanCommandBuffer {
+    fn pipeline_barrier2(
+        &self,
+        cmd_pipeline_barrier2: unsafe extern "C" fn(VkCommandBuffer, *const VkDependencyInfo),
+        dependency_info: &DependencyInfo,
+    ) {
+        let memory_barriers: Vec<VkMemoryBarrier2> = dependency_info
+            .memory_barriers
+            .iter()
+            .map(Into::into)
+            .collect();
+
+        let buffer_barriers: Vec<VkBufferMemoryBarrier2> = dependency_info
+            .buffer_barriers
+            .iter()
+            .map(Into::into)
+            .collect();
+
+        let image_barriers: Vec<VkImageMemoryBarrier2> = dependency_info
+            .image_barriers
+            .iter()
+            .map(Into::into)
+            .collect();
+
+        let native = VkDependencyInfo {
+            sType: VK_STRUCTURE_TYPE_DEPENDENCY_INFO,
+            pNext: std::ptr::null(),
+            dependencyFlags: 0,
+            memoryBarrierCount: memory_barriers.len() as u32,
+            pMemoryBarriers: memory_barriers.as_ptr(),
+            bufferMemoryBarrierCount: buffer_barriers.len() as u32,
+            pBufferMemoryBarriers: buffer_barriers.as_ptr(),
+            imageMemoryBarrierCount: image_barriers.len() as u32,
+            pImageMemoryBarriers: image_barriers.as_ptr(),
+        };
+
+        unsafe { cmd_pipeline_barrier2(self.handle, &native) }
+    }
+
+    /// Records the barriers without synchronization2, which takes a single pair of stage masks
+    /// for the whole command.
+    fn pipeline_barrier1(&self, dependency_info: &DependencyInfo) {
+        let mut src_stages = crate::PipelineStageFlags::empty();
+        let mut dst_stages = crate::PipelineStageFlags::empty();
+
+        let mut add_stages = |src, dst| {
+            src_stages |= src;
+            dst_stages |= dst;
+        };
+
+        let memory_barriers: Vec<VkMemoryBarrier> = dependency_info
+            .memory_barriers
+            .iter()
+            .inspect(|barrier| add_stages(barrier.src_stages, barrier.dst_stages))
+            .map(Into::into)
+            .collect();
+
+        let buffer_barriers: Vec<VkBufferMemoryBarrier> = dependency_info
+            .buffer_barriers
+            .iter()
+            .inspect(|barrier| add_stages(barrier.src_stages, barrier.dst_stages))
+            .map(Into::into)
+            .collect();
+
+        let image_barriers: Vec<VkImageMemoryBarrier> = dependency_info
+            .image_barriers
+            .iter()
+            .inspect(|barrier| add_stages(barrier.src_stages, barrier.dst_stages))
+            .map(Into::into)
+            .collect();
+
+        // empty stage masks are only valid with synchronization2
+        if src_stages.is_empty() {
+            src_stages = crate::PipelineStageFlags::TOP_OF_PIPE;
+        }
+        if dst_stages.is_empty() {
+            dst_stages = crate::PipelineStageFlags::BOTTOM_OF_PIPE;
+        }
+
+        wrapper::cmd_pipeline_barrier(
+            vkCmdPipelineBarrier,
+            self.handle,
+            src_stages.into(),
+            dst_stages.into(),
+            0,
+            &memory_barriers,
+            &buffer_barriers,
+            &image_barriers,
+        );
+    }
+}
+
+fn src_queue_family(queue_transfer: Option<(u32, u32)>) -> u32 {
+    queue_transfer.map_or(VK_QUEUE_FAMILY_IGNORED, |(src, _)| src)
+}
+
+fn dst_queue_family(queue_transfer: Option<(u32, u32)>) -> u32 {
+    queue_transfer.map_or(VK_QUEUE_FAMILY_IGNORED, |(_, dst)| dst)
+}
+
+impl Into<VkImageSubresourceRange> for crate::ImageSubresourceRange {
+    fn into(self) -> VkImageSubresourceRange {
+        VkImageSubresourceRange {
+            aspectMask: self.aspect.into(),
+            baseMipLevel: self.base_mip_level,
+            levelCount: self.level_count,
+            baseArrayLayer: self.base_array_layer,
+            layerCount: self.layer_count,
+        }
+    }
+}
+
+impl Into<VkMemoryBarrier2> for &MemoryBarrier {
+    fn into(self) -> VkMemoryBarrier2 {
+        VkMemoryBarrier2 {
+            sType: VK_STRUCTURE_TYPE_MEMORY_BARRIER_2,
+            pNext: std::ptr::null(),
+            srcStageMask: self.src_stages.into(),
+            srcAccessMask: self.src_access.into(),
+            dstStageMask: self.dst_stages.into(),
+            dstAccessMask: self.dst_access.into(),
+        }
+    }
+}
+
+impl Into<VkBufferMemoryBarrier2> for &BufferBarrier<'_> {
+    fn into(self) -> VkBufferMemoryBarrier2 {
+        VkBufferMemoryBarrier2 {
+            sType: VK_STRUCTURE_TYPE_BUFFER_MEMORY_BARRIER_2,
+            pNext: std::ptr::null(),
+            srcStageMask: self.src_stages.into(),
+            srcAccessMask: self.src_access.into(),
+            dstStageMask: self.dst_stages.into(),
+            dstAccessMask: self.dst_access.into(),
+            srcQueueFamilyIndex: src_queue_family(self.queue_transfer),
+            dstQueueFamilyIndex: dst_queue_family(self.queue_transfer),
+            buffer: self.buffer.handle(),
+            offset: self.offset,
+            size: self.size.unwrap_or(VK_WHOLE_SIZE),
+        }
+    }
+}
+
+impl Into<VkImageMemoryBarrier2> for &ImageBarrier<'_> {
+    fn into(self) -> VkImageMemoryBarrier2 {
+        VkImageMemoryBarrier2 {
+            sType: VK_STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER_2,
+            pNext: std::ptr::null(),
+            srcStageMask: self.src_stages.into(),
+            srcAccessMask: self.src_access.into(),
+            dstStageMask: self.dst_stages.into(),
+            dstAccessMask: self.dst_access.into(),
+            oldLayout: self.old_layout.into(),
+            newLayout: self.new_layout.into(),
+            srcQueueFamilyIndex: src_queue_family(self.queue_transfer),
+            dstQueueFamilyIndex: dst_queue_family(self.queue_transfer),
+            image: self.image.handle(),
+            subresourceRange: self.subresource_range.into(),
+        }
+    }
+}
+
+impl Into<VkMemoryBarrier> for &MemoryBarrier {
+    fn into(self) -> VkMemoryBarrier {
+        VkMemoryBarrier {
+            sType: VK_STRUCTURE_TYPE_MEMORY_BARRIER,
+            pNext: std::ptr::null(),
+            srcAccessMask: self.src_access.into(),
+            dstAccessMask: self.dst_access.into(),
+        }
+    }
+}
+
+impl Into<VkBufferMemoryBarrier> for &BufferBarrier<'_> {
+    fn into(self) -> VkBufferMemoryBarrier {
+        VkBufferMemoryBarrier {
+            sType: VK_STRUCTURE_TYPE_BUFFER_MEMORY_BARRIER,
+            pNext: std::ptr::null(),
+            srcAccessMask: self.src_access.into(),
+            dstAccessMask: self.dst_access.into(),
+            srcQueueFamilyIndex: src_queue_family(self.queue_transfer),
+            dstQueueFamilyIndex: dst_queue_family(self.queue_transfer),
+            buffer: self.buffer.handle(),
+            offset: self.offset,
+            size: self.size.unwrap_or(VK_WHOLE_SIZE),
+        }
+    }
+}
+
+impl Into<VkImageMemoryBarrier> for &ImageBarrier<'_> {
+    fn into(self) -> VkImageMemoryBarrier {
+        VkImageMemoryBarrier {
+            sType: VK_STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER,
+            pNext: std::ptr::null(),
+            srcAccessMask: self.src_access.into(),
+            dstAccessMask: self.dst_access.into(),
+            oldLayout: self.old_layout.into(),
+            newLayout: self.new_layout.into(),
+            srcQueueFamilyIndex: src_queue_family(self.queue_transfer),
+            dstQueueFamilyIndex: dst_queue_family(self.queue_transfer),
+            image: self.image.handle(),
+            subresourceRange: self.subresource_range.into(),
+        }
+    }
+}
+
+/*
+   Copies
+*/
+
+impl Into<VkBufferCopy> for &BufferCopy {
+    fn into(self) -> VkBufferCopy {
+        VkBufferCopy {
+            srcOffset: self.src_offset,
+            dstOffset: self.dst_offset,
+            size: self.size,
+        }
+    }
+}
+
+impl Into<VkBufferImageCopy> for &BufferImageCopy {
+    fn into(self) -> VkBufferImageCopy {
+        VkBufferImageCopy {
+            bufferOffset: self.buffer_offset,
+            // tightly packed
+            bufferRowLength: 0,
+            bufferImageHeight: 0,
+            imageSubresource: VkImageSubresourceLayers {
+                aspectMask: self.aspect.into(),
+                mipLevel: self.mip_level,
+                baseArrayLayer: self.base_array_layer,
+                layerCount: self.layer_count,
+            },
+            imageOffset: VkOffset3D {
+                x: self.image_offset.x,
+                y: self.image_offset.y,
+                z: 0,
+            },
+            imageExtent: VkExtent3D {
+                width: self.image_extent.width,
+                height: self.image_extent.height,
+                depth: 1,
+            },
+        }
+    }
+}
+
+/*
+   Dynamic Rendering
+*/
+
+impl Into<VkRenderingAttachmentInfo> for &RenderingAttachment<'_> {
+    fn into(self) -> VkRenderingAttachmentInfo {
+        VkRenderingAttachmentInfo {
+            sType: VK_STRUCTURE_TYPE_RENDERING_ATTACHMENT_INFO,
+            pNext: std::ptr::null(),
+            imageView: self.view.handle(),
+            imageLayout: self.layout.into(),
+            resolveMode: VK_RESOLVE_MODE_NONE,
+            resolveImageView: std::ptr::null_mut(),
+            resolveImageLayout: VK_IMAGE_LAYOUT_UNDEFINED,
+            loadOp: self.load_op.into(),
+            storeOp: self.store_op.into(),
+            clearValue: self.clear_value.into(),
+        }
+    }
+}
+
 // pub struct VulkanCommandBufferRecordContext {
 //     buffer: VkCommandBuffer,
 // }
@@ -42,6 +42,8 @@ impl Debug for VulkanCommandPool {
 impl VulkanObject for VulkanCommandPool {
     type Handle = VkCommandPool;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_COMMAND_POOL;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -58,6 +60,8 @@ impl crate::api::traits::CommandPool<VulkanApi> for VulkanCommandPool {
         context: <VulkanApi as GraphicsApi>::Context,
         create_info: CommandPoolCreateInfo,
     ) -> crate::Result<Self> {
+        let label = create_info.label;
+
         let mut flags = {
             let mut f = 0;
             create_info
@@ -73,7 +77,7 @@ impl crate::api::traits::CommandPool<VulkanApi> for VulkanCommandPool {
             sType: VK_STRUCTURE_TYPE_COMMAND_POOL_CREATE_INFO,
             pNext: std::ptr::null(),
             flags,
-            queueFamilyIndex: 0,
+            queueFamilyIndex: create_info.queue_family_index,
         };
 
         let handle = wrapper::create_command_pool(
@@ -88,6 +92,12 @@ impl crate::api::traits::CommandPool<VulkanApi> for VulkanCommandPool {
             device: context.clone(),
         });
 
-        Ok(VulkanCommandPool { handle, ownership })
+        let pool = VulkanCommandPool { handle, ownership };
+
+        if let Some(label) = label {
+            pool.set_debug_name(label)?;
+        }
+
+        Ok(pool)
     }
 }
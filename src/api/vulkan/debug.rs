@@ -0,0 +1,224 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::api::vulkan::*;
+use std::ffi::CString;
+
+use vulkan_sys::*;
+
+/*
+   Debug Utils Functions
+*/
+
+#[cfg(feature = "validation")]
+pub(crate) struct DebugUtilsFunctions {
+    set_object_name:
+        unsafe extern "C" fn(VkDevice, *const VkDebugUtilsObjectNameInfoEXT) -> VkResult,
+    cmd_begin_label: unsafe extern "C" fn(VkCommandBuffer, *const VkDebugUtilsLabelEXT),
+    cmd_end_label: unsafe extern "C" fn(VkCommandBuffer),
+    cmd_insert_label: unsafe extern "C" fn(VkCommandBuffer, *const VkDebugUtilsLabelEXT),
+    queue_begin_label: unsafe extern "C" fn(VkQueue, *const VkDebugUtilsLabelEXT),
+    queue_end_label: unsafe extern "C" fn(VkQueue),
+    queue_insert_label: unsafe extern "C" fn(VkQueue, *const VkDebugUtilsLabelEXT),
+}
+
+#[cfg(feature = "validation")]
+impl DebugUtilsFunctions {
+    /// Loads the `VK_EXT_debug_utils` functions, or `None` when the extension isn't available.
+    pub(crate) fn load(instance: VkInstance) -> Option<Self> {
+        Some(Self {
+            set_object_name: wrapper::SetDebugUtilsObjectNameEXT::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+            cmd_begin_label: wrapper::CmdBeginDebugUtilsLabelEXT::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+            cmd_end_label: wrapper::CmdEndDebugUtilsLabelEXT::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+            cmd_insert_label: wrapper::CmdInsertDebugUtilsLabelEXT::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+            queue_begin_label: wrapper::QueueBeginDebugUtilsLabelEXT::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+            queue_end_label: wrapper::QueueEndDebugUtilsLabelEXT::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+            queue_insert_label: wrapper::QueueInsertDebugUtilsLabelEXT::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+        })
+    }
+}
+
+/// Converts a name for the api, dropping interior NULs which C strings can't hold.
+#[cfg(feature = "validation")]
+fn native_name(name: &str) -> CString {
+    CString::new(name.replace('\0', "")).unwrap()
+}
+
+/*
+   Object Names
+*/
+
+impl VulkanDevice {
+    /// Attaches a debug name to an object created by this device.
+    ///
+    /// Names are only forwarded to the api when the `validation` feature is enabled and
+    /// `VK_EXT_debug_utils` is available, and are otherwise ignored.
+    pub(crate) fn set_object_name(
+        &self,
+        object_type: VkObjectType,
+        object_handle: u64,
+        name: &str,
+    ) -> crate::Result<()> {
+        #[cfg(feature = "validation")]
+        if let Some(debug_utils) = self.instance().debug_utils() {
+            let name = native_name(name);
+
+            let name_info = VkDebugUtilsObjectNameInfoEXT {
+                sType: VK_STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_NAME_INFO_EXT,
+                pNext: std::ptr::null(),
+                objectType: object_type,
+                objectHandle: object_handle,
+                pObjectName: name.as_ptr(),
+            };
+
+            wrapper::set_debug_utils_object_name_ext(
+                debug_utils.set_object_name,
+                self.handle(),
+                &name_info,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/*
+   Labels
+*/
+
+#[derive(Debug, Copy, Clone)]
+pub struct DebugLabel<'a> {
+    pub name: &'a str,
+    pub color: [f32; 4],
+}
+
+impl<'a> DebugLabel<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            color: [0.0; 4],
+        }
+    }
+}
+
+#[cfg(feature = "validation")]
+fn with_native_label<F: FnOnce(&VkDebugUtilsLabelEXT)>(label: DebugLabel, f: F) {
+    let name = native_name(label.name);
+
+    let native = VkDebugUtilsLabelEXT {
+        sType: VK_STRUCTURE_TYPE_DEBUG_UTILS_LABEL_EXT,
+        pNext: std::ptr::null(),
+        pLabelName: name.as_ptr(),
+        color: label.color,
+    };
+
+    f(&native)
+}
+
+/// Objects that debug labels can be recorded into.
+///
+/// Labels are only forwarded to the api when the `validation` feature is enabled and
+/// `VK_EXT_debug_utils` is available, and are otherwise ignored.
+pub trait VulkanDebugLabels: Sized {
+    fn begin_debug_label(&self, label: DebugLabel);
+    fn end_debug_label(&self);
+    fn insert_debug_label(&self, label: DebugLabel);
+
+    /// Begins a label that is ended when the returned scope is dropped.
+    fn debug_label_scope(&self, label: DebugLabel) -> DebugLabelScope<'_, Self> {
+        self.begin_debug_label(label);
+        DebugLabelScope { target: self }
+    }
+}
+
+pub struct DebugLabelScope<'a, T: VulkanDebugLabels> {
+    target: &'a T,
+}
+
+impl<T: VulkanDebugLabels> Drop for DebugLabelScope<'_, T> {
+    fn drop(&mut self) {
+        self.target.end_debug_label();
+    }
+}
+
+impl VulkanDebugLabels for VulkanCommandBuffer {
+    fn begin_debug_label(&self, label: DebugLabel) {
+        #[cfg(feature = "validation")]
+        if let Some(debug_utils) = self.instance().debug_utils() {
+            with_native_label(label, |native| unsafe {
+                (debug_utils.cmd_begin_label)(self.handle(), native)
+            });
+        }
+    }
+
+    fn end_debug_label(&self) {
+        #[cfg(feature = "validation")]
+        if let Some(debug_utils) = self.instance().debug_utils() {
+            unsafe { (debug_utils.cmd_end_label)(self.handle()) }
+        }
+    }
+
+    fn insert_debug_label(&self, label: DebugLabel) {
+        #[cfg(feature = "validation")]
+        if let Some(debug_utils) = self.instance().debug_utils() {
+            with_native_label(label, |native| unsafe {
+                (debug_utils.cmd_insert_label)(self.handle(), native)
+            });
+        }
+    }
+}
+
+// labels on a queue whose device was dropped are ignored
+impl VulkanDebugLabels for VulkanQueue {
+    fn begin_debug_label(&self, label: DebugLabel) {
+        #[cfg(feature = "validation")]
+        if let Some(device) = self.device() {
+            if let Some(debug_utils) = device.instance().debug_utils() {
+                with_native_label(label, |native| unsafe {
+                    (debug_utils.queue_begin_label)(self.handle(), native)
+                });
+            }
+        }
+    }
+
+    fn end_debug_label(&self) {
+        #[cfg(feature = "validation")]
+        if let Some(device) = self.device() {
+            if let Some(debug_utils) = device.instance().debug_utils() {
+                unsafe { (debug_utils.queue_end_label)(self.handle()) }
+            }
+        }
+    }
+
+    fn insert_debug_label(&self, label: DebugLabel) {
+        #[cfg(feature = "validation")]
+        if let Some(device) = self.device() {
+            if let Some(debug_utils) = device.instance().debug_utils() {
+                with_native_label(label, |native| unsafe {
+                    (debug_utils.queue_insert_label)(self.handle(), native)
+                });
+            }
+        }
+    }
+}
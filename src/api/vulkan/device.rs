@@ -3,9 +3,10 @@
 
 use crate::api::traits::*;
 use crate::api::vulkan::{
-    Ownership, VulkanApi, VulkanInstance, VulkanInstanceObject, VulkanObject, VulkanPhysicalDevice,
+    check, Ownership, VulkanApi, VulkanHandle, VulkanInstance, VulkanInstanceObject, VulkanObject,
+    VulkanPhysicalDevice,
 };
-use crate::ContextCreateInfo;
+use crate::{ContextCreateInfo, PresentInfo, SubmitInfo};
 use std::any::type_name;
 use std::fmt::{Debug, Formatter};
 use std::ptr::{null, null_mut};
@@ -18,6 +19,28 @@ struct VulkanDeviceOwnership {
     instance: VulkanInstance,
     physical_device: VulkanPhysicalDevice,
     queues: Vec<<VulkanApi as GraphicsApi>::Queue>,
+    /// Set when `VK_KHR_synchronization2` is enabled.
+    cmd_pipeline_barrier2: Option<unsafe extern "C" fn(VkCommandBuffer, *const VkDependencyInfo)>,
+    /// Set when `VK_KHR_dynamic_rendering` is enabled.
+    dynamic_rendering: Option<DynamicRenderingFunctions>,
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct DynamicRenderingFunctions {
+    pub(crate) cmd_begin_rendering: unsafe extern "C" fn(VkCommandBuffer, *const VkRenderingInfo),
+    pub(crate) cmd_end_rendering: unsafe extern "C" fn(VkCommandBuffer),
+}
+
+impl DynamicRenderingFunctions {
+    fn load(instance: VkInstance) -> Option<Self> {
+        Some(Self {
+            cmd_begin_rendering: wrapper::CmdBeginRenderingKHR::load(
+                vkGetInstanceProcAddr,
+                instance,
+            )?,
+            cmd_end_rendering: wrapper::CmdEndRenderingKHR::load(vkGetInstanceProcAddr, instance)?,
+        })
+    }
 }
 
 impl Drop for VulkanDeviceOwnership {
@@ -43,6 +66,8 @@ impl Debug for VulkanDevice {
 impl VulkanObject for VulkanDevice {
     type Handle = VkDevice;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_DEVICE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -54,11 +79,55 @@ impl VulkanInstanceObject for VulkanDevice {
     }
 }
 
+impl VulkanDevice {
+    pub(crate) fn physical_device(&self) -> &VulkanPhysicalDevice {
+        &self.ownership.physical_device
+    }
+
+    pub(crate) fn cmd_pipeline_barrier2(
+        &self,
+    ) -> Option<unsafe extern "C" fn(VkCommandBuffer, *const VkDependencyInfo)> {
+        self.ownership.cmd_pipeline_barrier2
+    }
+
+    /// The dynamic rendering commands, or an error if the device doesn't support them.
+    pub(crate) fn dynamic_rendering(&self) -> crate::Result<DynamicRenderingFunctions> {
+        self.ownership
+            .dynamic_rendering
+            .ok_or(crate::Error::MissingExtension("VK_KHR_dynamic_rendering"))
+    }
+
+    /// Names the device in validation messages and debugging tools.
+    pub fn set_debug_name(&self, name: &str) -> crate::Result<()> {
+        self.set_object_name(Self::OBJECT_TYPE, self.handle.as_raw(), name)
+    }
+}
+
 fn get_device_layers() -> Vec<*const std::ffi::c_char> {
     Vec::new()
 }
 
-fn get_device_extensions() -> Vec<*const std::ffi::c_char> {
+/// Optional extensions enabled when the device supports them.
+struct OptionalExtensions {
+    synchronization2: bool,
+    dynamic_rendering: bool,
+}
+
+impl OptionalExtensions {
+    fn query(device: &VulkanPhysicalDevice) -> Self {
+        let supports = |name: &[u8]| {
+            device
+                .supports_extension(unsafe { std::ffi::CStr::from_ptr(name.as_ptr() as *const _) })
+        };
+
+        Self {
+            synchronization2: supports(VK_KHR_SYNCHRONIZATION_2_EXTENSION_NAME),
+            dynamic_rendering: supports(VK_KHR_DYNAMIC_RENDERING_EXTENSION_NAME),
+        }
+    }
+}
+
+fn get_device_extensions(optional: &OptionalExtensions) -> Vec<*const std::ffi::c_char> {
     let mut vec: Vec<*const std::ffi::c_char> = Vec::new();
     vec.reserve(8);
 
@@ -67,6 +136,14 @@ fn get_device_extensions() -> Vec<*const std::ffi::c_char> {
         vec.push(VK_KHR_SWAPCHAIN_EXTENSION_NAME.as_ptr() as *const _);
     }
 
+    if optional.synchronization2 {
+        vec.push(VK_KHR_SYNCHRONIZATION_2_EXTENSION_NAME.as_ptr() as *const _);
+    }
+
+    if optional.dynamic_rendering {
+        vec.push(VK_KHR_DYNAMIC_RENDERING_EXTENSION_NAME.as_ptr() as *const _);
+    }
+
     vec
 }
 
@@ -74,28 +151,63 @@ impl Context<VulkanApi> for VulkanDevice {
     fn new(
         root: <VulkanApi as GraphicsApi>::Root,
         device: <VulkanApi as GraphicsApi>::Device,
-        _create_info: ContextCreateInfo,
+        create_info: ContextCreateInfo,
     ) -> crate::Result<Self> {
+        let label = create_info.label;
+
+        // barriers fall back to vkCmdPipelineBarrier without synchronization2
+        let optional = OptionalExtensions::query(&device);
+
         let layers = get_device_layers();
-        let extensions = get_device_extensions();
+        let extensions = get_device_extensions(&optional);
 
-        let graphics_queue_family = 0;
-        let graphics_queue_priorities: &[f32] = &[1.0];
+        let queue_families = device.queue_families();
 
-        let queue_create_infos = &[VkDeviceQueueCreateInfo {
-            sType: VK_STRUCTURE_TYPE_DEVICE_QUEUE_CREATE_INFO,
-            pNext: null(),
-            flags: 0,
-            queueFamilyIndex: graphics_queue_family,
-            queueCount: graphics_queue_priorities.len() as u32,
-            pQueuePriorities: graphics_queue_priorities.as_ptr(),
-        }];
+        let graphics_queue_family = 0;
+        let transfer_queue_family = find_transfer_queue_family(&queue_families);
+        let queue_priorities: &[f32] = &[1.0];
+
+        let queue_create_infos: Vec<VkDeviceQueueCreateInfo> =
+            std::iter::once(graphics_queue_family)
+                .chain(transfer_queue_family)
+                .map(|family| VkDeviceQueueCreateInfo {
+                    sType: VK_STRUCTURE_TYPE_DEVICE_QUEUE_CREATE_INFO,
+                    pNext: null(),
+                    flags: 0,
+                    queueFamilyIndex: family,
+                    queueCount: queue_priorities.len() as u32,
+                    pQueuePriorities: queue_priorities.as_ptr(),
+                })
+                .collect();
 
         let features: VkPhysicalDeviceFeatures = unsafe { std::mem::zeroed() };
 
+        // feature structs for the enabled extensions, chained through pNext
+        let mut next: *mut std::ffi::c_void = null_mut();
+
+        let mut synchronization2_features = VkPhysicalDeviceSynchronization2Features {
+            sType: VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SYNCHRONIZATION_2_FEATURES,
+            pNext: null_mut(),
+            synchronization2: VK_TRUE,
+        };
+        if optional.synchronization2 {
+            synchronization2_features.pNext = next;
+            next = &mut synchronization2_features as *mut _ as *mut _;
+        }
+
+        let mut dynamic_rendering_features = VkPhysicalDeviceDynamicRenderingFeatures {
+            sType: VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_DYNAMIC_RENDERING_FEATURES,
+            pNext: null_mut(),
+            dynamicRendering: VK_TRUE,
+        };
+        if optional.dynamic_rendering {
+            dynamic_rendering_features.pNext = next;
+            next = &mut dynamic_rendering_features as *mut _ as *mut _;
+        }
+
         let create_info = VkDeviceCreateInfo {
             sType: VK_STRUCTURE_TYPE_DEVICE_CREATE_INFO,
-            pNext: null(),
+            pNext: next as *const _,
             flags: 0,
             queueCreateInfoCount: queue_create_infos.len() as u32,
             pQueueCreateInfos: queue_create_infos.as_ptr(),
@@ -108,16 +220,49 @@ impl Context<VulkanApi> for VulkanDevice {
 
         let handle = wrapper::create_device(vkCreateDevice, device.handle(), &create_info, None)?;
 
-        let queue = wrapper::get_device_queue(vkGetDeviceQueue, handle, 0, 0);
+        let queues: Vec<(VkQueue, u32)> = queue_create_infos
+            .iter()
+            .map(|info| {
+                let family = info.queueFamilyIndex;
+                let queue = wrapper::get_device_queue(vkGetDeviceQueue, handle, family, 0);
+                (queue, family)
+            })
+            .collect();
+
+        let cmd_pipeline_barrier2 = if optional.synchronization2 {
+            wrapper::CmdPipelineBarrier2KHR::load(vkGetInstanceProcAddr, root.handle())
+        } else {
+            None
+        };
+
+        let dynamic_rendering = if optional.dynamic_rendering {
+            DynamicRenderingFunctions::load(root.handle())
+        } else {
+            None
+        };
 
         let ownership = Ownership::new_cyclic(|weak| VulkanDeviceOwnership {
             handle,
             instance: root,
             physical_device: device,
-            queues: vec![<VulkanApi as GraphicsApi>::Queue::new(queue, weak.clone())],
+            queues: queues
+                .into_iter()
+                .map(|(queue, family)| {
+                    let flags = queue_flags(queue_families[family as usize].queueFlags);
+                    <VulkanApi as GraphicsApi>::Queue::new(queue, family, flags, weak.clone())
+                })
+                .collect(),
+            cmd_pipeline_barrier2,
+            dynamic_rendering,
         });
 
-        Ok(VulkanDevice { handle, ownership })
+        let context = VulkanDevice { handle, ownership };
+
+        if let Some(label) = label {
+            context.set_debug_name(label)?;
+        }
+
+        Ok(context)
     }
 
     fn queues(&self) -> &[<VulkanApi as GraphicsApi>::Queue] {
@@ -125,12 +270,55 @@ impl Context<VulkanApi> for VulkanDevice {
     }
 }
 
+/// A family with transfer support but no graphics, which usually maps to a dedicated copy
+/// engine. Transfer-only families are preferred over async compute ones.
+fn find_transfer_queue_family(families: &[VkQueueFamilyProperties]) -> Option<u32> {
+    let has =
+        |family: &VkQueueFamilyProperties, flag: i32| family.queueFlags & flag as VkQueueFlags != 0;
+
+    let candidates = || {
+        families.iter().enumerate().filter(|(_, family)| {
+            family.queueCount > 0
+                && has(family, VK_QUEUE_TRANSFER_BIT)
+                && !has(family, VK_QUEUE_GRAPHICS_BIT)
+        })
+    };
+
+    candidates()
+        .find(|(_, family)| !has(family, VK_QUEUE_COMPUTE_BIT))
+        .or_else(|| candidates().next())
+        .map(|(index, _)| index as u32)
+}
+
+fn queue_flags(native: VkQueueFlags) -> crate::QueueFlags {
+    let mut flags = crate::QueueFlags::empty();
+
+    let pairs = [
+        (VK_QUEUE_GRAPHICS_BIT, crate::QueueFlags::GRAPHICS),
+        (VK_QUEUE_COMPUTE_BIT, crate::QueueFlags::COMPUTE),
+        (VK_QUEUE_TRANSFER_BIT, crate::QueueFlags::TRANSFER),
+    ];
+
+    for (bit, flag) in pairs {
+        flags.set(flag, native & bit as VkQueueFlags != 0);
+    }
+
+    // graphics and compute queues support transfers without reporting it
+    if flags.intersects(crate::QueueFlags::GRAPHICS | crate::QueueFlags::COMPUTE) {
+        flags |= crate::QueueFlags::TRANSFER;
+    }
+
+    flags
+}
+
 /*
    Queue
 */
 
 struct QueueOwnership {
     handle: VkQueue,
+    family_index: u32,
+    flags: crate::QueueFlags,
     device: Weak<VulkanDeviceOwnership>,
 }
 
@@ -141,11 +329,40 @@ pub struct VulkanQueue {
 }
 
 impl VulkanQueue {
-    fn new(handle: VkQueue, device: Weak<VulkanDeviceOwnership>) -> Self {
-        let ownership = Ownership::new(QueueOwnership { handle, device });
+    fn new(
+        handle: VkQueue,
+        family_index: u32,
+        flags: crate::QueueFlags,
+        device: Weak<VulkanDeviceOwnership>,
+    ) -> Self {
+        let ownership = Ownership::new(QueueOwnership {
+            handle,
+            family_index,
+            flags,
+            device,
+        });
 
         Self { handle, ownership }
     }
+
+    /// The device the queue belongs to, or `None` once it's been dropped.
+    pub(crate) fn device(&self) -> Option<VulkanDevice> {
+        let ownership = self.ownership.device.upgrade()?;
+
+        Some(VulkanDevice {
+            handle: ownership.handle,
+            ownership,
+        })
+    }
+
+    /// Names the queue in validation messages and debugging tools. Does nothing once the device
+    /// has been dropped.
+    pub fn set_debug_name(&self, name: &str) -> crate::Result<()> {
+        match self.device() {
+            Some(device) => device.set_object_name(Self::OBJECT_TYPE, self.handle.as_raw(), name),
+            None => Ok(()),
+        }
+    }
 }
 
 impl Debug for VulkanQueue {
@@ -156,11 +373,97 @@ impl Debug for VulkanQueue {
     }
 }
 
-impl crate::api::traits::Queue<VulkanApi> for VulkanQueue {}
+impl crate::api::traits::Queue<VulkanApi> for VulkanQueue {
+    fn family_index(&self) -> u32 {
+        self.ownership.family_index
+    }
+
+    fn flags(&self) -> crate::QueueFlags {
+        self.ownership.flags
+    }
+
+    fn submit(
+        &self,
+        submit_info: &SubmitInfo,
+        fence: Option<&<VulkanApi as GraphicsApi>::Fence>,
+    ) -> crate::Result<()> {
+        let wait_semaphores: Vec<_> = submit_info
+            .wait_semaphores
+            .iter()
+            .map(|(semaphore, _)| semaphore.handle())
+            .collect();
+
+        let wait_stages: Vec<VkPipelineStageFlags> = submit_info
+            .wait_semaphores
+            .iter()
+            .map(|(_, stages)| (*stages).into())
+            .collect();
+
+        let command_buffers: Vec<_> = submit_info
+            .command_buffers
+            .iter()
+            .map(|command_buffer| command_buffer.handle())
+            .collect();
+
+        let signal_semaphores: Vec<_> = submit_info
+            .signal_semaphores
+            .iter()
+            .map(|semaphore| semaphore.handle())
+            .collect();
+
+        let native = VkSubmitInfo {
+            sType: VK_STRUCTURE_TYPE_SUBMIT_INFO,
+            pNext: null(),
+            waitSemaphoreCount: wait_semaphores.len() as u32,
+            pWaitSemaphores: wait_semaphores.as_ptr(),
+            pWaitDstStageMask: wait_stages.as_ptr(),
+            commandBufferCount: command_buffers.len() as u32,
+            pCommandBuffers: command_buffers.as_ptr(),
+            signalSemaphoreCount: signal_semaphores.len() as u32,
+            pSignalSemaphores: signal_semaphores.as_ptr(),
+        };
+
+        let fence = fence.map(|fence| fence.handle()).unwrap_or(null_mut());
+
+        check(unsafe { vkQueueSubmit(self.handle, 1, &native, fence) })?;
+        Ok(())
+    }
+
+    fn present(&self, present_info: &PresentInfo) -> crate::Result<bool> {
+        let wait_semaphores: Vec<_> = present_info
+            .wait_semaphores
+            .iter()
+            .map(|semaphore| semaphore.handle())
+            .collect();
+
+        let swapchain = present_info.swapchain.handle();
+
+        let native = VkPresentInfoKHR {
+            sType: VK_STRUCTURE_TYPE_PRESENT_INFO_KHR,
+            pNext: null(),
+            waitSemaphoreCount: wait_semaphores.len() as u32,
+            pWaitSemaphores: wait_semaphores.as_ptr(),
+            swapchainCount: 1,
+            pSwapchains: &swapchain,
+            pImageIndices: &present_info.image_index,
+            pResults: null_mut(),
+        };
+
+        let result = check(unsafe { vkQueuePresentKHR(self.handle, &native) })?;
+        Ok(result == VK_SUBOPTIMAL_KHR)
+    }
+
+    fn wait_idle(&self) -> crate::Result<()> {
+        check(unsafe { vkQueueWaitIdle(self.handle) })?;
+        Ok(())
+    }
+}
 
 impl VulkanObject for VulkanQueue {
     type Handle = VkQueue;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_QUEUE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
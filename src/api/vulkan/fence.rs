@@ -8,6 +8,7 @@ use vulkan_sys::*;
 
 use crate::FenceCreateInfo;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 
 struct FenceOwnership {
     handle: VkFence,
@@ -37,6 +38,8 @@ impl Debug for VulkanFence {
 impl VulkanObject for VulkanFence {
     type Handle = VkFence;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_FENCE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -74,6 +77,35 @@ impl crate::api::traits::Fence<VulkanApi> for VulkanFence {
             device: context,
         });
 
-        Ok(VulkanFence { handle, ownership })
+        let fence = VulkanFence { handle, ownership };
+
+        if let Some(label) = create_info.label {
+            fence.set_debug_name(label)?;
+        }
+
+        Ok(fence)
+    }
+
+    fn wait(&self, timeout: Option<Duration>) -> crate::Result<bool> {
+        let timeout = timeout
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(u64::MAX);
+
+        let result = unsafe {
+            vkWaitForFences(
+                self.ownership.device.handle(),
+                1,
+                &self.handle,
+                VK_TRUE,
+                timeout,
+            )
+        };
+
+        Ok(check(result)? != VK_TIMEOUT)
+    }
+
+    fn reset(&self) -> crate::Result<()> {
+        check(unsafe { vkResetFences(self.ownership.device.handle(), 1, &self.handle) })?;
+        Ok(())
     }
 }
@@ -40,6 +40,8 @@ impl std::fmt::Debug for VulkanFramebuffer {
 impl VulkanObject for VulkanFramebuffer {
     type Handle = VkFramebuffer;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_FRAMEBUFFER;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -92,6 +94,12 @@ impl crate::api::traits::Framebuffer<VulkanApi> for VulkanFramebuffer {
             render_pass: create_info.render_pass,
         });
 
-        Ok(Self { handle, ownership })
+        let framebuffer = Self { handle, ownership };
+
+        if let Some(label) = create_info.label {
+            framebuffer.set_debug_name(label)?;
+        }
+
+        Ok(framebuffer)
     }
 }
@@ -3,7 +3,7 @@
 
 use crate::api::vulkan::*;
 use crate::prelude::GraphicsApi;
-use crate::ImageViewCreateInfo;
+use crate::{ImageCreateInfo, ImageViewCreateInfo};
 use std::fmt::{Debug, Formatter};
 use vulkan_sys::*;
 
@@ -13,17 +13,48 @@ use vulkan_sys::*;
 
 struct VulkanImageOwnership {
     handle: VkImage,
+    format: crate::Format,
+    extent: crate::Extent2D,
+    mip_levels: u32,
+    array_layers: u32,
+    /// `None` for swapchain images, which the swapchain owns.
+    // destroyed after the image
+    memory: Option<VulkanMemory>,
+    device: VulkanDevice,
+}
+
+impl Drop for VulkanImageOwnership {
+    fn drop(&mut self) {
+        if self.memory.is_some() {
+            wrapper::destroy_image(vkDestroyImage, self.device.handle(), self.handle, None);
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct VulkanImage {
     handle: VkImage,
-    // ownership: Ownership<VulkanSwapchainImageOwnership>,
+    ownership: Ownership<VulkanImageOwnership>,
 }
 
 impl VulkanImage {
-    pub(crate) fn swapchain(handle: VkImage) -> Self {
-        Self { handle }
+    pub(crate) fn swapchain(
+        handle: VkImage,
+        device: VulkanDevice,
+        format: crate::Format,
+        extent: crate::Extent2D,
+    ) -> Self {
+        let ownership = Ownership::new(VulkanImageOwnership {
+            handle,
+            format,
+            extent,
+            mip_levels: 1,
+            array_layers: 1,
+            memory: None,
+            device,
+        });
+
+        Self { handle, ownership }
     }
 }
 
@@ -35,15 +66,123 @@ impl Debug for VulkanImage {
     }
 }
 
+impl PartialEq for VulkanImage {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
 impl VulkanObject for VulkanImage {
     type Handle = VkImage;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_IMAGE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
 }
 
-impl crate::api::traits::Image<VulkanApi> for VulkanImage {}
+impl VulkanDeviceObject for VulkanImage {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
+    }
+}
+
+impl crate::api::traits::Image<VulkanApi> for VulkanImage {
+    fn new(
+        context: <VulkanApi as GraphicsApi>::Context,
+        create_info: ImageCreateInfo,
+    ) -> crate::Result<Self> {
+        let flags = match create_info.cube {
+            true => VK_IMAGE_CREATE_CUBE_COMPATIBLE_BIT as VkImageCreateFlags,
+            false => 0,
+        };
+
+        let info = VkImageCreateInfo {
+            sType: VK_STRUCTURE_TYPE_IMAGE_CREATE_INFO,
+            pNext: std::ptr::null(),
+            flags,
+            imageType: VK_IMAGE_TYPE_2D,
+            format: create_info.format.into(),
+            extent: VkExtent3D {
+                width: create_info.extent.width,
+                height: create_info.extent.height,
+                depth: 1,
+            },
+            mipLevels: create_info.mip_levels,
+            arrayLayers: create_info.array_layers,
+            samples: VK_SAMPLE_COUNT_1_BIT,
+            tiling: VK_IMAGE_TILING_OPTIMAL,
+            usage: create_info.usage.into(),
+            sharingMode: VK_SHARING_MODE_EXCLUSIVE,
+            queueFamilyIndexCount: 0,
+            pQueueFamilyIndices: std::ptr::null(),
+            initialLayout: VK_IMAGE_LAYOUT_UNDEFINED,
+        };
+
+        let handle = wrapper::create_image(vkCreateImage, context.handle(), &info, None)?;
+
+        let requirements = wrapper::get_image_memory_requirements(
+            vkGetImageMemoryRequirements,
+            context.handle(),
+            handle,
+        );
+
+        let memory = VulkanMemory::allocate(&context, &requirements, create_info.location)
+            .and_then(|memory| {
+                wrapper::bind_image_memory(
+                    vkBindImageMemory,
+                    context.handle(),
+                    handle,
+                    memory.handle(),
+                    0,
+                )?;
+                Ok(memory)
+            });
+
+        let memory = match memory {
+            Ok(memory) => memory,
+            Err(error) => {
+                wrapper::destroy_image(vkDestroyImage, context.handle(), handle, None);
+                return Err(error);
+            }
+        };
+
+        let ownership = Ownership::new(VulkanImageOwnership {
+            handle,
+            format: create_info.format,
+            extent: create_info.extent,
+            mip_levels: create_info.mip_levels,
+            array_layers: create_info.array_layers,
+            memory: Some(memory),
+            device: context,
+        });
+
+        let image = Self { handle, ownership };
+
+        if let Some(label) = create_info.label {
+            image.set_debug_name(label)?;
+        }
+
+        Ok(image)
+    }
+
+    fn format(&self) -> crate::Format {
+        self.ownership.format
+    }
+
+    fn extent(&self) -> crate::Extent2D {
+        self.ownership.extent
+    }
+
+    fn mip_levels(&self) -> u32 {
+        self.ownership.mip_levels
+    }
+
+    fn array_layers(&self) -> u32 {
+        self.ownership.array_layers
+    }
+}
 
 /*
    Image View
@@ -78,6 +217,8 @@ impl Debug for VulkanImageView {
 impl VulkanObject for VulkanImageView {
     type Handle = VkImageView;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_IMAGE_VIEW;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -125,6 +266,12 @@ impl crate::api::traits::ImageView<VulkanApi> for VulkanImageView {
             device: context,
         });
 
-        Ok(VulkanImageView { handle, ownership })
+        let view = VulkanImageView { handle, ownership };
+
+        if let Some(label) = create_info.label {
+            view.set_debug_name(label)?;
+        }
+
+        Ok(view)
     }
 }
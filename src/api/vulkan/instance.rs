@@ -14,10 +14,18 @@ use vulkan_sys::*;
 struct InstanceOwnership {
     handle: VkInstance,
     physical_devices: OnceLock<Vec<<VulkanApi as GraphicsApi>::Device>>,
+    /// Whether `VK_KHR_get_physical_device_properties2` is enabled.
+    get_properties2: bool,
 
     #[cfg(feature = "validation")]
     debug_messenger: VkDebugUtilsMessengerEXT,
 
+    #[cfg(feature = "validation")]
+    debug_utils_state: Box<DebugUtilsState>,
+
+    #[cfg(feature = "validation")]
+    debug_utils: Option<DebugUtilsFunctions>,
+
     #[cfg(feature = "validation")]
     destroy_debug_utils:
         unsafe extern "C" fn(VkInstance, VkDebugUtilsMessengerEXT, *const VkAllocationCallbacks),
@@ -59,10 +67,26 @@ fn get_instance_layers() -> Vec<*const std::ffi::c_char> {
     vec
 }
 
-fn get_instance_extensions() -> Vec<*const std::ffi::c_char> {
+fn supports_instance_extension(name: &[u8]) -> bool {
+    let name = unsafe { std::ffi::CStr::from_ptr(name.as_ptr() as *const _) };
+
+    wrapper::enumerate_instance_extension_properties(vkEnumerateInstanceExtensionProperties, None)
+        .map(|extensions| {
+            extensions.iter().any(|extension| unsafe {
+                std::ffi::CStr::from_ptr(extension.extensionName.as_ptr()) == name
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn get_instance_extensions(get_properties2: bool) -> Vec<*const std::ffi::c_char> {
     let mut vec: Vec<*const std::ffi::c_char> = Vec::new();
     vec.reserve(8);
 
+    if get_properties2 {
+        vec.push(VK_KHR_GET_PHYSICAL_DEVICE_PROPERTIES_2_EXTENSION_NAME.as_ptr() as *const _);
+    }
+
     #[cfg(feature = "graphics")]
     {
         vec.push(VK_KHR_SURFACE_EXTENSION_NAME.as_ptr() as *const _);
@@ -81,9 +105,13 @@ fn get_instance_extensions() -> Vec<*const std::ffi::c_char> {
 }
 
 impl ApiRoot<VulkanApi> for VulkanInstance {
-    fn new(_create_info: &RootCreateInfo) -> crate::Result<Self> {
+    fn new(create_info: &RootCreateInfo) -> crate::Result<Self> {
+        // driver properties are only reported through vkGetPhysicalDeviceProperties2
+        let get_properties2 =
+            supports_instance_extension(VK_KHR_GET_PHYSICAL_DEVICE_PROPERTIES_2_EXTENSION_NAME);
+
         let layers = get_instance_layers();
-        let extensions = get_instance_extensions();
+        let extensions = get_instance_extensions(get_properties2);
 
         let instance_create_info = VkInstanceCreateInfo {
             sType: VK_STRUCTURE_TYPE_INSTANCE_CREATE_INFO,
@@ -98,6 +126,12 @@ impl ApiRoot<VulkanApi> for VulkanInstance {
 
         let handle = wrapper::create_instance(vkCreateInstance, &instance_create_info, None)?;
 
+        #[cfg(feature = "validation")]
+        let debug_utils_state = Box::new(DebugUtilsState {
+            callback: create_info.debug_callback.clone(),
+            error_policy: create_info.validation_error_policy,
+        });
+
         #[cfg(feature = "validation")]
         let (debug_messenger, destroy_debug_utils) = {
             let create_debug_utils =
@@ -122,7 +156,7 @@ impl ApiRoot<VulkanApi> for VulkanInstance {
                     | VK_DEBUG_UTILS_MESSAGE_TYPE_PERFORMANCE_BIT_EXT)
                     as VkDebugUtilsMessageTypeFlagsEXT,
                 pfnUserCallback: debug_utils_callback,
-                pUserData: std::ptr::null_mut(),
+                pUserData: debug_utils_state.as_ref() as *const DebugUtilsState as *mut _,
             };
 
             let debug_messenger = wrapper::create_debug_utils_messenger_ext(
@@ -135,13 +169,23 @@ impl ApiRoot<VulkanApi> for VulkanInstance {
             (debug_messenger, destroy_debug_utils)
         };
 
+        #[cfg(feature = "validation")]
+        let debug_utils = DebugUtilsFunctions::load(handle);
+
         let ownership = Ownership::new(InstanceOwnership {
             handle,
             physical_devices: OnceLock::new(),
+            get_properties2,
 
             #[cfg(feature = "validation")]
             debug_messenger,
 
+            #[cfg(feature = "validation")]
+            debug_utils_state,
+
+            #[cfg(feature = "validation")]
+            debug_utils,
+
             #[cfg(feature = "validation")]
             destroy_debug_utils,
         });
@@ -158,10 +202,20 @@ impl ApiRoot<VulkanApi> for VulkanInstance {
 }
 
 impl VulkanInstance {
+    #[cfg(feature = "validation")]
+    pub(crate) fn debug_utils(&self) -> Option<&DebugUtilsFunctions> {
+        self.ownership.debug_utils.as_ref()
+    }
+
     fn enumerate_physical_device(&self) -> crate::Result<Vec<<VulkanApi as GraphicsApi>::Device>> {
+        let get_properties2 = if self.ownership.get_properties2 {
+            wrapper::GetPhysicalDeviceProperties2KHR::load(vkGetInstanceProcAddr, self.handle)
+        } else {
+            None
+        };
+
         let to_physical_device = |handle: VkPhysicalDevice| {
-            let properties =
-                wrapper::get_physical_device_properties(vkGetPhysicalDeviceProperties, handle);
+            let properties = VulkanPhysicalDeviceProperties::query(handle, get_properties2);
             let features =
                 wrapper::get_physical_device_features(vkGetPhysicalDeviceFeatures, handle);
 
@@ -181,6 +235,8 @@ impl VulkanInstance {
 impl VulkanObject for VulkanInstance {
     type Handle = VkInstance;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_INSTANCE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -190,15 +246,25 @@ impl VulkanObject for VulkanInstance {
    Debug Utils
 */
 
-// todo : implement user application callbacks
+#[cfg(feature = "validation")]
+struct DebugUtilsState {
+    callback: Option<crate::DebugCallback>,
+    error_policy: crate::ValidationErrorPolicy,
+}
+
 #[cfg(feature = "validation")]
 unsafe extern "C" fn debug_utils_callback(
     severity: VkDebugUtilsMessageSeverityFlagBitsEXT,
     message_type: VkDebugUtilsMessageTypeFlagsEXT,
     data: *const VkDebugUtilsMessengerCallbackDataEXT,
-    _user_data: *mut std::ffi::c_void,
+    user_data: *mut std::ffi::c_void,
 ) -> VkBool32 {
-    debug_utils_callback_safe(severity, message_type, &*data);
+    debug_utils_callback_safe(
+        severity,
+        message_type,
+        &*data,
+        &*(user_data as *const DebugUtilsState),
+    );
     VK_FALSE
 }
 
@@ -219,73 +285,103 @@ impl Debug for DebugUtilsObjectNameWrapper<'_> {
 }
 
 #[cfg(feature = "validation")]
-fn create_message(data: &VkDebugUtilsMessengerCallbackDataEXT) -> String {
-    // #[cfg(windows)]
-    // const LINE_ENDING: &'static str = "\r\n";
-    // #[cfg(not(windows))]
-    // const LINE_ENDING: &'static str = "\n";
-
-    let message_name = unsafe {
-        std::ffi::CStr::from_ptr(data.pMessageIdName)
-            .to_str()
-            .unwrap()
+unsafe fn optional_str(ptr: *const std::ffi::c_char) -> Option<String> {
+    (!ptr.is_null()).then(|| std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned())
+}
+
+#[cfg(feature = "validation")]
+fn create_debug_message(
+    severity: crate::DebugMessageSeverity,
+    types: VkDebugUtilsMessageTypeFlagsEXT,
+    data: &VkDebugUtilsMessengerCallbackDataEXT,
+) -> crate::DebugMessage {
+    let objects = match data.objectCount {
+        0 => &[][..],
+        count => unsafe { std::slice::from_raw_parts(data.pObjects, count as usize) },
     };
-    let message = unsafe { std::ffi::CStr::from_ptr(data.pMessage).to_str().unwrap() };
-
-    // let objects = unsafe { std::slice::from_raw_parts(data.pObjects, data.objectCount as usize) }
-    //     .iter()
-    //     .map(|object| {
-    //         let object_name = unsafe {
-    //             (object.pObjectName != std::ptr::null())
-    //                 .then(|| {
-    //                     std::ffi::CStr::from_ptr(object.pObjectName)
-    //                         .to_str()
-    //                         .unwrap()
-    //                 })
-    //                 .unwrap_or("None")
-    //         };
-    //         DebugUtilsObjectNameWrapper {
-    //             object_type: object.objectType,
-    //             object_name,
-    //         }
-    //     })
-    //     .collect::<Vec<_>>();
-
-    // let formatted_message = format!(
-    //     "Objects = {:#?}{}{}: {}",
-    //     objects, LINE_ENDING, message_name, message
-    // );
-
-    let formatted_message = format!("{}", message);
-    // let formatted_message = format!("{}: {}", message_name, message);
-
-    formatted_message
+
+    let objects = objects
+        .iter()
+        .map(|object| crate::DebugObject {
+            object_type: object.objectType as i32,
+            handle: object.objectHandle,
+            name: unsafe { optional_str(object.pObjectName) },
+        })
+        .collect();
+
+    crate::DebugMessage {
+        severity,
+        types: crate::DebugMessageTypeFlags::from_bits_truncate(types as u32),
+        id_name: unsafe { optional_str(data.pMessageIdName) },
+        id_number: data.messageIdNumber,
+        message: unsafe { optional_str(data.pMessage) }.unwrap_or_default(),
+        objects,
+    }
+}
+
+#[cfg(feature = "validation")]
+fn format_log_message(message: &crate::DebugMessage) -> String {
+    #[cfg(windows)]
+    const LINE_ENDING: &'static str = "\r\n";
+    #[cfg(not(windows))]
+    const LINE_ENDING: &'static str = "\n";
+
+    let objects = message
+        .objects
+        .iter()
+        .filter_map(|object| {
+            object
+                .name
+                .as_deref()
+                .map(|object_name| DebugUtilsObjectNameWrapper {
+                    object_type: object.object_type as VkObjectType,
+                    object_name,
+                })
+        })
+        .collect::<Vec<_>>();
+
+    match objects.is_empty() {
+        true => message.message.clone(),
+        false => format!("Objects = {:#?}{}{}", objects, LINE_ENDING, message.message),
+    }
 }
 
 #[cfg(feature = "validation")]
 fn debug_utils_callback_safe(
     severity: VkDebugUtilsMessageSeverityFlagBitsEXT,
-    _types: VkDebugUtilsMessageTypeFlagsEXT,
+    types: VkDebugUtilsMessageTypeFlagsEXT,
     data: &VkDebugUtilsMessengerCallbackDataEXT,
+    state: &DebugUtilsState,
 ) {
-    if severity == VK_DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT {
-        panic!("Vulkan : {}", create_message(data));
+    let (severity, level) = match severity {
+        VK_DEBUG_UTILS_MESSAGE_SEVERITY_VERBOSE_BIT_EXT => {
+            (crate::DebugMessageSeverity::Verbose, log::Level::Trace)
+        }
+        VK_DEBUG_UTILS_MESSAGE_SEVERITY_INFO_BIT_EXT => {
+            (crate::DebugMessageSeverity::Info, log::Level::Debug)
+        }
+        VK_DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT => {
+            (crate::DebugMessageSeverity::Warning, log::Level::Warn)
+        }
+        _ => (crate::DebugMessageSeverity::Error, log::Level::Error),
+    };
+
+    let message = create_debug_message(severity, types, data);
+
+    if let Some(callback) = &state.callback {
+        callback.call(&message);
     }
 
-    let level = match severity {
-        VK_DEBUG_UTILS_MESSAGE_SEVERITY_VERBOSE_BIT_EXT => log::Level::Trace,
-        VK_DEBUG_UTILS_MESSAGE_SEVERITY_INFO_BIT_EXT => log::Level::Debug,
-        VK_DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT => log::Level::Warn,
-        VK_DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT => log::Level::Error,
-        _ => panic!(),
-    };
+    if severity == crate::DebugMessageSeverity::Error
+        && state.error_policy == crate::ValidationErrorPolicy::Panic
+    {
+        panic!("Vulkan : {}", message.message);
+    }
 
     if log::logger().enabled(&log::Metadata::builder().level(level).build()) {
-        let msg = create_message(data);
-
         log::logger().log(
             &log::Record::builder()
-                .args(format_args!("{}", msg))
+                .args(format_args!("{}", format_log_message(&message)))
                 .level(level)
                 .target("Vulkan")
                 .build(),
@@ -0,0 +1,152 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::api::vulkan::*;
+use crate::MemoryLocation;
+use vulkan_sys::*;
+
+/// A dedicated allocation backing a single resource.
+///
+/// Host visible memory is mapped once when allocated and stays mapped until it's freed.
+pub(crate) struct VulkanMemory {
+    handle: VkDeviceMemory,
+    device: VulkanDevice,
+    /// The start of the mapping, or null unless the memory is host visible.
+    mapped: *mut u8,
+}
+
+impl Drop for VulkanMemory {
+    fn drop(&mut self) {
+        if !self.mapped.is_null() {
+            unsafe { vkUnmapMemory(self.device.handle(), self.handle) };
+        }
+
+        wrapper::free_memory(vkFreeMemory, self.device.handle(), self.handle, None);
+    }
+}
+
+impl VulkanMemory {
+    pub(crate) fn allocate(
+        device: &VulkanDevice,
+        requirements: &VkMemoryRequirements,
+        location: MemoryLocation,
+    ) -> crate::Result<Self> {
+        let memory_type_index = find_memory_type(device, requirements.memoryTypeBits, location)
+            .ok_or(crate::Error::Unsupported(
+                "no memory type matches the requested location",
+            ))?;
+
+        let allocate_info = VkMemoryAllocateInfo {
+            sType: VK_STRUCTURE_TYPE_MEMORY_ALLOCATE_INFO,
+            pNext: std::ptr::null(),
+            allocationSize: requirements.size,
+            memoryTypeIndex: memory_type_index,
+        };
+
+        let handle =
+            wrapper::allocate_memory(vkAllocateMemory, device.handle(), &allocate_info, None)?;
+
+        let mut memory = Self {
+            handle,
+            device: device.clone(),
+            mapped: std::ptr::null_mut(),
+        };
+
+        if location != MemoryLocation::DeviceLocal {
+            memory.mapped = memory.map()?;
+        }
+
+        Ok(memory)
+    }
+
+    pub(crate) fn handle(&self) -> VkDeviceMemory {
+        self.handle
+    }
+
+    /// Copies `data` into the memory at `offset`. The memory must be host visible, and is
+    /// always allocated coherent so no flush is needed.
+    pub(crate) fn write(&self, offset: u64, data: &[u8]) -> crate::Result<()> {
+        assert!(!self.mapped.is_null(), "memory is not host visible");
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                self.mapped.add(offset as usize),
+                data.len(),
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Copies the memory at `offset` into `data`. The memory must be host visible.
+    pub(crate) fn read(&self, offset: u64, data: &mut [u8]) -> crate::Result<()> {
+        assert!(!self.mapped.is_null(), "memory is not host visible");
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self.mapped.add(offset as usize),
+                data.as_mut_ptr(),
+                data.len(),
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Maps the whole allocation.
+    fn map(&self) -> crate::Result<*mut u8> {
+        let mut mapped: *mut std::ffi::c_void = std::ptr::null_mut();
+
+        check(unsafe {
+            vkMapMemory(
+                self.device.handle(),
+                self.handle,
+                0,
+                VK_WHOLE_SIZE,
+                0,
+                &mut mapped,
+            )
+        })?;
+
+        Ok(mapped as *mut u8)
+    }
+}
+
+/// Picks the first memory type allowed by `type_bits` that has the flags `location` requires,
+/// preferring one that also has the flags it benefits from.
+fn find_memory_type(
+    device: &VulkanDevice,
+    type_bits: u32,
+    location: MemoryLocation,
+) -> Option<u32> {
+    let host_visible = (VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | VK_MEMORY_PROPERTY_HOST_COHERENT_BIT)
+        as VkMemoryPropertyFlags;
+
+    let (required, preferred) = match location {
+        MemoryLocation::DeviceLocal => (
+            VK_MEMORY_PROPERTY_DEVICE_LOCAL_BIT as VkMemoryPropertyFlags,
+            0,
+        ),
+        MemoryLocation::Upload => (host_visible, 0),
+        MemoryLocation::Readback => (
+            host_visible,
+            VK_MEMORY_PROPERTY_HOST_CACHED_BIT as VkMemoryPropertyFlags,
+        ),
+    };
+
+    let properties = device.physical_device().memory_properties();
+    let types = &properties.memoryTypes[..properties.memoryTypeCount as usize];
+
+    let find = |flags: VkMemoryPropertyFlags| {
+        types
+            .iter()
+            .enumerate()
+            .position(|(index, memory_type)| {
+                type_bits & (1 << index) != 0 && memory_type.propertyFlags & flags == flags
+            })
+            .map(|index| index as u32)
+    };
+
+    find(required | preferred).or_else(|| find(required))
+}
@@ -28,6 +28,12 @@ pub use swapchain::*;
 mod image;
 pub use image::*;
 
+mod memory;
+pub use memory::*;
+
+mod buffer;
+pub use buffer::*;
+
 mod shader;
 pub use shader::*;
 
@@ -43,6 +49,9 @@ pub use fence::*;
 mod semaphore;
 pub use semaphore::*;
 
+mod debug;
+pub use debug::*;
+
 use vulkan_sys::*;
 
 use std::sync::Arc;
@@ -60,6 +69,8 @@ impl crate::api::traits::GraphicsApi for VulkanApi {
     type CommandPool = VulkanCommandPool;
     type CommandBuffer = VulkanCommandBuffer;
 
+    type Buffer = VulkanBuffer;
+
     type Swapchain = VulkanSwapchain;
     type Image = VulkanImage;
     type ImageView = VulkanImageView;
@@ -70,6 +81,7 @@ impl crate::api::traits::GraphicsApi for VulkanApi {
     type Framebuffer = VulkanFramebuffer;
 
     type PipelineLayout = VulkanPipelineLayout;
+    type PipelineCache = VulkanPipelineCache;
 
     // type VertexInputState = VulkanPipelineVertexInputStateCreateInfo;
     // type InputAssemblyState = VulkanPipelineInputAssemblyStateCreateInfo;
@@ -91,6 +103,18 @@ impl crate::api::traits::GraphicsApi for VulkanApi {
     type CullModeConstants = constants::VulkanCullModeConstants;
     type FrontFaceConstants = constants::VulkanFrontFaceConstants;
     type PrimitiveTopologyConstants = constants::VulkanPrimitiveTopologyConstants;
+    type DescriptorTypeConstants = constants::VulkanDescriptorTypeConstants;
+    type ShaderStageConstants = constants::VulkanShaderStageConstants;
+    type VertexInputRateConstants = constants::VulkanVertexInputRateConstants;
+    type ImageLayoutConstants = constants::VulkanImageLayoutConstants;
+    type AccessConstants = constants::VulkanAccessConstants;
+    type PipelineStageConstants = constants::VulkanPipelineStageConstants;
+    type ImageAspectConstants = constants::VulkanImageAspectConstants;
+    type BufferUsageConstants = constants::VulkanBufferUsageConstants;
+    type ImageUsageConstants = constants::VulkanImageUsageConstants;
+    type AttachmentLoadOpConstants = constants::VulkanAttachmentLoadOpConstants;
+    type AttachmentStoreOpConstants = constants::VulkanAttachmentStoreOpConstants;
+    type FormatFeatureConstants = constants::VulkanFormatFeatureConstants;
 }
 
 mod constants {
@@ -176,6 +200,82 @@ mod constants {
 
         const R10G10B10A2_UINT: i32 = VK_FORMAT_A2B10G10R10_UINT_PACK32;
         const R10G10B10A2_UNORM: i32 = VK_FORMAT_A2B10G10R10_UNORM_PACK32;
+
+        const D16_UNORM: i32 = VK_FORMAT_D16_UNORM;
+        const D32_SFLOAT: i32 = VK_FORMAT_D32_SFLOAT;
+        const D24_UNORM_S8_UINT: i32 = VK_FORMAT_D24_UNORM_S8_UINT;
+        const D32_SFLOAT_S8_UINT: i32 = VK_FORMAT_D32_SFLOAT_S8_UINT;
+
+        const B10G11R11_UFLOAT: i32 = VK_FORMAT_B10G11R11_UFLOAT_PACK32;
+        const E5B9G9R9_UFLOAT: i32 = VK_FORMAT_E5B9G9R9_UFLOAT_PACK32;
+
+        const BC1_RGBA_UNORM: i32 = VK_FORMAT_BC1_RGBA_UNORM_BLOCK;
+        const BC1_RGBA_UNORM_SRGB: i32 = VK_FORMAT_BC1_RGBA_SRGB_BLOCK;
+        const BC2_UNORM: i32 = VK_FORMAT_BC2_UNORM_BLOCK;
+        const BC2_UNORM_SRGB: i32 = VK_FORMAT_BC2_SRGB_BLOCK;
+        const BC3_UNORM: i32 = VK_FORMAT_BC3_UNORM_BLOCK;
+        const BC3_UNORM_SRGB: i32 = VK_FORMAT_BC3_SRGB_BLOCK;
+        const BC4_UNORM: i32 = VK_FORMAT_BC4_UNORM_BLOCK;
+        const BC4_SNORM: i32 = VK_FORMAT_BC4_SNORM_BLOCK;
+        const BC5_UNORM: i32 = VK_FORMAT_BC5_UNORM_BLOCK;
+        const BC5_SNORM: i32 = VK_FORMAT_BC5_SNORM_BLOCK;
+        const BC6H_UFLOAT: i32 = VK_FORMAT_BC6H_UFLOAT_BLOCK;
+        const BC6H_SFLOAT: i32 = VK_FORMAT_BC6H_SFLOAT_BLOCK;
+        const BC7_UNORM: i32 = VK_FORMAT_BC7_UNORM_BLOCK;
+        const BC7_UNORM_SRGB: i32 = VK_FORMAT_BC7_SRGB_BLOCK;
+
+        const ETC2_R8G8B8_UNORM: i32 = VK_FORMAT_ETC2_R8G8B8_UNORM_BLOCK;
+        const ETC2_R8G8B8_UNORM_SRGB: i32 = VK_FORMAT_ETC2_R8G8B8_SRGB_BLOCK;
+        const ETC2_R8G8B8A1_UNORM: i32 = VK_FORMAT_ETC2_R8G8B8A1_UNORM_BLOCK;
+        const ETC2_R8G8B8A1_UNORM_SRGB: i32 = VK_FORMAT_ETC2_R8G8B8A1_SRGB_BLOCK;
+        const ETC2_R8G8B8A8_UNORM: i32 = VK_FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK;
+        const ETC2_R8G8B8A8_UNORM_SRGB: i32 = VK_FORMAT_ETC2_R8G8B8A8_SRGB_BLOCK;
+        const EAC_R11_UNORM: i32 = VK_FORMAT_EAC_R11_UNORM_BLOCK;
+        const EAC_R11_SNORM: i32 = VK_FORMAT_EAC_R11_SNORM_BLOCK;
+        const EAC_R11G11_UNORM: i32 = VK_FORMAT_EAC_R11G11_UNORM_BLOCK;
+        const EAC_R11G11_SNORM: i32 = VK_FORMAT_EAC_R11G11_SNORM_BLOCK;
+
+        const ASTC_4x4_UNORM: i32 = VK_FORMAT_ASTC_4x4_UNORM_BLOCK;
+        const ASTC_4x4_UNORM_SRGB: i32 = VK_FORMAT_ASTC_4x4_SRGB_BLOCK;
+
+        const ASTC_5x4_UNORM: i32 = VK_FORMAT_ASTC_5x4_UNORM_BLOCK;
+        const ASTC_5x4_UNORM_SRGB: i32 = VK_FORMAT_ASTC_5x4_SRGB_BLOCK;
+
+        const ASTC_5x5_UNORM: i32 = VK_FORMAT_ASTC_5x5_UNORM_BLOCK;
+        const ASTC_5x5_UNORM_SRGB: i32 = VK_FORMAT_ASTC_5x5_SRGB_BLOCK;
+
+        const ASTC_6x5_UNORM: i32 = VK_FORMAT_ASTC_6x5_UNORM_BLOCK;
+        const ASTC_6x5_UNORM_SRGB: i32 = VK_FORMAT_ASTC_6x5_SRGB_BLOCK;
+
+        const ASTC_6x6_UNORM: i32 = VK_FORMAT_ASTC_6x6_UNORM_BLOCK;
+        const ASTC_6x6_UNORM_SRGB: i32 = VK_FORMAT_ASTC_6x6_SRGB_BLOCK;
+
+        const ASTC_8x5_UNORM: i32 = VK_FORMAT_ASTC_8x5_UNORM_BLOCK;
+        const ASTC_8x5_UNORM_SRGB: i32 = VK_FORMAT_ASTC_8x5_SRGB_BLOCK;
+
+        const ASTC_8x6_UNORM: i32 = VK_FORMAT_ASTC_8x6_UNORM_BLOCK;
+        const ASTC_8x6_UNORM_SRGB: i32 = VK_FORMAT_ASTC_8x6_SRGB_BLOCK;
+
+        const ASTC_8x8_UNORM: i32 = VK_FORMAT_ASTC_8x8_UNORM_BLOCK;
+        const ASTC_8x8_UNORM_SRGB: i32 = VK_FORMAT_ASTC_8x8_SRGB_BLOCK;
+
+        const ASTC_10x5_UNORM: i32 = VK_FORMAT_ASTC_10x5_UNORM_BLOCK;
+        const ASTC_10x5_UNORM_SRGB: i32 = VK_FORMAT_ASTC_10x5_SRGB_BLOCK;
+
+        const ASTC_10x6_UNORM: i32 = VK_FORMAT_ASTC_10x6_UNORM_BLOCK;
+        const ASTC_10x6_UNORM_SRGB: i32 = VK_FORMAT_ASTC_10x6_SRGB_BLOCK;
+
+        const ASTC_10x8_UNORM: i32 = VK_FORMAT_ASTC_10x8_UNORM_BLOCK;
+        const ASTC_10x8_UNORM_SRGB: i32 = VK_FORMAT_ASTC_10x8_SRGB_BLOCK;
+
+        const ASTC_10x10_UNORM: i32 = VK_FORMAT_ASTC_10x10_UNORM_BLOCK;
+        const ASTC_10x10_UNORM_SRGB: i32 = VK_FORMAT_ASTC_10x10_SRGB_BLOCK;
+
+        const ASTC_12x10_UNORM: i32 = VK_FORMAT_ASTC_12x10_UNORM_BLOCK;
+        const ASTC_12x10_UNORM_SRGB: i32 = VK_FORMAT_ASTC_12x10_SRGB_BLOCK;
+
+        const ASTC_12x12_UNORM: i32 = VK_FORMAT_ASTC_12x12_UNORM_BLOCK;
+        const ASTC_12x12_UNORM_SRGB: i32 = VK_FORMAT_ASTC_12x12_SRGB_BLOCK;
     }
 
     pub struct VulkanPresentModeConstants;
@@ -267,10 +367,169 @@ mod constants {
             VK_PRIMITIVE_TOPOLOGY_TRIANGLE_STRIP_WITH_ADJACENCY;
         const PATCH_LIST: i32 = VK_PRIMITIVE_TOPOLOGY_PATCH_LIST;
     }
+
+    pub struct VulkanDescriptorTypeConstants;
+    impl crate::api::traits::constants::DescriptorTypeConstants for VulkanDescriptorTypeConstants {
+        const SAMPLER: i32 = VK_DESCRIPTOR_TYPE_SAMPLER;
+        const COMBINED_IMAGE_SAMPLER: i32 = VK_DESCRIPTOR_TYPE_COMBINED_IMAGE_SAMPLER;
+        const SAMPLED_IMAGE: i32 = VK_DESCRIPTOR_TYPE_SAMPLED_IMAGE;
+        const STORAGE_IMAGE: i32 = VK_DESCRIPTOR_TYPE_STORAGE_IMAGE;
+        const UNIFORM_TEXEL_BUFFER: i32 = VK_DESCRIPTOR_TYPE_UNIFORM_TEXEL_BUFFER;
+        const STORAGE_TEXEL_BUFFER: i32 = VK_DESCRIPTOR_TYPE_STORAGE_TEXEL_BUFFER;
+        const UNIFORM_BUFFER: i32 = VK_DESCRIPTOR_TYPE_UNIFORM_BUFFER;
+        const STORAGE_BUFFER: i32 = VK_DESCRIPTOR_TYPE_STORAGE_BUFFER;
+        const INPUT_ATTACHMENT: i32 = VK_DESCRIPTOR_TYPE_INPUT_ATTACHMENT;
+        const ACCELERATION_STRUCTURE: i32 = VK_DESCRIPTOR_TYPE_ACCELERATION_STRUCTURE_KHR;
+    }
+
+    pub struct VulkanShaderStageConstants;
+    impl crate::api::traits::constants::ShaderStageConstants for VulkanShaderStageConstants {
+        const VERTEX: i32 = VK_SHADER_STAGE_VERTEX_BIT;
+        const TESSELLATION_CONTROL: i32 = VK_SHADER_STAGE_TESSELLATION_CONTROL_BIT;
+        const TESSELLATION_EVALUATION: i32 = VK_SHADER_STAGE_TESSELLATION_EVALUATION_BIT;
+        const GEOMETRY: i32 = VK_SHADER_STAGE_GEOMETRY_BIT;
+        const FRAGMENT: i32 = VK_SHADER_STAGE_FRAGMENT_BIT;
+        const COMPUTE: i32 = VK_SHADER_STAGE_COMPUTE_BIT;
+    }
+
+    pub struct VulkanVertexInputRateConstants;
+    impl crate::api::traits::constants::VertexInputRateConstants for VulkanVertexInputRateConstants {
+        const VERTEX: i32 = VK_VERTEX_INPUT_RATE_VERTEX;
+        const INSTANCE: i32 = VK_VERTEX_INPUT_RATE_INSTANCE;
+    }
+
+    pub struct VulkanImageLayoutConstants;
+    impl crate::api::traits::constants::ImageLayoutConstants for VulkanImageLayoutConstants {
+        const UNDEFINED: i32 = VK_IMAGE_LAYOUT_UNDEFINED;
+        const GENERAL: i32 = VK_IMAGE_LAYOUT_GENERAL;
+        const COLOR_ATTACHMENT: i32 = VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL;
+        const DEPTH_STENCIL_ATTACHMENT: i32 = VK_IMAGE_LAYOUT_DEPTH_STENCIL_ATTACHMENT_OPTIMAL;
+        const DEPTH_STENCIL_READ_ONLY: i32 = VK_IMAGE_LAYOUT_DEPTH_STENCIL_READ_ONLY_OPTIMAL;
+        const SHADER_READ_ONLY: i32 = VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL;
+        const TRANSFER_SRC: i32 = VK_IMAGE_LAYOUT_TRANSFER_SRC_OPTIMAL;
+        const TRANSFER_DST: i32 = VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL;
+        const PREINITIALIZED: i32 = VK_IMAGE_LAYOUT_PREINITIALIZED;
+        const PRESENT_SRC: i32 = VK_IMAGE_LAYOUT_PRESENT_SRC_KHR;
+    }
+
+    // the synchronization2 bits share their values with the original flags they extend
+    pub struct VulkanAccessConstants;
+    impl crate::api::traits::constants::AccessConstants for VulkanAccessConstants {
+        const INDIRECT_COMMAND_READ: u64 = VK_ACCESS_2_INDIRECT_COMMAND_READ_BIT as u64;
+        const INDEX_READ: u64 = VK_ACCESS_2_INDEX_READ_BIT as u64;
+        const VERTEX_ATTRIBUTE_READ: u64 = VK_ACCESS_2_VERTEX_ATTRIBUTE_READ_BIT as u64;
+        const UNIFORM_READ: u64 = VK_ACCESS_2_UNIFORM_READ_BIT as u64;
+        const INPUT_ATTACHMENT_READ: u64 = VK_ACCESS_2_INPUT_ATTACHMENT_READ_BIT as u64;
+        const SHADER_READ: u64 = VK_ACCESS_2_SHADER_READ_BIT as u64;
+        const SHADER_WRITE: u64 = VK_ACCESS_2_SHADER_WRITE_BIT as u64;
+        const COLOR_ATTACHMENT_READ: u64 = VK_ACCESS_2_COLOR_ATTACHMENT_READ_BIT as u64;
+        const COLOR_ATTACHMENT_WRITE: u64 = VK_ACCESS_2_COLOR_ATTACHMENT_WRITE_BIT as u64;
+        const DEPTH_STENCIL_ATTACHMENT_READ: u64 =
+            VK_ACCESS_2_DEPTH_STENCIL_ATTACHMENT_READ_BIT as u64;
+        const DEPTH_STENCIL_ATTACHMENT_WRITE: u64 =
+            VK_ACCESS_2_DEPTH_STENCIL_ATTACHMENT_WRITE_BIT as u64;
+        const TRANSFER_READ: u64 = VK_ACCESS_2_TRANSFER_READ_BIT as u64;
+        const TRANSFER_WRITE: u64 = VK_ACCESS_2_TRANSFER_WRITE_BIT as u64;
+        const HOST_READ: u64 = VK_ACCESS_2_HOST_READ_BIT as u64;
+        const HOST_WRITE: u64 = VK_ACCESS_2_HOST_WRITE_BIT as u64;
+        const MEMORY_READ: u64 = VK_ACCESS_2_MEMORY_READ_BIT as u64;
+        const MEMORY_WRITE: u64 = VK_ACCESS_2_MEMORY_WRITE_BIT as u64;
+    }
+
+    pub struct VulkanPipelineStageConstants;
+    impl crate::api::traits::constants::PipelineStageConstants for VulkanPipelineStageConstants {
+        const TOP_OF_PIPE: u64 = VK_PIPELINE_STAGE_2_TOP_OF_PIPE_BIT as u64;
+        const DRAW_INDIRECT: u64 = VK_PIPELINE_STAGE_2_DRAW_INDIRECT_BIT as u64;
+        const VERTEX_INPUT: u64 = VK_PIPELINE_STAGE_2_VERTEX_INPUT_BIT as u64;
+        const VERTEX_SHADER: u64 = VK_PIPELINE_STAGE_2_VERTEX_SHADER_BIT as u64;
+        const TESSELLATION_CONTROL_SHADER: u64 =
+            VK_PIPELINE_STAGE_2_TESSELLATION_CONTROL_SHADER_BIT as u64;
+        const TESSELLATION_EVALUATION_SHADER: u64 =
+            VK_PIPELINE_STAGE_2_TESSELLATION_EVALUATION_SHADER_BIT as u64;
+        const GEOMETRY_SHADER: u64 = VK_PIPELINE_STAGE_2_GEOMETRY_SHADER_BIT as u64;
+        const FRAGMENT_SHADER: u64 = VK_PIPELINE_STAGE_2_FRAGMENT_SHADER_BIT as u64;
+        const EARLY_FRAGMENT_TESTS: u64 = VK_PIPELINE_STAGE_2_EARLY_FRAGMENT_TESTS_BIT as u64;
+        const LATE_FRAGMENT_TESTS: u64 = VK_PIPELINE_STAGE_2_LATE_FRAGMENT_TESTS_BIT as u64;
+        const COLOR_ATTACHMENT_OUTPUT: u64 = VK_PIPELINE_STAGE_2_COLOR_ATTACHMENT_OUTPUT_BIT as u64;
+        const COMPUTE_SHADER: u64 = VK_PIPELINE_STAGE_2_COMPUTE_SHADER_BIT as u64;
+        const TRANSFER: u64 = VK_PIPELINE_STAGE_2_TRANSFER_BIT as u64;
+        const BOTTOM_OF_PIPE: u64 = VK_PIPELINE_STAGE_2_BOTTOM_OF_PIPE_BIT as u64;
+        const HOST: u64 = VK_PIPELINE_STAGE_2_HOST_BIT as u64;
+        const ALL_GRAPHICS: u64 = VK_PIPELINE_STAGE_2_ALL_GRAPHICS_BIT as u64;
+        const ALL_COMMANDS: u64 = VK_PIPELINE_STAGE_2_ALL_COMMANDS_BIT as u64;
+    }
+
+    pub struct VulkanImageAspectConstants;
+    impl crate::api::traits::constants::ImageAspectConstants for VulkanImageAspectConstants {
+        const COLOR: i32 = VK_IMAGE_ASPECT_COLOR_BIT;
+        const DEPTH: i32 = VK_IMAGE_ASPECT_DEPTH_BIT;
+        const STENCIL: i32 = VK_IMAGE_ASPECT_STENCIL_BIT;
+    }
+
+    pub struct VulkanBufferUsageConstants;
+    impl crate::api::traits::constants::BufferUsageConstants for VulkanBufferUsageConstants {
+        const TRANSFER_SRC: i32 = VK_BUFFER_USAGE_TRANSFER_SRC_BIT;
+        const TRANSFER_DST: i32 = VK_BUFFER_USAGE_TRANSFER_DST_BIT;
+        const UNIFORM: i32 = VK_BUFFER_USAGE_UNIFORM_BUFFER_BIT;
+        const STORAGE: i32 = VK_BUFFER_USAGE_STORAGE_BUFFER_BIT;
+        const INDEX: i32 = VK_BUFFER_USAGE_INDEX_BUFFER_BIT;
+        const VERTEX: i32 = VK_BUFFER_USAGE_VERTEX_BUFFER_BIT;
+        const INDIRECT: i32 = VK_BUFFER_USAGE_INDIRECT_BUFFER_BIT;
+    }
+
+    pub struct VulkanImageUsageConstants;
+    impl crate::api::traits::constants::ImageUsageConstants for VulkanImageUsageConstants {
+        const TRANSFER_SRC: i32 = VK_IMAGE_USAGE_TRANSFER_SRC_BIT;
+        const TRANSFER_DST: i32 = VK_IMAGE_USAGE_TRANSFER_DST_BIT;
+        const SAMPLED: i32 = VK_IMAGE_USAGE_SAMPLED_BIT;
+        const STORAGE: i32 = VK_IMAGE_USAGE_STORAGE_BIT;
+        const COLOR_ATTACHMENT: i32 = VK_IMAGE_USAGE_COLOR_ATTACHMENT_BIT;
+        const DEPTH_STENCIL_ATTACHMENT: i32 = VK_IMAGE_USAGE_DEPTH_STENCIL_ATTACHMENT_BIT;
+    }
+
+    pub struct VulkanFormatFeatureConstants;
+    impl crate::api::traits::constants::FormatFeatureConstants for VulkanFormatFeatureConstants {
+        const SAMPLED: i32 = VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT;
+        const FILTERABLE: i32 = VK_FORMAT_FEATURE_SAMPLED_IMAGE_FILTER_LINEAR_BIT;
+        const COLOR_ATTACHMENT: i32 = VK_FORMAT_FEATURE_COLOR_ATTACHMENT_BIT;
+        const BLENDABLE: i32 = VK_FORMAT_FEATURE_COLOR_ATTACHMENT_BLEND_BIT;
+        const DEPTH_STENCIL_ATTACHMENT: i32 = VK_FORMAT_FEATURE_DEPTH_STENCIL_ATTACHMENT_BIT;
+        const STORAGE: i32 = VK_FORMAT_FEATURE_STORAGE_IMAGE_BIT;
+        const VERTEX_BUFFER: i32 = VK_FORMAT_FEATURE_VERTEX_BUFFER_BIT;
+        const BLIT_SRC: i32 = VK_FORMAT_FEATURE_BLIT_SRC_BIT;
+        const BLIT_DST: i32 = VK_FORMAT_FEATURE_BLIT_DST_BIT;
+    }
+
+    pub struct VulkanAttachmentLoadOpConstants;
+    impl crate::api::traits::constants::AttachmentLoadOpConstants for VulkanAttachmentLoadOpConstants {
+        const LOAD: i32 = VK_ATTACHMENT_LOAD_OP_LOAD;
+        const CLEAR: i32 = VK_ATTACHMENT_LOAD_OP_CLEAR;
+        const DONT_CARE: i32 = VK_ATTACHMENT_LOAD_OP_DONT_CARE;
+    }
+
+    pub struct VulkanAttachmentStoreOpConstants;
+    impl crate::api::traits::constants::AttachmentStoreOpConstants
+        for VulkanAttachmentStoreOpConstants
+    {
+        const STORE: i32 = VK_ATTACHMENT_STORE_OP_STORE;
+        const DONT_CARE: i32 = VK_ATTACHMENT_STORE_OP_DONT_CARE;
+    }
+}
+
+pub trait VulkanHandle: Copy {
+    fn as_raw(self) -> u64;
+}
+
+impl<T> VulkanHandle for *mut T {
+    fn as_raw(self) -> u64 {
+        self as usize as u64
+    }
 }
 
 pub trait VulkanObject {
-    type Handle;
+    type Handle: VulkanHandle;
+
+    const OBJECT_TYPE: VkObjectType;
 
     fn handle(&self) -> Self::Handle;
 }
@@ -281,6 +540,12 @@ pub trait VulkanInstanceObject: VulkanObject {
 
 pub trait VulkanDeviceObject: VulkanInstanceObject {
     fn device(&self) -> &VulkanDevice;
+
+    /// Names the object in validation messages and debugging tools.
+    fn set_debug_name(&self, name: &str) -> crate::Result<()> {
+        self.device()
+            .set_object_name(Self::OBJECT_TYPE, self.handle().as_raw(), name)
+    }
 }
 
 impl<T: VulkanDeviceObject> VulkanInstanceObject for T {
@@ -297,119 +562,60 @@ pub trait VulkanType {
 
 type Ownership<T> = Arc<T>;
 
+/// Turns the result of a raw call into an error for error codes, passing success codes through.
+pub(crate) fn check(result: VkResult) -> crate::Result<VkResult> {
+    match result {
+        VK_ERROR_OUT_OF_DATE_KHR => Err(crate::Error::SwapchainOutOfDate),
+        result if result < 0 => Err(crate::Error::VulkanResult(result)),
+        result => Ok(result),
+    }
+}
+
 impl Into<VkPresentModeKHR> for crate::PresentMode {
     fn into(self) -> VkPresentModeKHR {
-        unsafe { std::mem::transmute(self) }
+        self.raw()
     }
 }
 
 impl From<VkPresentModeKHR> for crate::PresentMode {
     fn from(value: VkPresentModeKHR) -> Self {
-        unsafe { std::mem::transmute(value) }
+        Self::from_raw(value)
     }
 }
 
 impl Into<VkCompositeAlphaFlagBitsKHR> for crate::CompositeAlphaMode {
     fn into(self) -> VkCompositeAlphaFlagBitsKHR {
-        unsafe { std::mem::transmute(self) }
+        self as VkCompositeAlphaFlagBitsKHR
     }
 }
 
 impl Into<VkFormat> for crate::Format {
     fn into(self) -> VkFormat {
-        unsafe { std::mem::transmute(self) }
+        self.raw()
     }
 }
 
 impl From<VkFormat> for crate::Format {
     fn from(value: VkFormat) -> Self {
-        use crate::Format;
-
-        if !cfg!(debug_assertions) {
-            unsafe { std::mem::transmute(value) }
-        } else {
-            match value {
-                VK_FORMAT_R8_UINT => Format::R8_UINT,
-                VK_FORMAT_R8_SINT => Format::R8_SINT,
-                VK_FORMAT_R8_UNORM => Format::R8_UNORM,
-                VK_FORMAT_R8_SNORM => Format::R8_SNORM,
-
-                VK_FORMAT_R8G8_UINT => Format::R8G8_UINT,
-                VK_FORMAT_R8G8_SINT => Format::R8G8_SINT,
-                VK_FORMAT_R8G8_UNORM => Format::R8G8_UNORM,
-                VK_FORMAT_R8G8_SNORM => Format::R8G8_SNORM,
-
-                VK_FORMAT_R8G8B8A8_UINT => Format::R8G8B8A8_UINT,
-                VK_FORMAT_R8G8B8A8_SINT => Format::R8G8B8A8_SINT,
-                VK_FORMAT_R8G8B8A8_UNORM => Format::R8G8B8A8_UNORM,
-                VK_FORMAT_R8G8B8A8_SNORM => Format::R8G8B8A8_SNORM,
-                VK_FORMAT_R8G8B8A8_SRGB => Format::R8G8B8A8_UNORM_SRGB,
-
-                VK_FORMAT_B8G8R8A8_UNORM => Format::B8G8R8A8_UNORM,
-                VK_FORMAT_B8G8R8A8_SRGB => Format::B8G8R8A8_UNORM_SRGB,
-
-                VK_FORMAT_R16_UINT => Format::R16_UINT,
-                VK_FORMAT_R16_SINT => Format::R16_SINT,
-                VK_FORMAT_R16_UNORM => Format::R16_UNORM,
-                VK_FORMAT_R16_SNORM => Format::R16_SNORM,
-
-                VK_FORMAT_R16G16_UINT => Format::R16G16_UINT,
-                VK_FORMAT_R16G16_SINT => Format::R16G16_SINT,
-                VK_FORMAT_R16G16_UNORM => Format::R16G16_UNORM,
-                VK_FORMAT_R16G16_SNORM => Format::R16G16_SNORM,
-
-                VK_FORMAT_R16G16B16A16_UINT => Format::R16G16B16A16_UINT,
-                VK_FORMAT_R16G16B16A16_SINT => Format::R16G16B16A16_SINT,
-                VK_FORMAT_R16G16B16A16_UNORM => Format::R16G16B16A16_UNORM,
-                VK_FORMAT_R16G16B16A16_SNORM => Format::R16G16B16A16_SNORM,
-                VK_FORMAT_R16G16B16A16_SFLOAT => Format::R16G16B16A16_SFLOAT,
-
-                VK_FORMAT_R32_UINT => Format::R32_UINT,
-                VK_FORMAT_R32_SINT => Format::R32_SINT,
-                VK_FORMAT_R32_SFLOAT => Format::R32_SFLOAT,
-
-                VK_FORMAT_R32G32_UINT => Format::R32G32_UINT,
-                VK_FORMAT_R32G32_SINT => Format::R32G32_SINT,
-                VK_FORMAT_R32G32_SFLOAT => Format::R32G32_SFLOAT,
-
-                VK_FORMAT_R32G32B32_UINT => Format::R32G32B32_UINT,
-                VK_FORMAT_R32G32B32_SINT => Format::R32G32B32_SINT,
-                VK_FORMAT_R32G32B32_SFLOAT => Format::R32G32B32_SFLOAT,
-
-                VK_FORMAT_R32G32B32A32_UINT => Format::R32G32B32A32_UINT,
-                VK_FORMAT_R32G32B32A32_SINT => Format::R32G32B32A32_SINT,
-                VK_FORMAT_R32G32B32A32_SFLOAT => Format::R32G32B32A32_SFLOAT,
-
-                VK_FORMAT_A2B10G10R10_UINT_PACK32 => Format::R10G10B10A2_UINT,
-                VK_FORMAT_A2B10G10R10_UNORM_PACK32 => Format::R10G10B10A2_UNORM,
-
-                _ => panic!("Unknown format"),
-            }
-        }
+        Self::from_raw(value)
     }
 }
 
 impl Into<VkColorSpaceKHR> for crate::Colorspace {
     fn into(self) -> VkColorSpaceKHR {
-        unsafe { std::mem::transmute(self) }
+        self.raw()
     }
 }
 
 impl From<VkColorSpaceKHR> for crate::Colorspace {
     fn from(value: VkColorSpaceKHR) -> Self {
-        use crate::Colorspace;
-
-        if !cfg!(debug_assertions) {
-            unsafe { std::mem::transmute(value) }
-        } else {
-            match value {
-                VK_COLOR_SPACE_SRGB_NONLINEAR_KHR => Colorspace::SRGB_NONLINEAR,
-                VK_COLOR_SPACE_EXTENDED_SRGB_LINEAR_EXT => Colorspace::SRGB_EXT_LINEAR,
-                VK_COLOR_SPACE_HDR10_ST2084_EXT => Colorspace::HDR10_ST2084,
-                VK_COLOR_SPACE_HDR10_HLG_EXT => Colorspace::HDR10_HLG,
-                _ => panic!("Unknown colorspace"),
-            }
-        }
+        Self::from_raw(value)
+    }
+}
+
+impl From<VkPhysicalDeviceType> for crate::DeviceType {
+    fn from(value: VkPhysicalDeviceType) -> Self {
+        Self::from_raw(value)
     }
 }
 
@@ -476,87 +682,205 @@ impl From<VkRect2D> for crate::Rect2D {
     }
 }
 
+/// Checked conversion from a native value to an enum that has no `Unknown` variant.
+macro_rules! try_from_native {
+    ($native:ty => $name:ident { $($variant:ident),* $(,)? }) => {
+        impl TryFrom<$native> for crate::$name {
+            type Error = crate::Error;
+
+            fn try_from(value: $native) -> crate::Result<Self> {
+                $(
+                    if value == crate::$name::$variant as $native {
+                        return Ok(crate::$name::$variant);
+                    }
+                )*
+
+                Err(crate::Error::UnknownValue {
+                    kind: stringify!($name),
+                    value: value as i64,
+                })
+            }
+        }
+    };
+}
+
 impl Into<VkPrimitiveTopology> for crate::PrimitiveTopology {
     fn into(self) -> VkPrimitiveTopology {
-        unsafe { std::mem::transmute(self) }
+        self as VkPrimitiveTopology
     }
 }
 
-impl From<VkPrimitiveTopology> for crate::PrimitiveTopology {
-    fn from(value: VkPrimitiveTopology) -> Self {
-        unsafe { std::mem::transmute(value) }
-    }
-}
+try_from_native!(VkPrimitiveTopology => PrimitiveTopology {
+    PointList,
+    LineList,
+    LineStrip,
+    TriangleList,
+    TriangleStrip,
+    TriangleFan,
+    LineListWithAdjacency,
+    LineStripWithAdjacency,
+    TriangleListWithAdjacency,
+    TriangleStripWithAdjacency,
+    PatchList,
+});
 
 impl Into<VkPolygonMode> for crate::PolygonMode {
     fn into(self) -> VkPolygonMode {
-        unsafe { std::mem::transmute(self) }
+        self as VkPolygonMode
     }
 }
 
-impl From<VkPolygonMode> for crate::PolygonMode {
-    fn from(value: VkPolygonMode) -> Self {
-        unsafe { std::mem::transmute(value) }
-    }
-}
+try_from_native!(VkPolygonMode => PolygonMode { Fill, Wireframe });
 
 impl Into<VkCullModeFlags> for crate::CullMode {
     fn into(self) -> VkCullModeFlags {
-        unsafe { std::mem::transmute(self) }
+        self as VkCullModeFlags
     }
 }
 
-impl From<VkCullModeFlags> for crate::CullMode {
-    fn from(value: VkCullModeFlags) -> Self {
-        unsafe { std::mem::transmute(value) }
-    }
-}
+try_from_native!(VkCullModeFlags => CullMode { None, Front, Back });
 
 impl Into<VkFrontFace> for crate::FrontFace {
     fn into(self) -> VkFrontFace {
-        unsafe { std::mem::transmute(self) }
+        self as VkFrontFace
     }
 }
 
-impl From<VkFrontFace> for crate::FrontFace {
-    fn from(value: VkFrontFace) -> Self {
-        unsafe { std::mem::transmute(value) }
-    }
-}
+try_from_native!(VkFrontFace => FrontFace { Clockwise, CounterClockwise });
 
 impl Into<VkBlendOp> for crate::BlendOp {
     fn into(self) -> VkBlendOp {
-        unsafe { std::mem::transmute(self) }
+        self as VkBlendOp
     }
 }
 
-impl From<VkBlendOp> for crate::BlendOp {
-    fn from(value: VkBlendOp) -> Self {
-        unsafe { std::mem::transmute(value) }
-    }
-}
+try_from_native!(VkBlendOp => BlendOp { Add, Subtract, ReverseSubtract, Min, Max });
 
 impl Into<VkBlendFactor> for crate::BlendFactor {
     fn into(self) -> VkBlendFactor {
-        unsafe { std::mem::transmute(self) }
+        self as VkBlendFactor
     }
 }
 
-impl From<VkBlendFactor> for crate::BlendFactor {
-    fn from(value: VkBlendFactor) -> Self {
-        unsafe { std::mem::transmute(value) }
-    }
-}
+try_from_native!(VkBlendFactor => BlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    DstColor,
+    OneMinusDstColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstAlpha,
+    OneMinusDstAlpha,
+});
 
 impl Into<VkColorComponentFlags> for crate::ColorComponentFlags {
     fn into(self) -> VkColorComponentFlags {
-        unsafe { std::mem::transmute(self) }
+        self.bits() as VkColorComponentFlags
     }
 }
 
 impl From<VkColorComponentFlags> for crate::ColorComponentFlags {
     fn from(value: VkColorComponentFlags) -> Self {
-        unsafe { std::mem::transmute(value) }
+        Self::from_bits_retain(value as i32)
+    }
+}
+
+impl Into<VkDescriptorType> for crate::DescriptorType {
+    fn into(self) -> VkDescriptorType {
+        self as VkDescriptorType
+    }
+}
+
+impl Into<VkShaderStageFlags> for crate::ShaderStageFlags {
+    fn into(self) -> VkShaderStageFlags {
+        self.bits() as VkShaderStageFlags
+    }
+}
+
+impl Into<VkImageLayout> for crate::ImageLayout {
+    fn into(self) -> VkImageLayout {
+        self as VkImageLayout
+    }
+}
+
+impl Into<VkAccessFlags2> for crate::AccessFlags {
+    fn into(self) -> VkAccessFlags2 {
+        self.bits() as VkAccessFlags2
+    }
+}
+
+impl Into<VkAccessFlags> for crate::AccessFlags {
+    fn into(self) -> VkAccessFlags {
+        self.bits() as VkAccessFlags
+    }
+}
+
+impl Into<VkPipelineStageFlags2> for crate::PipelineStageFlags {
+    fn into(self) -> VkPipelineStageFlags2 {
+        self.bits() as VkPipelineStageFlags2
+    }
+}
+
+impl Into<VkPipelineStageFlags> for crate::PipelineStageFlags {
+    fn into(self) -> VkPipelineStageFlags {
+        self.bits() as VkPipelineStageFlags
+    }
+}
+
+impl Into<VkImageAspectFlags> for crate::ImageAspectFlags {
+    fn into(self) -> VkImageAspectFlags {
+        self.bits() as VkImageAspectFlags
+    }
+}
+
+impl Into<VkBufferUsageFlags> for crate::BufferUsageFlags {
+    fn into(self) -> VkBufferUsageFlags {
+        self.bits() as VkBufferUsageFlags
+    }
+}
+
+impl Into<VkImageUsageFlags> for crate::ImageUsageFlags {
+    fn into(self) -> VkImageUsageFlags {
+        self.bits() as VkImageUsageFlags
+    }
+}
+
+impl Into<VkAttachmentLoadOp> for crate::AttachmentLoadOp {
+    fn into(self) -> VkAttachmentLoadOp {
+        self as VkAttachmentLoadOp
+    }
+}
+
+impl Into<VkAttachmentStoreOp> for crate::AttachmentStoreOp {
+    fn into(self) -> VkAttachmentStoreOp {
+        self as VkAttachmentStoreOp
+    }
+}
+
+impl Into<VkClearValue> for crate::ClearValue {
+    fn into(self) -> VkClearValue {
+        match self {
+            crate::ClearValue::Color(crate::ClearColor::Float(float32)) => VkClearValue {
+                color: VkClearColorValue { float32 },
+            },
+            crate::ClearValue::Color(crate::ClearColor::Int(int32)) => VkClearValue {
+                color: VkClearColorValue { int32 },
+            },
+            crate::ClearValue::Color(crate::ClearColor::Uint(uint32)) => VkClearValue {
+                color: VkClearColorValue { uint32 },
+            },
+            crate::ClearValue::DepthStencil { depth, stencil } => VkClearValue {
+                depthStencil: VkClearDepthStencilValue { depth, stencil },
+            },
+        }
+    }
+}
+
+impl Into<VkVertexInputRate> for crate::VertexInputRate {
+    fn into(self) -> VkVertexInputRate {
+        self as VkVertexInputRate
     }
 }
 
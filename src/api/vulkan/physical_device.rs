@@ -17,7 +17,7 @@ use vulkan_sys::*;
 struct PhysicalDeviceOwnership {
     handle: VkPhysicalDevice,
     properties: VulkanPhysicalDeviceProperties,
-    // features: VulkanPhysicalDeviceFeatures,
+    features: crate::DeviceFeatureFlags,
 }
 
 #[derive(Clone)]
@@ -31,7 +31,7 @@ impl Debug for VulkanPhysicalDevice {
         f.debug_struct(type_name::<Self>())
             .field("handle", &self.handle)
             .field("properties", &self.ownership.properties)
-            // .field("features", &self.ownership.features)
+            .field("features", &self.ownership.features)
             .finish()
     }
 }
@@ -39,6 +39,8 @@ impl Debug for VulkanPhysicalDevice {
 impl VulkanObject for VulkanPhysicalDevice {
     type Handle = VkPhysicalDevice;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_PHYSICAL_DEVICE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -47,17 +49,35 @@ impl VulkanObject for VulkanPhysicalDevice {
 impl VulkanPhysicalDevice {
     pub(crate) fn new(
         handle: VkPhysicalDevice,
-        properties: VkPhysicalDeviceProperties,
+        properties: VulkanPhysicalDeviceProperties,
         features: VkPhysicalDeviceFeatures,
     ) -> Self {
         let ownership = Ownership::new(PhysicalDeviceOwnership {
             handle,
-            properties: properties.into(),
-            // features: features.into(),
+            properties,
+            features: supported_features(handle, &features),
         });
 
         Self { handle, ownership }
     }
+
+    pub(crate) fn supports_extension(&self, name: &std::ffi::CStr) -> bool {
+        supports_extension(self.handle, name)
+    }
+
+    pub(crate) fn queue_families(&self) -> Vec<VkQueueFamilyProperties> {
+        wrapper::get_physical_device_queue_family_properties(
+            vkGetPhysicalDeviceQueueFamilyProperties,
+            self.handle,
+        )
+    }
+
+    pub(crate) fn memory_properties(&self) -> VkPhysicalDeviceMemoryProperties {
+        wrapper::get_physical_device_memory_properties(
+            vkGetPhysicalDeviceMemoryProperties,
+            self.handle,
+        )
+    }
 }
 
 impl crate::api::traits::Device<VulkanApi> for VulkanPhysicalDevice {
@@ -65,9 +85,19 @@ impl crate::api::traits::Device<VulkanApi> for VulkanPhysicalDevice {
         &self.ownership.properties
     }
 
-    // fn features(&self) -> &<VulkanApi as GraphicsApi>::DeviceFeatures {
-    //     &self.ownership.features
-    // }
+    fn features(&self) -> crate::DeviceFeatureFlags {
+        self.ownership.features
+    }
+
+    fn dedicated_memory(&self) -> u64 {
+        let properties = self.memory_properties();
+
+        properties.memoryHeaps[..properties.memoryHeapCount as usize]
+            .iter()
+            .filter(|heap| heap.flags & VK_MEMORY_HEAP_DEVICE_LOCAL_BIT as VkMemoryHeapFlags != 0)
+            .map(|heap| heap.size)
+            .sum()
+    }
 
     fn supports_surface(&self, surface: <VulkanApi as GraphicsApi>::Surface) -> bool {
         // todo : implement queue family indexing
@@ -116,19 +146,147 @@ impl crate::api::traits::Device<VulkanApi> for VulkanPhysicalDevice {
         )
         .map(|formats| formats.into_iter().map(|pm| pm.into()).collect())?)
     }
+
+    fn format_capabilities(&self, format: crate::Format) -> crate::FormatCapabilities {
+        let properties = wrapper::get_physical_device_format_properties(
+            vkGetPhysicalDeviceFormatProperties,
+            self.handle(),
+            format.into(),
+        );
+
+        // features xgpu has no flag for are dropped
+        let features = |native: VkFormatFeatureFlags| {
+            crate::FormatFeatureFlags::from_bits_truncate(native as i32)
+        };
+
+        crate::FormatCapabilities {
+            optimal_tiling: features(properties.optimalTilingFeatures),
+            linear_tiling: features(properties.linearTilingFeatures),
+            buffer: features(properties.bufferFeatures),
+        }
+    }
+}
+
+fn supported_features(
+    handle: VkPhysicalDevice,
+    features: &VkPhysicalDeviceFeatures,
+) -> crate::DeviceFeatureFlags {
+    use crate::DeviceFeatureFlags as Flags;
+
+    let mut flags = Flags::empty();
+
+    let core = [
+        (features.geometryShader, Flags::GEOMETRY_SHADER),
+        (features.tessellationShader, Flags::TESSELLATION_SHADER),
+        (features.samplerAnisotropy, Flags::SAMPLER_ANISOTROPY),
+        (features.fillModeNonSolid, Flags::FILL_MODE_NON_SOLID),
+        (features.wideLines, Flags::WIDE_LINES),
+        (features.multiDrawIndirect, Flags::MULTI_DRAW_INDIRECT),
+        (features.textureCompressionBC, Flags::TEXTURE_COMPRESSION_BC),
+        (
+            features.textureCompressionETC2,
+            Flags::TEXTURE_COMPRESSION_ETC2,
+        ),
+        (
+            features.textureCompressionASTC_LDR,
+            Flags::TEXTURE_COMPRESSION_ASTC,
+        ),
+    ];
+
+    for (supported, flag) in core {
+        flags.set(flag, supported == VK_TRUE);
+    }
+
+    let extensions = [
+        (
+            VK_KHR_DYNAMIC_RENDERING_EXTENSION_NAME,
+            Flags::DYNAMIC_RENDERING,
+        ),
+        (
+            VK_KHR_SYNCHRONIZATION_2_EXTENSION_NAME,
+            Flags::SYNCHRONIZATION2,
+        ),
+    ];
+
+    for (name, flag) in extensions {
+        let name = unsafe { std::ffi::CStr::from_ptr(name.as_ptr() as *const _) };
+        flags.set(flag, supports_extension(handle, name));
+    }
+
+    flags
+}
+
+fn supports_extension(handle: VkPhysicalDevice, name: &std::ffi::CStr) -> bool {
+    wrapper::enumerate_device_extension_properties(
+        vkEnumerateDeviceExtensionProperties,
+        handle,
+        None,
+    )
+    .map(|extensions| {
+        extensions.iter().any(|extension| unsafe {
+            std::ffi::CStr::from_ptr(extension.extensionName.as_ptr()) == name
+        })
+    })
+    .unwrap_or(false)
 }
 
 /*
    Physical Device Properties
 */
 
+pub(crate) type GetPhysicalDeviceProperties2 =
+    unsafe extern "C" fn(VkPhysicalDevice, *mut VkPhysicalDeviceProperties2);
+
 pub struct VulkanPhysicalDeviceProperties {
     native: VkPhysicalDeviceProperties,
+    /// Set when the device supports `VK_KHR_driver_properties`.
+    driver: Option<VkPhysicalDeviceDriverProperties>,
 }
 
-impl From<VkPhysicalDeviceProperties> for VulkanPhysicalDeviceProperties {
-    fn from(native: VkPhysicalDeviceProperties) -> Self {
-        Self { native }
+impl VulkanPhysicalDeviceProperties {
+    /// Queries the device's properties, including driver properties when `get_properties2` is
+    /// available.
+    pub(crate) fn query(
+        handle: VkPhysicalDevice,
+        get_properties2: Option<GetPhysicalDeviceProperties2>,
+    ) -> Self {
+        let native = wrapper::get_physical_device_properties(vkGetPhysicalDeviceProperties, handle);
+
+        let driver_properties_name = unsafe {
+            std::ffi::CStr::from_ptr(VK_KHR_DRIVER_PROPERTIES_EXTENSION_NAME.as_ptr() as *const _)
+        };
+
+        let driver = get_properties2
+            .filter(|_| supports_extension(handle, driver_properties_name))
+            .map(|get_properties2| {
+                let mut driver: VkPhysicalDeviceDriverProperties = unsafe { std::mem::zeroed() };
+                driver.sType = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_DRIVER_PROPERTIES;
+
+                let mut properties2: VkPhysicalDeviceProperties2 = unsafe { std::mem::zeroed() };
+                properties2.sType = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_PROPERTIES_2;
+                properties2.pNext = &mut driver as *mut _ as *mut _;
+
+                unsafe { get_properties2(handle, &mut properties2) };
+
+                driver.pNext = std::ptr::null_mut();
+                driver
+            });
+
+        Self { native, driver }
+    }
+}
+
+fn c_str(chars: &[std::ffi::c_char]) -> &str {
+    unsafe { std::ffi::CStr::from_ptr(chars.as_ptr()) }
+        .to_str()
+        .unwrap_or_default()
+}
+
+impl VulkanType for VulkanPhysicalDeviceProperties {
+    type Type = VkPhysicalDeviceProperties;
+
+    fn native(&self) -> &Self::Type {
+        &self.native
     }
 }
 
@@ -140,19 +298,63 @@ impl Debug for VulkanPhysicalDeviceProperties {
 
 impl crate::api::traits::DeviceProperties<VulkanApi> for VulkanPhysicalDeviceProperties {
     fn name(&self) -> &str {
-        unsafe {
-            std::ffi::CStr::from_ptr(self.native.deviceName.as_ptr())
-                .to_str()
-                .unwrap()
-        }
+        c_str(&self.native.deviceName)
     }
 
     fn device_type(&self) -> crate::DeviceType {
-        unsafe { std::mem::transmute(self.native.deviceType) }
+        self.native.deviceType.into()
     }
 
     fn vendor(&self) -> crate::Vendor {
-        unsafe { std::mem::transmute(self.native.vendorID) }
+        self.native.vendorID.into()
+    }
+
+    fn vendor_id(&self) -> u32 {
+        self.native.vendorID
+    }
+
+    fn device_id(&self) -> u32 {
+        self.native.deviceID
+    }
+
+    fn driver_version(&self) -> crate::DriverVersion {
+        crate::DriverVersion::decode(self.vendor(), self.native.driverVersion)
+    }
+
+    fn api_version(&self) -> crate::ApiVersion {
+        let version = self.native.apiVersion;
+
+        crate::ApiVersion {
+            major: (version >> 22) & 0x7F,
+            minor: (version >> 12) & 0x3FF,
+            patch: version & 0xFFF,
+        }
+    }
+
+    fn limits(&self) -> crate::DeviceLimits {
+        let limits = &self.native.limits;
+
+        crate::DeviceLimits {
+            max_image_dimension_2d: limits.maxImageDimension2D,
+            max_image_array_layers: limits.maxImageArrayLayers,
+            max_color_attachments: limits.maxColorAttachments,
+            max_bound_descriptor_sets: limits.maxBoundDescriptorSets,
+            max_push_constants_size: limits.maxPushConstantsSize,
+            max_vertex_input_attributes: limits.maxVertexInputAttributes,
+            max_compute_work_group_invocations: limits.maxComputeWorkGroupInvocations,
+        }
+    }
+
+    fn pipeline_cache_uuid(&self) -> [u8; 16] {
+        self.native.pipelineCacheUUID
+    }
+
+    fn driver_name(&self) -> Option<&str> {
+        self.driver.as_ref().map(|driver| c_str(&driver.driverName))
+    }
+
+    fn driver_info(&self) -> Option<&str> {
+        self.driver.as_ref().map(|driver| c_str(&driver.driverInfo))
     }
 }
 
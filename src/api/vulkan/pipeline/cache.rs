@@ -0,0 +1,270 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::api::traits::*;
+use std::fmt::Debug;
+
+use crate::api::vulkan::*;
+use crate::PipelineCacheCreateInfo;
+use vulkan_sys::*;
+
+/*
+   Pipeline Cache Header
+*/
+
+const PIPELINE_CACHE_HEADER_SIZE: usize = 16 + VK_UUID_SIZE as usize;
+
+/// The header vulkan places at the start of serialized pipeline cache data.
+///
+/// All fields are stored least significant byte first regardless of host byte order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct PipelineCacheHeader {
+    header_size: u32,
+    header_version: u32,
+    vendor_id: u32,
+    device_id: u32,
+    pipeline_cache_uuid: [u8; VK_UUID_SIZE as usize],
+}
+
+impl PipelineCacheHeader {
+    fn read(data: &[u8]) -> Option<Self> {
+        if data.len() < PIPELINE_CACHE_HEADER_SIZE {
+            return None;
+        }
+
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+
+        let header = Self {
+            header_size: read_u32(0),
+            header_version: read_u32(4),
+            vendor_id: read_u32(8),
+            device_id: read_u32(12),
+            pipeline_cache_uuid: data[16..PIPELINE_CACHE_HEADER_SIZE].try_into().unwrap(),
+        };
+
+        let valid = header.header_size as usize >= PIPELINE_CACHE_HEADER_SIZE
+            && header.header_size as usize <= data.len()
+            && header.header_version == VK_PIPELINE_CACHE_HEADER_VERSION_ONE as u32;
+
+        valid.then_some(header)
+    }
+
+    fn is_compatible(&self, properties: &VkPhysicalDeviceProperties) -> bool {
+        self.vendor_id == properties.vendorID
+            && self.device_id == properties.deviceID
+            && self.pipeline_cache_uuid[..] == properties.pipelineCacheUUID[..]
+    }
+}
+
+/*
+   Pipeline Cache
+*/
+
+struct PipelineCacheOwnership {
+    handle: VkPipelineCache,
+    device: VulkanDevice,
+}
+
+impl Drop for PipelineCacheOwnership {
+    fn drop(&mut self) {
+        wrapper::destroy_pipeline_cache(
+            vkDestroyPipelineCache,
+            self.device.handle(),
+            self.handle,
+            None,
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct VulkanPipelineCache {
+    handle: VkPipelineCache,
+    ownership: Ownership<PipelineCacheOwnership>,
+}
+
+impl Debug for VulkanPipelineCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(std::any::type_name::<Self>())
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+impl VulkanObject for VulkanPipelineCache {
+    type Handle = VkPipelineCache;
+
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_PIPELINE_CACHE;
+
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+impl VulkanDeviceObject for VulkanPipelineCache {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
+    }
+}
+
+impl crate::api::traits::PipelineCache<VulkanApi> for VulkanPipelineCache {
+    fn new(
+        context: <VulkanApi as GraphicsApi>::Context,
+        create_info: PipelineCacheCreateInfo,
+    ) -> crate::Result<Self> {
+        let properties = context.physical_device().properties().native();
+
+        // stale or foreign data is dropped rather than handed to the driver
+        let initial_data = create_info.initial_data.filter(|data| {
+            let compatible = PipelineCacheHeader::read(data)
+                .is_some_and(|header| header.is_compatible(properties));
+
+            if !compatible {
+                log::warn!("Discarding incompatible pipeline cache data");
+            }
+
+            compatible
+        });
+
+        let initial_data = initial_data.unwrap_or_default();
+
+        let info = VkPipelineCacheCreateInfo {
+            sType: VK_STRUCTURE_TYPE_PIPELINE_CACHE_CREATE_INFO,
+            pNext: std::ptr::null(),
+            flags: 0,
+            initialDataSize: initial_data.len(),
+            pInitialData: initial_data.as_ptr() as *const _,
+        };
+
+        let handle =
+            wrapper::create_pipeline_cache(vkCreatePipelineCache, context.handle(), &info, None)?;
+
+        let ownership = Ownership::new(PipelineCacheOwnership {
+            handle,
+            device: context,
+        });
+
+        let cache = Self { handle, ownership };
+
+        if let Some(label) = create_info.label {
+            cache.set_debug_name(label)?;
+        }
+
+        Ok(cache)
+    }
+
+    fn data(&self) -> crate::Result<Vec<u8>> {
+        Ok(wrapper::get_pipeline_cache_data(
+            vkGetPipelineCacheData,
+            self.device().handle(),
+            self.handle,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: [u8; VK_UUID_SIZE as usize] = [7; VK_UUID_SIZE as usize];
+
+    fn blob(header_version: u32, vendor_id: u32, device_id: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(PIPELINE_CACHE_HEADER_SIZE as u32).to_le_bytes());
+        data.extend_from_slice(&header_version.to_le_bytes());
+        data.extend_from_slice(&vendor_id.to_le_bytes());
+        data.extend_from_slice(&device_id.to_le_bytes());
+        data.extend_from_slice(&UUID);
+        // cache contents following the header
+        data.extend_from_slice(&[0xAB; 8]);
+        data
+    }
+
+    fn properties(
+        vendor_id: u32,
+        device_id: u32,
+        uuid: [u8; VK_UUID_SIZE as usize],
+    ) -> VkPhysicalDeviceProperties {
+        let mut properties: VkPhysicalDeviceProperties = unsafe { std::mem::zeroed() };
+        properties.vendorID = vendor_id;
+        properties.deviceID = device_id;
+        properties.pipelineCacheUUID = uuid;
+        properties
+    }
+
+    #[test]
+    fn reads_a_valid_header() {
+        let header = PipelineCacheHeader::read(&blob(
+            VK_PIPELINE_CACHE_HEADER_VERSION_ONE as u32,
+            0x10DE,
+            0x2684,
+        ))
+        .unwrap();
+
+        assert_eq!(header.vendor_id, 0x10DE);
+        assert_eq!(header.device_id, 0x2684);
+        assert_eq!(header.pipeline_cache_uuid, UUID);
+        assert!(header.is_compatible(&properties(0x10DE, 0x2684, UUID)));
+    }
+
+    #[test]
+    fn rejects_short_blobs() {
+        let data = blob(VK_PIPELINE_CACHE_HEADER_VERSION_ONE as u32, 0x10DE, 0x2684);
+
+        assert_eq!(PipelineCacheHeader::read(&[]), None);
+        assert_eq!(
+            PipelineCacheHeader::read(&data[..PIPELINE_CACHE_HEADER_SIZE - 1]),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_header_sizes_outside_the_blob() {
+        let mut data = blob(VK_PIPELINE_CACHE_HEADER_VERSION_ONE as u32, 0x10DE, 0x2684);
+
+        data[..4].copy_from_slice(&8u32.to_le_bytes());
+        assert_eq!(PipelineCacheHeader::read(&data), None);
+
+        let past_end = data.len() as u32 + 1;
+        data[..4].copy_from_slice(&past_end.to_le_bytes());
+        assert_eq!(PipelineCacheHeader::read(&data), None);
+    }
+
+    #[test]
+    fn rejects_other_header_versions() {
+        let version = VK_PIPELINE_CACHE_HEADER_VERSION_ONE as u32 + 1;
+
+        assert_eq!(
+            PipelineCacheHeader::read(&blob(version, 0x10DE, 0x2684)),
+            None
+        );
+        assert_eq!(PipelineCacheHeader::read(&blob(0, 0x10DE, 0x2684)), None);
+    }
+
+    #[test]
+    fn is_incompatible_with_other_devices() {
+        let header = PipelineCacheHeader::read(&blob(
+            VK_PIPELINE_CACHE_HEADER_VERSION_ONE as u32,
+            0x10DE,
+            0x2684,
+        ))
+        .unwrap();
+
+        assert!(!header.is_compatible(&properties(0x1002, 0x2684, UUID)));
+        assert!(!header.is_compatible(&properties(0x10DE, 0x2704, UUID)));
+    }
+
+    #[test]
+    fn is_incompatible_with_other_uuids() {
+        let header = PipelineCacheHeader::read(&blob(
+            VK_PIPELINE_CACHE_HEADER_VERSION_ONE as u32,
+            0x10DE,
+            0x2684,
+        ))
+        .unwrap();
+
+        let mut uuid = UUID;
+        uuid[VK_UUID_SIZE as usize - 1] ^= 1;
+        assert!(!header.is_compatible(&properties(0x10DE, 0x2684, uuid)));
+    }
+}
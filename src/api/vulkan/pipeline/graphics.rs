@@ -6,7 +6,8 @@ use crate::prelude::GraphicsApi;
 
 use vulkan_sys::*;
 
-use crate::BlendAttachmentState;
+use crate::{BlendAttachmentState, PipelineTarget};
+use std::ffi::CString;
 use std::fmt::Debug;
 
 /*
@@ -43,14 +44,23 @@ impl Debug for VulkanGraphicsPipeline {
 impl VulkanObject for VulkanGraphicsPipeline {
     type Handle = VkPipeline;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_PIPELINE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
 }
 
+impl VulkanDeviceObject for VulkanGraphicsPipeline {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
+    }
+}
+
 pub trait VulkanGraphicsPipelineExt: Sized {
     fn create_pipelines(
         device: VulkanDevice,
+        cache: Option<&VulkanPipelineCache>,
         create_info: &[VkGraphicsPipelineCreateInfo],
     ) -> crate::Result<Vec<Self>>;
 }
@@ -58,15 +68,36 @@ pub trait VulkanGraphicsPipelineExt: Sized {
 impl VulkanGraphicsPipelineExt for VulkanGraphicsPipeline {
     fn create_pipelines(
         device: VulkanDevice,
+        cache: Option<&VulkanPipelineCache>,
         create_info: &[VkGraphicsPipelineCreateInfo],
     ) -> crate::Result<Vec<Self>> {
-        let handles = wrapper::create_graphics_pipelines(
-            vkCreateGraphicsPipelines,
-            device.handle(),
-            std::ptr::null_mut(),
-            create_info,
-            None,
-        )?;
+        let cache = cache
+            .map(|cache| cache.handle())
+            .unwrap_or(std::ptr::null_mut());
+
+        let mut handles = vec![std::ptr::null_mut(); create_info.len()];
+
+        let result = unsafe {
+            vkCreateGraphicsPipelines(
+                device.handle(),
+                cache,
+                create_info.len() as u32,
+                create_info.as_ptr(),
+                std::ptr::null(),
+                handles.as_mut_ptr(),
+            )
+        };
+
+        // a failed call may still have created some of the pipelines
+        if let Err(error) = check(result) {
+            for &handle in handles.iter().filter(|handle| !handle.is_null()) {
+                unsafe {
+                    wrapper::destroy_pipeline(vkDestroyPipeline, device.handle(), handle, None);
+                }
+            }
+
+            return Err(error);
+        }
 
         Ok(handles
             .into_iter()
@@ -87,16 +118,179 @@ impl crate::api::traits::GraphicsPipeline<VulkanApi> for VulkanGraphicsPipeline
         context: <VulkanApi as GraphicsApi>::Context,
         create_info: crate::GraphicsPipelineCreateInfo,
     ) -> crate::Result<Self> {
-        let shader_stages = collect_shader_stages(&create_info.shaders);
+        Self::new_batch(context, std::slice::from_ref(&create_info))
+            .pop()
+            .unwrap()
+    }
+
+    fn new_batch(
+        context: <VulkanApi as GraphicsApi>::Context,
+        create_infos: &[crate::GraphicsPipelineCreateInfo],
+    ) -> Vec<crate::Result<Self>> {
+        let mut states = Vec::with_capacity(create_infos.len());
+        let mut results: Vec<Option<crate::Result<Self>>> = Vec::with_capacity(create_infos.len());
+
+        for create_info in create_infos {
+            let state = check_target(&context, create_info)
+                .and_then(|()| check_interfaces(create_info))
+                .and_then(|()| GraphicsPipelineState::new(create_info));
+
+            match state {
+                Ok(state) => {
+                    states.push(Some(state));
+                    results.push(None);
+                }
+                Err(error) => {
+                    states.push(None);
+                    results.push(Some(Err(error)));
+                }
+            }
+        }
+
+        // vulkan takes a single cache per call, so pipelines sharing a cache are batched together
+        let mut remaining: Vec<usize> = (0..create_infos.len())
+            .filter(|&i| results[i].is_none())
+            .collect();
+
+        while let Some(&first) = remaining.first() {
+            let cache = create_infos[first].cache.as_ref();
+            let cache_handle = cache.map(|cache| cache.handle());
+
+            let (batch, rest): (Vec<usize>, Vec<usize>) = remaining.iter().partition(|&&i| {
+                create_infos[i].cache.as_ref().map(|cache| cache.handle()) == cache_handle
+            });
+            remaining = rest;
+
+            let state = |i: usize| states[i].as_ref().unwrap();
+            let infos: Vec<_> = batch.iter().map(|&i| state(i).native()).collect();
+
+            match Self::create_pipelines(context.clone(), cache, &infos) {
+                Ok(pipelines) => {
+                    for (&i, pipeline) in batch.iter().zip(pipelines) {
+                        results[i] = Some(Ok(pipeline));
+                    }
+                }
+                // a failed batch doesn't report which pipeline failed, so isolate them one by one
+                Err(_) => {
+                    for &i in batch.iter() {
+                        let result =
+                            Self::create_pipelines(context.clone(), cache, &[state(i).native()])
+                                .map(|mut pipelines| pipelines.pop().unwrap());
+
+                        results[i] = Some(result);
+                    }
+                }
+            }
+        }
+
+        results
+            .into_iter()
+            .zip(create_infos)
+            .map(|(result, create_info)| {
+                let pipeline = result.unwrap()?;
+
+                if let Some(label) = create_info.label {
+                    pipeline.set_debug_name(label)?;
+                }
+
+                Ok(pipeline)
+            })
+            .collect()
+    }
+}
+
+/// Dynamic rendering pipelines can only be created when the device supports it.
+fn check_target(
+    context: &VulkanDevice,
+    create_info: &crate::GraphicsPipelineCreateInfo,
+) -> crate::Result<()> {
+    if let PipelineTarget::Dynamic(_) = create_info.target {
+        context.dynamic_rendering()?;
+    }
+
+    Ok(())
+}
+
+/// Checks the shader stages link with each other and the render target, when validation is
+/// enabled.
+fn check_interfaces(create_info: &crate::GraphicsPipelineCreateInfo) -> crate::Result<()> {
+    #[cfg(feature = "validation")]
+    crate::spirv::check_stage_interfaces(
+        &create_info.shaders,
+        color_attachment_count(&create_info.target),
+    )?;
+
+    Ok(())
+}
+
+fn color_attachment_count(target: &PipelineTarget) -> u32 {
+    match target {
+        PipelineTarget::RenderPass {
+            render_pass,
+            subpass,
+        } => render_pass.color_attachment_count(*subpass),
+        PipelineTarget::Dynamic(formats) => formats.color.len() as u32,
+    }
+}
+
+/*
+   Graphics Pipeline State
+*/
+
+static DYNAMIC_STATES: [VkDynamicState; 2] = [VK_DYNAMIC_STATE_VIEWPORT, VK_DYNAMIC_STATE_SCISSOR];
+
+/// Owns the native state a `VkGraphicsPipelineCreateInfo` points into.
+struct GraphicsPipelineState {
+    stage_states: Vec<ShaderStageState>,
+    shader_stages: Vec<VkPipelineShaderStageCreateInfo>,
+    vertex_bindings: Vec<VkVertexInputBindingDescription>,
+    vertex_attributes: Vec<VkVertexInputAttributeDescription>,
+    input_state: VkPipelineVertexInputStateCreateInfo,
+    input_assembly_state: VkPipelineInputAssemblyStateCreateInfo,
+    viewport_state: VkPipelineViewportStateCreateInfo,
+    rasterization_state: VkPipelineRasterizationStateCreateInfo,
+    multisample_state: VkPipelineMultisampleStateCreateInfo,
+    depth_stencil_state: VkPipelineDepthStencilStateCreateInfo,
+    blend_attachments: Vec<VkPipelineColorBlendAttachmentState>,
+    color_blend_state: VkPipelineColorBlendStateCreateInfo,
+    dynamic_state: VkPipelineDynamicStateCreateInfo,
+    layout: VkPipelineLayout,
+    render_pass: VkRenderPass,
+    subpass: u32,
+    color_formats: Vec<VkFormat>,
+    /// Set for dynamic rendering pipelines, which have no render pass.
+    rendering: Option<VkPipelineRenderingCreateInfo>,
+}
+
+impl GraphicsPipelineState {
+    fn new(create_info: &crate::GraphicsPipelineCreateInfo) -> crate::Result<Self> {
+        let stage_states = collect_shader_stages(&create_info.shaders)?;
+        let shader_stages = stage_states.iter().map(ShaderStageState::native).collect();
+
+        let vertex_bindings: Vec<VkVertexInputBindingDescription> = create_info
+            .vertex_input
+            .bindings
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect();
+
+        let vertex_attributes: Vec<VkVertexInputAttributeDescription> = create_info
+            .vertex_input
+            .attributes
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect();
 
         let input_state = VkPipelineVertexInputStateCreateInfo {
             sType: VK_STRUCTURE_TYPE_PIPELINE_VERTEX_INPUT_STATE_CREATE_INFO,
             pNext: std::ptr::null(),
             flags: 0,
-            vertexBindingDescriptionCount: 0,
-            pVertexBindingDescriptions: std::ptr::null(),
-            vertexAttributeDescriptionCount: 0,
-            pVertexAttributeDescriptions: std::ptr::null(),
+            vertexBindingDescriptionCount: vertex_bindings.len() as u32,
+            pVertexBindingDescriptions: vertex_bindings.as_ptr(),
+            vertexAttributeDescriptionCount: vertex_attributes.len() as u32,
+            pVertexAttributeDescriptions: vertex_attributes.as_ptr(),
         };
 
         let input_assembly_state = VkPipelineInputAssemblyStateCreateInfo {
@@ -184,6 +378,7 @@ impl crate::api::traits::GraphicsPipeline<VulkanApi> for VulkanGraphicsPipeline
             .map(Into::into)
             .collect();
 
+        // points into the vec's heap allocation, which stays put when the vec is moved
         let color_blend_state = VkPipelineColorBlendStateCreateInfo {
             sType: VK_STRUCTURE_TYPE_PIPELINE_COLOR_BLEND_STATE_CREATE_INFO,
             pNext: std::ptr::null(),
@@ -199,35 +394,107 @@ impl crate::api::traits::GraphicsPipeline<VulkanApi> for VulkanGraphicsPipeline
             sType: VK_STRUCTURE_TYPE_PIPELINE_DYNAMIC_STATE_CREATE_INFO,
             pNext: std::ptr::null(),
             flags: 0,
-            dynamicStateCount: 2,
-            pDynamicStates: [VK_DYNAMIC_STATE_VIEWPORT, VK_DYNAMIC_STATE_SCISSOR].as_ptr(),
+            dynamicStateCount: DYNAMIC_STATES.len() as u32,
+            pDynamicStates: DYNAMIC_STATES.as_ptr(),
+        };
+
+        let (render_pass, subpass, color_formats, rendering) = match &create_info.target {
+            PipelineTarget::RenderPass {
+                render_pass,
+                subpass,
+            } => (render_pass.handle(), *subpass, Vec::new(), None),
+            PipelineTarget::Dynamic(formats) => {
+                let color_formats: Vec<VkFormat> =
+                    formats.color.iter().copied().map(Into::into).collect();
+
+                // points into the vec's heap allocation, which stays put when the vec is moved
+                let rendering = VkPipelineRenderingCreateInfo {
+                    sType: VK_STRUCTURE_TYPE_PIPELINE_RENDERING_CREATE_INFO,
+                    pNext: std::ptr::null(),
+                    viewMask: 0,
+                    colorAttachmentCount: color_formats.len() as u32,
+                    pColorAttachmentFormats: color_formats.as_ptr(),
+                    depthAttachmentFormat: formats.depth.map_or(VK_FORMAT_UNDEFINED, Into::into),
+                    stencilAttachmentFormat: formats
+                        .stencil
+                        .map_or(VK_FORMAT_UNDEFINED, Into::into),
+                };
+
+                (std::ptr::null_mut(), 0, color_formats, Some(rendering))
+            }
         };
 
-        let info = VkGraphicsPipelineCreateInfo {
+        Ok(Self {
+            stage_states,
+            shader_stages,
+            vertex_bindings,
+            vertex_attributes,
+            input_state,
+            input_assembly_state,
+            viewport_state,
+            rasterization_state,
+            multisample_state,
+            depth_stencil_state,
+            blend_attachments,
+            color_blend_state,
+            dynamic_state,
+            layout: create_info.layout.handle(),
+            render_pass,
+            subpass,
+            color_formats,
+            rendering,
+        })
+    }
+
+    /// The returned create info borrows from `self` and must not outlive it.
+    fn native(&self) -> VkGraphicsPipelineCreateInfo {
+        VkGraphicsPipelineCreateInfo {
             sType: VK_STRUCTURE_TYPE_GRAPHICS_PIPELINE_CREATE_INFO,
-            pNext: std::ptr::null(),
+            pNext: self
+                .rendering
+                .as_ref()
+                .map_or(std::ptr::null(), |rendering| {
+                    rendering as *const _ as *const _
+                }),
             flags: 0,
-            stageCount: shader_stages.len() as u32,
-            pStages: shader_stages.as_ptr(),
-            pVertexInputState: &input_state,
-            pInputAssemblyState: &input_assembly_state,
+            stageCount: self.shader_stages.len() as u32,
+            pStages: self.shader_stages.as_ptr(),
+            pVertexInputState: &self.input_state,
+            pInputAssemblyState: &self.input_assembly_state,
             pTessellationState: std::ptr::null(),
-            pViewportState: &viewport_state,
-            pRasterizationState: &rasterization_state,
-            pMultisampleState: &multisample_state,
-            pDepthStencilState: &depth_stencil_state,
-            pColorBlendState: &color_blend_state,
-            pDynamicState: &dynamic_state,
-            layout: create_info.layout.handle(),
-            renderPass: create_info.render_pass.handle(),
-            subpass: create_info.subpass,
+            pViewportState: &self.viewport_state,
+            pRasterizationState: &self.rasterization_state,
+            pMultisampleState: &self.multisample_state,
+            pDepthStencilState: &self.depth_stencil_state,
+            pColorBlendState: &self.color_blend_state,
+            pDynamicState: &self.dynamic_state,
+            layout: self.layout,
+            renderPass: self.render_pass,
+            subpass: self.subpass,
             basePipelineHandle: std::ptr::null_mut(),
             basePipelineIndex: 0,
-        };
+        }
+    }
+}
 
-        let pipeline = Self::create_pipelines(context, &[info])?.pop().unwrap();
+impl Into<VkVertexInputBindingDescription> for crate::VertexBindingDescription {
+    fn into(self) -> VkVertexInputBindingDescription {
+        VkVertexInputBindingDescription {
+            binding: self.binding,
+            stride: self.stride,
+            inputRate: self.input_rate.into(),
+        }
+    }
+}
 
-        Ok(pipeline)
+impl Into<VkVertexInputAttributeDescription> for crate::VertexAttributeDescription {
+    fn into(self) -> VkVertexInputAttributeDescription {
+        VkVertexInputAttributeDescription {
+            location: self.location,
+            binding: self.binding,
+            format: self.format.into(),
+            offset: self.offset,
+        }
     }
 }
 
@@ -246,50 +513,97 @@ impl Into<VkPipelineColorBlendAttachmentState> for BlendAttachmentState {
     }
 }
 
-fn create_shader_stage(
+/*
+   Shader Stage State
+*/
+
+/// Owns the entry point and specialization data a `VkPipelineShaderStageCreateInfo` points into.
+struct ShaderStageState {
     stage: VkShaderStageFlagBits,
-    shader: &VulkanShaderModule,
-) -> VkPipelineShaderStageCreateInfo {
-    VkPipelineShaderStageCreateInfo {
-        sType: VK_STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-        pNext: std::ptr::null(),
-        flags: 0,
-        stage,
-        module: shader.handle(),
-        pName: c"main".as_ptr(),
-        pSpecializationInfo: std::ptr::null(),
-    }
+    module: VkShaderModule,
+    entry_point: CString,
+    map_entries: Vec<VkSpecializationMapEntry>,
+    data: Vec<u8>,
+    specialization_info: VkSpecializationInfo,
 }
 
-fn collect_shader_stages(shaders: &crate::ShaderStages) -> Vec<VkPipelineShaderStageCreateInfo> {
-    let mut shader_stages: Vec<VkPipelineShaderStageCreateInfo> = Vec::new();
-    shader_stages.reserve(5);
+impl ShaderStageState {
+    fn new(stage: VkShaderStageFlagBits, shader_stage: &crate::ShaderStage) -> crate::Result<Self> {
+        let entry_point = CString::new(shader_stage.entry_point.as_str())
+            .map_err(|_| crate::Error::InvalidArgument("shader entry point contains a NUL byte"))?;
 
-    shaders.vertex.as_ref().inspect(|shader| {
-        shader_stages.push(create_shader_stage(VK_SHADER_STAGE_VERTEX_BIT, shader));
-    });
+        let constants = &shader_stage.specialization_constants;
 
-    shaders.tess_ctrl.as_ref().inspect(|shader| {
-        shader_stages.push(create_shader_stage(
-            VK_SHADER_STAGE_TESSELLATION_CONTROL_BIT,
-            shader,
-        ));
-    });
+        let mut map_entries = Vec::with_capacity(constants.len());
+        let mut data = Vec::with_capacity(constants.len() * 4);
 
-    shaders.tess_eval.as_ref().inspect(|shader| {
-        shader_stages.push(create_shader_stage(
-            VK_SHADER_STAGE_TESSELLATION_EVALUATION_BIT,
-            shader,
-        ));
-    });
+        for constant in constants {
+            let bytes = constant.value.to_ne_bytes();
+
+            map_entries.push(VkSpecializationMapEntry {
+                constantID: constant.id,
+                offset: data.len() as u32,
+                size: bytes.len(),
+            });
+
+            data.extend_from_slice(&bytes);
+        }
+
+        // points into the vecs' heap allocations, which stay put when the vecs are moved
+        let specialization_info = VkSpecializationInfo {
+            mapEntryCount: map_entries.len() as u32,
+            pMapEntries: map_entries.as_ptr(),
+            dataSize: data.len(),
+            pData: data.as_ptr() as *const _,
+        };
 
-    shaders.geometry.as_ref().inspect(|shader| {
-        shader_stages.push(create_shader_stage(VK_SHADER_STAGE_GEOMETRY_BIT, shader));
-    });
+        Ok(Self {
+            stage,
+            module: shader_stage.shader.handle(),
+            entry_point,
+            map_entries,
+            data,
+            specialization_info,
+        })
+    }
 
-    shaders.fragment.as_ref().inspect(|shader| {
-        shader_stages.push(create_shader_stage(VK_SHADER_STAGE_FRAGMENT_BIT, shader));
-    });
+    /// The returned create info borrows from `self` and must not outlive it.
+    fn native(&self) -> VkPipelineShaderStageCreateInfo {
+        let specialization_info = match self.map_entries.is_empty() {
+            true => std::ptr::null(),
+            false => &self.specialization_info as *const _,
+        };
+
+        VkPipelineShaderStageCreateInfo {
+            sType: VK_STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
+            pNext: std::ptr::null(),
+            flags: 0,
+            stage: self.stage,
+            module: self.module,
+            pName: self.entry_point.as_ptr(),
+            pSpecializationInfo: specialization_info,
+        }
+    }
+}
 
-    shader_stages
+fn collect_shader_stages(shaders: &crate::ShaderStages) -> crate::Result<Vec<ShaderStageState>> {
+    let stages = [
+        (VK_SHADER_STAGE_VERTEX_BIT, &shaders.vertex),
+        (VK_SHADER_STAGE_TESSELLATION_CONTROL_BIT, &shaders.tess_ctrl),
+        (
+            VK_SHADER_STAGE_TESSELLATION_EVALUATION_BIT,
+            &shaders.tess_eval,
+        ),
+        (VK_SHADER_STAGE_GEOMETRY_BIT, &shaders.geometry),
+        (VK_SHADER_STAGE_FRAGMENT_BIT, &shaders.fragment),
+    ];
+
+    stages
+        .into_iter()
+        .filter_map(|(stage, shader_stage)| {
+            shader_stage
+                .as_ref()
+                .map(|shader_stage| ShaderStageState::new(stage, shader_stage))
+        })
+        .collect()
 }
@@ -10,6 +10,7 @@ use vulkan_sys::*;
 
 struct VulkanPipelineLayoutOwnership {
     handle: VkPipelineLayout,
+    set_layouts: Vec<VkDescriptorSetLayout>,
     device: VulkanDevice,
 }
 
@@ -21,6 +22,61 @@ impl Drop for VulkanPipelineLayoutOwnership {
             self.handle,
             None,
         );
+
+        destroy_set_layouts(&self.device, &self.set_layouts);
+    }
+}
+
+fn destroy_set_layouts(device: &VulkanDevice, set_layouts: &[VkDescriptorSetLayout]) {
+    for set_layout in set_layouts {
+        wrapper::destroy_descriptor_set_layout(
+            vkDestroyDescriptorSetLayout,
+            device.handle(),
+            *set_layout,
+            None,
+        );
+    }
+}
+
+fn create_set_layout(
+    device: &VulkanDevice,
+    description: &DescriptorSetLayoutDescription,
+) -> crate::Result<VkDescriptorSetLayout> {
+    let bindings: Vec<VkDescriptorSetLayoutBinding> = description
+        .bindings
+        .iter()
+        .map(|binding| VkDescriptorSetLayoutBinding {
+            binding: binding.binding,
+            descriptorType: binding.descriptor_type.into(),
+            descriptorCount: binding.count,
+            stageFlags: binding.stages.into(),
+            pImmutableSamplers: std::ptr::null(),
+        })
+        .collect();
+
+    let create_info = VkDescriptorSetLayoutCreateInfo {
+        sType: VK_STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_CREATE_INFO,
+        pNext: std::ptr::null(),
+        flags: 0,
+        bindingCount: bindings.len() as u32,
+        pBindings: bindings.as_ptr(),
+    };
+
+    Ok(wrapper::create_descriptor_set_layout(
+        vkCreateDescriptorSetLayout,
+        device.handle(),
+        &create_info,
+        None,
+    )?)
+}
+
+impl Into<VkPushConstantRange> for PushConstantRange {
+    fn into(self) -> VkPushConstantRange {
+        VkPushConstantRange {
+            stageFlags: self.stages.into(),
+            offset: self.offset,
+            size: self.size,
+        }
     }
 }
 
@@ -33,11 +89,19 @@ pub struct VulkanPipelineLayout {
 impl VulkanObject for VulkanPipelineLayout {
     type Handle = VkPipelineLayout;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_PIPELINE_LAYOUT;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
 }
 
+impl VulkanDeviceObject for VulkanPipelineLayout {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
+    }
+}
+
 impl std::fmt::Debug for VulkanPipelineLayout {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct(std::any::type_name::<Self>())
@@ -51,28 +115,61 @@ impl crate::api::traits::PipelineLayout<VulkanApi> for VulkanPipelineLayout {
         context: <VulkanApi as GraphicsApi>::Context,
         create_info: PipelineLayoutCreateInfo,
     ) -> crate::Result<Self> {
+        let label = create_info.label;
+
+        let mut set_layouts = Vec::with_capacity(create_info.set_layouts.len());
+        for description in create_info.set_layouts {
+            match create_set_layout(&context, description) {
+                Ok(set_layout) => set_layouts.push(set_layout),
+                Err(error) => {
+                    destroy_set_layouts(&context, &set_layouts);
+                    return Err(error);
+                }
+            }
+        }
+
+        let push_constant_ranges: Vec<VkPushConstantRange> = create_info
+            .push_constant_ranges
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect();
+
         let create_info = VkPipelineLayoutCreateInfo {
             sType: VK_STRUCTURE_TYPE_PIPELINE_LAYOUT_CREATE_INFO,
             pNext: std::ptr::null(),
             flags: 0,
-            setLayoutCount: 0,
-            pSetLayouts: std::ptr::null(),
-            pushConstantRangeCount: 0,
-            pPushConstantRanges: std::ptr::null(),
+            setLayoutCount: set_layouts.len() as u32,
+            pSetLayouts: set_layouts.as_ptr(),
+            pushConstantRangeCount: push_constant_ranges.len() as u32,
+            pPushConstantRanges: push_constant_ranges.as_ptr(),
         };
 
-        let handle = wrapper::create_pipeline_layout(
+        let handle = match wrapper::create_pipeline_layout(
             vkCreatePipelineLayout,
             context.handle(),
             &create_info,
             None,
-        )?;
+        ) {
+            Ok(handle) => handle,
+            Err(error) => {
+                destroy_set_layouts(&context, &set_layouts);
+                return Err(error.into());
+            }
+        };
 
         let ownership = Ownership::new(VulkanPipelineLayoutOwnership {
             handle,
+            set_layouts,
             device: context,
         });
 
-        Ok(Self { handle, ownership })
+        let layout = Self { handle, ownership };
+
+        if let Some(label) = label {
+            layout.set_debug_name(label)?;
+        }
+
+        Ok(layout)
     }
 }
@@ -7,6 +7,9 @@ pub use render_pass::*;
 mod layout;
 pub use layout::*;
 
+mod cache;
+pub use cache::*;
+
 // mod input_state;
 // pub use input_state::*;
 //
@@ -12,6 +12,7 @@ use crate::util::stack_vec::*;
 
 struct RenderPassOwnership {
     handle: VkRenderPass,
+    color_attachment_counts: Vec<u32>,
     device: VulkanDevice,
 }
 
@@ -35,9 +36,22 @@ impl std::fmt::Debug for VulkanRenderPass {
     }
 }
 
+impl VulkanRenderPass {
+    /// Number of color attachments written by the given subpass.
+    pub(crate) fn color_attachment_count(&self, subpass: u32) -> u32 {
+        self.ownership
+            .color_attachment_counts
+            .get(subpass as usize)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
 impl VulkanObject for VulkanRenderPass {
     type Handle = VkRenderPass;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_RENDER_PASS;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -58,6 +72,8 @@ impl crate::api::traits::RenderPass<VulkanApi> for VulkanRenderPass {
         context: <VulkanApi as GraphicsApi>::Context,
         create_info: RenderPassCreateInfo,
     ) -> Result<Self> {
+        let label = create_info.label;
+
         let attachments: Vec<_> = create_info
             .attachments
             .iter()
@@ -89,28 +105,16 @@ impl crate::api::traits::RenderPass<VulkanApi> for VulkanRenderPass {
             })
             .collect();
 
-        // let resolve_attachments: Vec<Vec<VkAttachmentReference>> = create_info
-        //     .subpasses
-        //     .iter()
-        //     .map(|subpass| {
-        //         subpass
-        //             .resolve_attachments
-        //             .iter()
-        //             .map(|attachment| attachment.clone().into())
-        //             .collect()
-        //     })
-        //     .collect();
-
-        // let depth_stencil_attachments: Vec<Option<VkAttachmentReference>> = create_info
-        //     .subpasses
-        //     .iter()
-        //     .map(|subpass| {
-        //         subpass
-        //             .depth_stencil_attachment
-        //             .as_ref()
-        //             .map(|attachment| attachment.clone().into())
-        //     })
-        //     .collect();
+        let depth_stencil_attachments: Vec<Option<VkAttachmentReference>> = create_info
+            .subpasses
+            .iter()
+            .map(|subpass| {
+                subpass
+                    .depth_stencil_attachment
+                    .as_ref()
+                    .map(|attachment| attachment.clone().into())
+            })
+            .collect();
 
         let subpasses: Vec<_> = create_info
             .subpasses
@@ -124,12 +128,17 @@ impl crate::api::traits::RenderPass<VulkanApi> for VulkanRenderPass {
                 colorAttachmentCount: color_attachments[i].len() as u32,
                 pColorAttachments: color_attachments[i].as_ptr(),
                 pResolveAttachments: std::ptr::null(),
-                pDepthStencilAttachment: std::ptr::null(),
-                preserveAttachmentCount: 0,
-                pPreserveAttachments: std::ptr::null(),
+                pDepthStencilAttachment: depth_stencil_attachments[i]
+                    .as_ref()
+                    .map_or(std::ptr::null(), |attachment| attachment),
+                preserveAttachmentCount: subpass.preserve_attachments.len() as u32,
+                pPreserveAttachments: subpass.preserve_attachments.as_ptr(),
             })
             .collect();
 
+        let dependencies: Vec<VkSubpassDependency> =
+            create_info.dependencies.iter().map(Into::into).collect();
+
         let create_info = VkRenderPassCreateInfo {
             sType: VK_STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO,
             pNext: std::ptr::null(),
@@ -138,19 +147,31 @@ impl crate::api::traits::RenderPass<VulkanApi> for VulkanRenderPass {
             pAttachments: attachments.as_ptr(),
             subpassCount: subpasses.len() as u32,
             pSubpasses: subpasses.as_ptr(),
-            dependencyCount: 0,
-            pDependencies: std::ptr::null(),
+            dependencyCount: dependencies.len() as u32,
+            pDependencies: dependencies.as_ptr(),
         };
 
         let handle =
             wrapper::create_render_pass(vkCreateRenderPass, context.handle(), &create_info, None)?;
 
+        let color_attachment_counts = color_attachments
+            .iter()
+            .map(|attachments| attachments.len() as u32)
+            .collect();
+
         let ownership = Ownership::new(RenderPassOwnership {
             handle,
+            color_attachment_counts,
             device: context,
         });
 
-        Ok(Self { handle, ownership })
+        let render_pass = Self { handle, ownership };
+
+        if let Some(label) = label {
+            render_pass.set_debug_name(label)?;
+        }
+
+        Ok(render_pass)
     }
 }
 
@@ -160,12 +181,30 @@ impl Into<VkAttachmentDescription> for AttachmentDescription {
             flags: 0,
             format: self.format.into(),
             samples: VK_SAMPLE_COUNT_1_BIT,
-            loadOp: VK_ATTACHMENT_LOAD_OP_DONT_CARE,
-            storeOp: VK_ATTACHMENT_STORE_OP_STORE,
-            stencilLoadOp: VK_ATTACHMENT_LOAD_OP_DONT_CARE,
-            stencilStoreOp: VK_ATTACHMENT_STORE_OP_DONT_CARE,
-            initialLayout: VK_IMAGE_LAYOUT_UNDEFINED,
-            finalLayout: VK_IMAGE_LAYOUT_PRESENT_SRC_KHR,
+            loadOp: self.load_op.into(),
+            storeOp: self.store_op.into(),
+            stencilLoadOp: self.stencil_load_op.into(),
+            stencilStoreOp: self.stencil_store_op.into(),
+            initialLayout: self.initial_layout.into(),
+            finalLayout: self.final_layout.into(),
+        }
+    }
+}
+
+impl Into<VkSubpassDependency> for &SubpassDependency {
+    fn into(self) -> VkSubpassDependency {
+        // render passes take the original flags, which the core stage and access bits fit in
+        VkSubpassDependency {
+            srcSubpass: self.src_subpass.unwrap_or(VK_SUBPASS_EXTERNAL),
+            dstSubpass: self.dst_subpass.unwrap_or(VK_SUBPASS_EXTERNAL),
+            srcStageMask: self.src_stages.into(),
+            dstStageMask: self.dst_stages.into(),
+            srcAccessMask: self.src_access.into(),
+            dstAccessMask: self.dst_access.into(),
+            dependencyFlags: match self.by_region {
+                true => VK_DEPENDENCY_BY_REGION_BIT as VkDependencyFlags,
+                false => 0,
+            },
         }
     }
 }
@@ -174,7 +213,7 @@ impl Into<VkAttachmentReference> for AttachmentReference {
     fn into(self) -> VkAttachmentReference {
         VkAttachmentReference {
             attachment: self.attachment,
-            layout: VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL,
+            layout: self.layout.into(),
         }
     }
 }
@@ -35,6 +35,8 @@ impl std::fmt::Debug for VulkanSemaphore {
 impl VulkanObject for VulkanSemaphore {
     type Handle = VkSemaphore;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_SEMAPHORE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -51,6 +53,8 @@ impl crate::api::traits::Semaphore<VulkanApi> for VulkanSemaphore {
         context: <VulkanApi as GraphicsApi>::Context,
         create_info: SemaphoreCreateInfo,
     ) -> crate::Result<Self> {
+        let label = create_info.label;
+
         let create_info = VkSemaphoreCreateInfo {
             sType: VK_STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
             pNext: std::ptr::null(),
@@ -64,6 +68,12 @@ impl crate::api::traits::Semaphore<VulkanApi> for VulkanSemaphore {
             device: context,
         });
 
-        Ok(Self { handle, ownership })
+        let semaphore = Self { handle, ownership };
+
+        if let Some(label) = label {
+            semaphore.set_debug_name(label)?;
+        }
+
+        Ok(semaphore)
     }
 }
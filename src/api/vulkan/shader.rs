@@ -1,11 +1,12 @@
 // Copyright (c) 2024 Jacob R. Green
 // All rights reserved.
 
-use crate::api::vulkan::{Ownership, VulkanApi, VulkanObject};
+use crate::api::vulkan::{Ownership, VulkanApi, VulkanDevice, VulkanDeviceObject, VulkanObject};
 use std::fmt::Debug;
 use vulkan_sys::*;
 
 use crate::prelude::*;
+use crate::spirv::ShaderReflection;
 use crate::ShaderCode;
 
 // #[derive(Debug, Clone)]
@@ -24,6 +25,7 @@ use crate::ShaderCode;
 struct ShaderModuleOwnership {
     handle: VkShaderModule,
     device: crate::api::vulkan::device::VulkanDevice,
+    reflection: Option<ShaderReflection>,
 }
 
 impl Drop for ShaderModuleOwnership {
@@ -51,27 +53,41 @@ impl Debug for VulkanShaderModule {
     }
 }
 
+impl PartialEq for VulkanShaderModule {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
 impl VulkanObject for VulkanShaderModule {
     type Handle = VkShaderModule;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_SHADER_MODULE;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
 }
 
+impl VulkanDeviceObject for VulkanShaderModule {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
+    }
+}
+
 impl crate::api::traits::Shader<VulkanApi> for VulkanShaderModule {
     fn from_code(
         context: <VulkanApi as GraphicsApi>::Context,
         code: ShaderCode,
     ) -> crate::Result<Self> {
-        let code: &[u8] = code.into();
+        let code = code.spirv()?;
 
         let create_info = VkShaderModuleCreateInfo {
             sType: VK_STRUCTURE_TYPE_SHADER_MODULE_CREATE_INFO,
             pNext: std::ptr::null(),
             flags: 0,
-            codeSize: code.len(),
-            pCode: code.as_ptr() as *const u32,
+            codeSize: code.len() * std::mem::size_of::<u32>(),
+            pCode: code.as_ptr(),
         };
 
         let handle = wrapper::create_shader_module(
@@ -81,11 +97,24 @@ impl crate::api::traits::Shader<VulkanApi> for VulkanShaderModule {
             None,
         )?;
 
+        let reflection = match ShaderReflection::from_words(&code) {
+            Ok(reflection) => Some(reflection),
+            Err(error) => {
+                log::warn!("Failed to reflect shader module: {}", error);
+                None
+            }
+        };
+
         let ownership = Ownership::new(ShaderModuleOwnership {
             handle,
             device: context,
+            reflection,
         });
 
         Ok(Self { handle, ownership })
     }
+
+    fn reflection(&self) -> Option<&ShaderReflection> {
+        self.ownership.reflection.as_ref()
+    }
 }
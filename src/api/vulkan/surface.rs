@@ -72,6 +72,8 @@ impl crate::api::traits::Surface<VulkanApi> for VulkanSurface {
 impl VulkanObject for VulkanSurface {
     type Handle = VkSurfaceKHR;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_SURFACE_KHR;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
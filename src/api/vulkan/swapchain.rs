@@ -2,7 +2,10 @@
 // All rights reserved.
 
 use crate::api::traits::GraphicsApi;
-use crate::api::vulkan::{device, surface, Ownership, VulkanApi, VulkanImage, VulkanObject};
+use crate::api::vulkan::{
+    check, device, surface, Ownership, VulkanApi, VulkanDevice, VulkanDeviceObject, VulkanImage,
+    VulkanObject,
+};
 use crate::{CompositeAlphaMode, SwapchainCreateInfo};
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
@@ -54,6 +57,17 @@ impl crate::api::traits::Swapchain<VulkanApi> for VulkanSwapchain {
         surface: <VulkanApi as GraphicsApi>::Surface,
         create_info: &SwapchainCreateInfo,
     ) -> crate::Result<Self> {
+        let label = create_info.label;
+        let (format, extent) = (create_info.format, create_info.extent);
+
+        let old_swapchain = create_info
+            .old_swapchain
+            .map(|swapchain| swapchain.handle())
+            .unwrap_or(std::ptr::null_mut());
+
+        let usage: VkImageUsageFlags =
+            (create_info.usage | crate::ImageUsageFlags::COLOR_ATTACHMENT).into();
+
         let create_info = VkSwapchainCreateInfoKHR {
             sType: VK_STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR,
             pNext: std::ptr::null(),
@@ -64,7 +78,7 @@ impl crate::api::traits::Swapchain<VulkanApi> for VulkanSwapchain {
             imageColorSpace: create_info.colorspace.into(),
             imageExtent: create_info.extent.into(),
             imageArrayLayers: 1,
-            imageUsage: VK_IMAGE_USAGE_COLOR_ATTACHMENT_BIT as _,
+            imageUsage: usage,
             imageSharingMode: VK_SHARING_MODE_EXCLUSIVE,
             queueFamilyIndexCount: 0,
             pQueueFamilyIndices: std::ptr::null(),
@@ -72,7 +86,7 @@ impl crate::api::traits::Swapchain<VulkanApi> for VulkanSwapchain {
             compositeAlpha: create_info.composite_alpha.into(),
             presentMode: create_info.present_mode.into(),
             clipped: 0,
-            oldSwapchain: std::ptr::null_mut(),
+            oldSwapchain: old_swapchain,
         };
 
         let handle = wrapper::create_swapchain_khr(
@@ -88,7 +102,7 @@ impl crate::api::traits::Swapchain<VulkanApi> for VulkanSwapchain {
 
         let images = image_handles
             .into_iter()
-            .map(|handle| VulkanImage::swapchain(handle))
+            .map(|handle| VulkanImage::swapchain(handle, context.clone(), format, extent))
             .collect();
 
         let ownership = Ownership::new(SwapchainOwnership {
@@ -98,7 +112,13 @@ impl crate::api::traits::Swapchain<VulkanApi> for VulkanSwapchain {
             images,
         });
 
-        Ok(VulkanSwapchain { handle, ownership })
+        let swapchain = VulkanSwapchain { handle, ownership };
+
+        if let Some(label) = label {
+            swapchain.set_debug_name(label)?;
+        }
+
+        Ok(swapchain)
     }
 
     fn images(&self) -> &[<VulkanApi as GraphicsApi>::Image] {
@@ -123,20 +143,38 @@ impl crate::api::traits::Swapchain<VulkanApi> for VulkanSwapchain {
             .map(|fence| fence.handle())
             .unwrap_or(std::ptr::null_mut());
 
-        Ok(wrapper::acquire_next_image_khr(
-            vkAcquireNextImageKHR,
-            self.ownership.device.handle(),
-            self.handle,
-            timeout,
-            Some(semaphore),
-            Some(fence),
-        )?)
+        let mut index = 0;
+
+        // called directly so out of date swapchains can be told apart from other errors
+        let result = unsafe {
+            vkAcquireNextImageKHR(
+                self.ownership.device.handle(),
+                self.handle,
+                timeout,
+                semaphore,
+                fence,
+                &mut index,
+            )
+        };
+
+        match check(result)? {
+            VK_TIMEOUT | VK_NOT_READY => Err(crate::Error::Timeout),
+            _ => Ok(index),
+        }
+    }
+}
+
+impl VulkanDeviceObject for VulkanSwapchain {
+    fn device(&self) -> &VulkanDevice {
+        &self.ownership.device
     }
 }
 
 impl VulkanObject for VulkanSwapchain {
     type Handle = VkSwapchainKHR;
 
+    const OBJECT_TYPE: VkObjectType = VK_OBJECT_TYPE_SWAPCHAIN_KHR;
+
     fn handle(&self) -> Self::Handle {
         self.handle
     }
@@ -0,0 +1,82 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DebugMessageSeverity {
+    Verbose,
+    Info,
+    Warning,
+    Error,
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct DebugMessageTypeFlags: u32 {
+        const GENERAL = 0x1;
+        const VALIDATION = 0x2;
+        const PERFORMANCE = 0x4;
+    }
+}
+
+/// An api object referenced by a debug message.
+#[derive(Debug, Clone)]
+pub struct DebugObject {
+    /// The backend specific object type, e.g. `VkObjectType` for vulkan.
+    pub object_type: i32,
+    pub handle: u64,
+    pub name: Option<String>,
+}
+
+/// A message reported by the api's validation / debug layer.
+#[derive(Debug, Clone)]
+pub struct DebugMessage {
+    pub severity: DebugMessageSeverity,
+    pub types: DebugMessageTypeFlags,
+    pub id_name: Option<String>,
+    pub id_number: i32,
+    pub message: String,
+    pub objects: Vec<DebugObject>,
+}
+
+/// User application callback for debug messages.
+///
+/// The callback may be invoked from any thread the driver reports from.
+#[derive(Clone)]
+pub struct DebugCallback(Arc<dyn Fn(&DebugMessage) + Send + Sync>);
+
+impl DebugCallback {
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&DebugMessage) + Send + Sync + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+
+    pub fn call(&self, message: &DebugMessage) {
+        (self.0)(message)
+    }
+}
+
+impl Debug for DebugCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(std::any::type_name::<Self>()).finish()
+    }
+}
+
+/// What to do when the validation layer reports an error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ValidationErrorPolicy {
+    /// Panic as soon as the error is reported.
+    Panic,
+    /// Report the error through the callback and `log`, then continue.
+    Continue,
+}
+
+impl Default for ValidationErrorPolicy {
+    fn default() -> Self {
+        Self::Panic
+    }
+}
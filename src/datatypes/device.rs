@@ -0,0 +1,121 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::Vendor;
+use std::fmt::{Display, Formatter};
+
+/// Version of the graphics api a device implements.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Display for ApiVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A driver version split into the components of the vendor's numbering scheme.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DriverVersion {
+    /// The version exactly as the driver reported it.
+    pub raw: u32,
+    components: [u32; 4],
+    len: usize,
+}
+
+impl DriverVersion {
+    /// Decodes a packed 32 bit driver version.
+    ///
+    /// NVIDIA packs 10.8.8.6 bits, and Intel's Windows driver packs 18.14 bits. Everyone else
+    /// uses the Vulkan api version layout.
+    pub fn decode(vendor: Vendor, raw: u32) -> Self {
+        match vendor {
+            Vendor::Nvidia => Self::new(
+                raw,
+                &[raw >> 22, (raw >> 14) & 0xFF, (raw >> 6) & 0xFF, raw & 0x3F],
+            ),
+            #[cfg(target_os = "windows")]
+            Vendor::Intel => Self::new(raw, &[raw >> 14, raw & 0x3FFF]),
+            _ => Self::new(raw, &[(raw >> 22) & 0x7F, (raw >> 12) & 0x3FF, raw & 0xFFF]),
+        }
+    }
+
+    /// A version for drivers that don't report one, with no components.
+    pub fn unknown() -> Self {
+        Self::new(0, &[])
+    }
+
+    fn new(raw: u32, components: &[u32]) -> Self {
+        let mut version = Self {
+            raw,
+            components: [0; 4],
+            len: components.len(),
+        };
+
+        version.components[..components.len()].copy_from_slice(components);
+        version
+    }
+
+    /// Version components, most significant first.
+    pub fn components(&self) -> &[u32] {
+        &self.components[..self.len]
+    }
+}
+
+impl Display for DriverVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, component) in self.components().iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+
+            write!(f, "{component}")?;
+        }
+
+        Ok(())
+    }
+}
+
+bitflags::bitflags! {
+    /// Optional device capabilities.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct DeviceFeatureFlags: u32 {
+        const GEOMETRY_SHADER = 0x1;
+        const TESSELLATION_SHADER = 0x2;
+        const SAMPLER_ANISOTROPY = 0x4;
+        const FILL_MODE_NON_SOLID = 0x8;
+        const WIDE_LINES = 0x10;
+        const MULTI_DRAW_INDIRECT = 0x20;
+        const TEXTURE_COMPRESSION_BC = 0x40;
+        const TEXTURE_COMPRESSION_ETC2 = 0x80;
+        const TEXTURE_COMPRESSION_ASTC = 0x100;
+        const DYNAMIC_RENDERING = 0x200;
+        const SYNCHRONIZATION2 = 0x400;
+    }
+}
+
+bitflags::bitflags! {
+    /// Kinds of work a queue accepts.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct QueueFlags: u32 {
+        const GRAPHICS = 0x1;
+        const COMPUTE = 0x2;
+        const TRANSFER = 0x4;
+    }
+}
+
+/// Upper bounds on what a device supports.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct DeviceLimits {
+    pub max_image_dimension_2d: u32,
+    pub max_image_array_layers: u32,
+    pub max_color_attachments: u32,
+    pub max_bound_descriptor_sets: u32,
+    pub max_push_constants_size: u32,
+    pub max_vertex_input_attributes: u32,
+    pub max_compute_work_group_invocations: u32,
+}
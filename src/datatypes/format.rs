@@ -65,4 +65,923 @@ pub enum Format {
 
     R10G10B10A2_UINT = data_format!(R10G10B10A2_UINT),
     R10G10B10A2_UNORM = data_format!(R10G10B10A2_UNORM),
+
+    D16_UNORM = data_format!(D16_UNORM),
+    D32_SFLOAT = data_format!(D32_SFLOAT),
+    D24_UNORM_S8_UINT = data_format!(D24_UNORM_S8_UINT),
+    D32_SFLOAT_S8_UINT = data_format!(D32_SFLOAT_S8_UINT),
+
+    B10G11R11_UFLOAT = data_format!(B10G11R11_UFLOAT),
+    E5B9G9R9_UFLOAT = data_format!(E5B9G9R9_UFLOAT),
+
+    BC1_RGBA_UNORM = data_format!(BC1_RGBA_UNORM),
+    BC1_RGBA_UNORM_SRGB = data_format!(BC1_RGBA_UNORM_SRGB),
+    BC2_UNORM = data_format!(BC2_UNORM),
+    BC2_UNORM_SRGB = data_format!(BC2_UNORM_SRGB),
+    BC3_UNORM = data_format!(BC3_UNORM),
+    BC3_UNORM_SRGB = data_format!(BC3_UNORM_SRGB),
+    BC4_UNORM = data_format!(BC4_UNORM),
+    BC4_SNORM = data_format!(BC4_SNORM),
+    BC5_UNORM = data_format!(BC5_UNORM),
+    BC5_SNORM = data_format!(BC5_SNORM),
+    BC6H_UFLOAT = data_format!(BC6H_UFLOAT),
+    BC6H_SFLOAT = data_format!(BC6H_SFLOAT),
+    BC7_UNORM = data_format!(BC7_UNORM),
+    BC7_UNORM_SRGB = data_format!(BC7_UNORM_SRGB),
+
+    ETC2_R8G8B8_UNORM = data_format!(ETC2_R8G8B8_UNORM),
+    ETC2_R8G8B8_UNORM_SRGB = data_format!(ETC2_R8G8B8_UNORM_SRGB),
+    ETC2_R8G8B8A1_UNORM = data_format!(ETC2_R8G8B8A1_UNORM),
+    ETC2_R8G8B8A1_UNORM_SRGB = data_format!(ETC2_R8G8B8A1_UNORM_SRGB),
+    ETC2_R8G8B8A8_UNORM = data_format!(ETC2_R8G8B8A8_UNORM),
+    ETC2_R8G8B8A8_UNORM_SRGB = data_format!(ETC2_R8G8B8A8_UNORM_SRGB),
+    EAC_R11_UNORM = data_format!(EAC_R11_UNORM),
+    EAC_R11_SNORM = data_format!(EAC_R11_SNORM),
+    EAC_R11G11_UNORM = data_format!(EAC_R11G11_UNORM),
+    EAC_R11G11_SNORM = data_format!(EAC_R11G11_SNORM),
+
+    ASTC_4x4_UNORM = data_format!(ASTC_4x4_UNORM),
+    ASTC_4x4_UNORM_SRGB = data_format!(ASTC_4x4_UNORM_SRGB),
+
+    ASTC_5x4_UNORM = data_format!(ASTC_5x4_UNORM),
+    ASTC_5x4_UNORM_SRGB = data_format!(ASTC_5x4_UNORM_SRGB),
+
+    ASTC_5x5_UNORM = data_format!(ASTC_5x5_UNORM),
+    ASTC_5x5_UNORM_SRGB = data_format!(ASTC_5x5_UNORM_SRGB),
+
+    ASTC_6x5_UNORM = data_format!(ASTC_6x5_UNORM),
+    ASTC_6x5_UNORM_SRGB = data_format!(ASTC_6x5_UNORM_SRGB),
+
+    ASTC_6x6_UNORM = data_format!(ASTC_6x6_UNORM),
+    ASTC_6x6_UNORM_SRGB = data_format!(ASTC_6x6_UNORM_SRGB),
+
+    ASTC_8x5_UNORM = data_format!(ASTC_8x5_UNORM),
+    ASTC_8x5_UNORM_SRGB = data_format!(ASTC_8x5_UNORM_SRGB),
+
+    ASTC_8x6_UNORM = data_format!(ASTC_8x6_UNORM),
+    ASTC_8x6_UNORM_SRGB = data_format!(ASTC_8x6_UNORM_SRGB),
+
+    ASTC_8x8_UNORM = data_format!(ASTC_8x8_UNORM),
+    ASTC_8x8_UNORM_SRGB = data_format!(ASTC_8x8_UNORM_SRGB),
+
+    ASTC_10x5_UNORM = data_format!(ASTC_10x5_UNORM),
+    ASTC_10x5_UNORM_SRGB = data_format!(ASTC_10x5_UNORM_SRGB),
+
+    ASTC_10x6_UNORM = data_format!(ASTC_10x6_UNORM),
+    ASTC_10x6_UNORM_SRGB = data_format!(ASTC_10x6_UNORM_SRGB),
+
+    ASTC_10x8_UNORM = data_format!(ASTC_10x8_UNORM),
+    ASTC_10x8_UNORM_SRGB = data_format!(ASTC_10x8_UNORM_SRGB),
+
+    ASTC_10x10_UNORM = data_format!(ASTC_10x10_UNORM),
+    ASTC_10x10_UNORM_SRGB = data_format!(ASTC_10x10_UNORM_SRGB),
+
+    ASTC_12x10_UNORM = data_format!(ASTC_12x10_UNORM),
+    ASTC_12x10_UNORM_SRGB = data_format!(ASTC_12x10_UNORM_SRGB),
+
+    ASTC_12x12_UNORM = data_format!(ASTC_12x12_UNORM),
+    ASTC_12x12_UNORM_SRGB = data_format!(ASTC_12x12_UNORM_SRGB),
+
+    /// A format the backend reported that xgpu doesn't know about.
+    Unknown(i32) = i32::MAX,
+}
+
+impl_raw!(Format {
+    R8_UINT,
+    R8_SINT,
+    R8_UNORM,
+    R8_SNORM,
+    R8G8_UINT,
+    R8G8_SINT,
+    R8G8_UNORM,
+    R8G8_SNORM,
+    R8G8B8A8_UINT,
+    R8G8B8A8_SINT,
+    R8G8B8A8_UNORM,
+    R8G8B8A8_SNORM,
+    R8G8B8A8_UNORM_SRGB,
+    B8G8R8A8_UNORM,
+    B8G8R8A8_UNORM_SRGB,
+    R16_UINT,
+    R16_SINT,
+    R16_UNORM,
+    R16_SNORM,
+    R16G16_UINT,
+    R16G16_SINT,
+    R16G16_UNORM,
+    R16G16_SNORM,
+    R16G16B16A16_UINT,
+    R16G16B16A16_SINT,
+    R16G16B16A16_UNORM,
+    R16G16B16A16_SNORM,
+    R16G16B16A16_SFLOAT,
+    R32_UINT,
+    R32_SINT,
+    R32_SFLOAT,
+    R32G32_UINT,
+    R32G32_SINT,
+    R32G32_SFLOAT,
+    R32G32B32_UINT,
+    R32G32B32_SINT,
+    R32G32B32_SFLOAT,
+    R32G32B32A32_UINT,
+    R32G32B32A32_SINT,
+    R32G32B32A32_SFLOAT,
+    R10G10B10A2_UINT,
+    R10G10B10A2_UNORM,
+    D16_UNORM,
+    D32_SFLOAT,
+    D24_UNORM_S8_UINT,
+    D32_SFLOAT_S8_UINT,
+    B10G11R11_UFLOAT,
+    E5B9G9R9_UFLOAT,
+    BC1_RGBA_UNORM,
+    BC1_RGBA_UNORM_SRGB,
+    BC2_UNORM,
+    BC2_UNORM_SRGB,
+    BC3_UNORM,
+    BC3_UNORM_SRGB,
+    BC4_UNORM,
+    BC4_SNORM,
+    BC5_UNORM,
+    BC5_SNORM,
+    BC6H_UFLOAT,
+    BC6H_SFLOAT,
+    BC7_UNORM,
+    BC7_UNORM_SRGB,
+    ETC2_R8G8B8_UNORM,
+    ETC2_R8G8B8_UNORM_SRGB,
+    ETC2_R8G8B8A1_UNORM,
+    ETC2_R8G8B8A1_UNORM_SRGB,
+    ETC2_R8G8B8A8_UNORM,
+    ETC2_R8G8B8A8_UNORM_SRGB,
+    EAC_R11_UNORM,
+    EAC_R11_SNORM,
+    EAC_R11G11_UNORM,
+    EAC_R11G11_SNORM,
+    ASTC_4x4_UNORM,
+    ASTC_4x4_UNORM_SRGB,
+    ASTC_5x4_UNORM,
+    ASTC_5x4_UNORM_SRGB,
+    ASTC_5x5_UNORM,
+    ASTC_5x5_UNORM_SRGB,
+    ASTC_6x5_UNORM,
+    ASTC_6x5_UNORM_SRGB,
+    ASTC_6x6_UNORM,
+    ASTC_6x6_UNORM_SRGB,
+    ASTC_8x5_UNORM,
+    ASTC_8x5_UNORM_SRGB,
+    ASTC_8x6_UNORM,
+    ASTC_8x6_UNORM_SRGB,
+    ASTC_8x8_UNORM,
+    ASTC_8x8_UNORM_SRGB,
+    ASTC_10x5_UNORM,
+    ASTC_10x5_UNORM_SRGB,
+    ASTC_10x6_UNORM,
+    ASTC_10x6_UNORM_SRGB,
+    ASTC_10x8_UNORM,
+    ASTC_10x8_UNORM_SRGB,
+    ASTC_10x10_UNORM,
+    ASTC_10x10_UNORM_SRGB,
+    ASTC_12x10_UNORM,
+    ASTC_12x10_UNORM_SRGB,
+    ASTC_12x12_UNORM,
+    ASTC_12x12_UNORM_SRGB,
+});
+
+/// How the components of a format are interpreted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NumericClass {
+    Unorm,
+    Snorm,
+    Uint,
+    Sint,
+    Sfloat,
+    /// Unsigned floating point, used by packed HDR formats and BC6H.
+    Ufloat,
+    /// Unsigned normalized with sRGB encoded color components.
+    Srgb,
+    /// The layout of an unknown format.
+    Unknown,
+}
+
+/*
+   Format Info
+*/
+
+#[derive(Copy, Clone)]
+struct FormatInfo {
+    block_size: u32,
+    block_width: u32,
+    block_height: u32,
+    components: u32,
+    bits: [u32; 4],
+    class: NumericClass,
+    depth: bool,
+    stencil: bool,
+}
+
+impl FormatInfo {
+    /// An uncompressed color format, `bits` being the width of each component in name order.
+    const fn color(class: NumericClass, bits: [u32; 4]) -> Self {
+        let mut components = 0;
+        let mut total = 0;
+        while components < 4 && bits[components] != 0 {
+            total += bits[components];
+            components += 1;
+        }
+
+        Self {
+            block_size: total / 8,
+            block_width: 1,
+            block_height: 1,
+            components: components as u32,
+            bits,
+            class,
+            depth: false,
+            stencil: false,
+        }
+    }
+
+    /// A color format whose block size isn't the sum of its component widths.
+    const fn packed(class: NumericClass, block_size: u32, bits: [u32; 4]) -> Self {
+        Self {
+            block_size,
+            ..Self::color(class, bits)
+        }
+    }
+
+    const fn compressed(
+        class: NumericClass,
+        block_size: u32,
+        block_width: u32,
+        block_height: u32,
+        components: u32,
+    ) -> Self {
+        Self {
+            block_size,
+            block_width,
+            block_height,
+            components,
+            bits: [0; 4],
+            class,
+            depth: false,
+            stencil: false,
+        }
+    }
+
+    /// An unknown format, whose texels have no size xgpu can compute.
+    const fn unknown() -> Self {
+        Self {
+            block_size: 0,
+            block_width: 1,
+            block_height: 1,
+            components: 0,
+            bits: [0; 4],
+            class: NumericClass::Unknown,
+            depth: false,
+            stencil: false,
+        }
+    }
+
+    /// A depth format, with a stencil component following depth in `bits` if `stencil` is set.
+    const fn depth_stencil(
+        class: NumericClass,
+        block_size: u32,
+        bits: [u32; 4],
+        stencil: bool,
+    ) -> Self {
+        Self {
+            block_size,
+            depth: true,
+            stencil,
+            ..Self::color(class, bits)
+        }
+    }
+}
+
+impl Format {
+    const fn info(self) -> FormatInfo {
+        use NumericClass::*;
+
+        match self {
+            Format::R8_UINT => FormatInfo::color(Uint, [8, 0, 0, 0]),
+            Format::R8_SINT => FormatInfo::color(Sint, [8, 0, 0, 0]),
+            Format::R8_UNORM => FormatInfo::color(Unorm, [8, 0, 0, 0]),
+            Format::R8_SNORM => FormatInfo::color(Snorm, [8, 0, 0, 0]),
+
+            Format::R8G8_UINT => FormatInfo::color(Uint, [8, 8, 0, 0]),
+            Format::R8G8_SINT => FormatInfo::color(Sint, [8, 8, 0, 0]),
+            Format::R8G8_UNORM => FormatInfo::color(Unorm, [8, 8, 0, 0]),
+            Format::R8G8_SNORM => FormatInfo::color(Snorm, [8, 8, 0, 0]),
+
+            Format::R8G8B8A8_UINT => FormatInfo::color(Uint, [8, 8, 8, 8]),
+            Format::R8G8B8A8_SINT => FormatInfo::color(Sint, [8, 8, 8, 8]),
+            Format::R8G8B8A8_UNORM => FormatInfo::color(Unorm, [8, 8, 8, 8]),
+            Format::R8G8B8A8_SNORM => FormatInfo::color(Snorm, [8, 8, 8, 8]),
+            Format::R8G8B8A8_UNORM_SRGB => FormatInfo::color(Srgb, [8, 8, 8, 8]),
+
+            Format::B8G8R8A8_UNORM => FormatInfo::color(Unorm, [8, 8, 8, 8]),
+            Format::B8G8R8A8_UNORM_SRGB => FormatInfo::color(Srgb, [8, 8, 8, 8]),
+
+            Format::R16_UINT => FormatInfo::color(Uint, [16, 0, 0, 0]),
+            Format::R16_SINT => FormatInfo::color(Sint, [16, 0, 0, 0]),
+            Format::R16_UNORM => FormatInfo::color(Unorm, [16, 0, 0, 0]),
+            Format::R16_SNORM => FormatInfo::color(Snorm, [16, 0, 0, 0]),
+
+            Format::R16G16_UINT => FormatInfo::color(Uint, [16, 16, 0, 0]),
+            Format::R16G16_SINT => FormatInfo::color(Sint, [16, 16, 0, 0]),
+            Format::R16G16_UNORM => FormatInfo::color(Unorm, [16, 16, 0, 0]),
+            Format::R16G16_SNORM => FormatInfo::color(Snorm, [16, 16, 0, 0]),
+
+            Format::R16G16B16A16_UINT => FormatInfo::color(Uint, [16, 16, 16, 16]),
+            Format::R16G16B16A16_SINT => FormatInfo::color(Sint, [16, 16, 16, 16]),
+            Format::R16G16B16A16_UNORM => FormatInfo::color(Unorm, [16, 16, 16, 16]),
+            Format::R16G16B16A16_SNORM => FormatInfo::color(Snorm, [16, 16, 16, 16]),
+            Format::R16G16B16A16_SFLOAT => FormatInfo::color(Sfloat, [16, 16, 16, 16]),
+
+            Format::R32_UINT => FormatInfo::color(Uint, [32, 0, 0, 0]),
+            Format::R32_SINT => FormatInfo::color(Sint, [32, 0, 0, 0]),
+            Format::R32_SFLOAT => FormatInfo::color(Sfloat, [32, 0, 0, 0]),
+
+            Format::R32G32_UINT => FormatInfo::color(Uint, [32, 32, 0, 0]),
+            Format::R32G32_SINT => FormatInfo::color(Sint, [32, 32, 0, 0]),
+            Format::R32G32_SFLOAT => FormatInfo::color(Sfloat, [32, 32, 0, 0]),
+
+            Format::R32G32B32_UINT => FormatInfo::color(Uint, [32, 32, 32, 0]),
+            Format::R32G32B32_SINT => FormatInfo::color(Sint, [32, 32, 32, 0]),
+            Format::R32G32B32_SFLOAT => FormatInfo::color(Sfloat, [32, 32, 32, 0]),
+
+            Format::R32G32B32A32_UINT => FormatInfo::color(Uint, [32, 32, 32, 32]),
+            Format::R32G32B32A32_SINT => FormatInfo::color(Sint, [32, 32, 32, 32]),
+            Format::R32G32B32A32_SFLOAT => FormatInfo::color(Sfloat, [32, 32, 32, 32]),
+
+            Format::R10G10B10A2_UINT => FormatInfo::color(Uint, [10, 10, 10, 2]),
+            Format::R10G10B10A2_UNORM => FormatInfo::color(Unorm, [10, 10, 10, 2]),
+
+            Format::D16_UNORM => FormatInfo::depth_stencil(Unorm, 2, [16, 0, 0, 0], false),
+            Format::D32_SFLOAT => FormatInfo::depth_stencil(Sfloat, 4, [32, 0, 0, 0], false),
+            Format::D24_UNORM_S8_UINT => FormatInfo::depth_stencil(Unorm, 4, [24, 8, 0, 0], true),
+            Format::D32_SFLOAT_S8_UINT => FormatInfo::depth_stencil(Sfloat, 8, [32, 8, 0, 0], true),
+
+            Format::B10G11R11_UFLOAT => FormatInfo::packed(Ufloat, 4, [10, 11, 11, 0]),
+            Format::E5B9G9R9_UFLOAT => FormatInfo::packed(Ufloat, 4, [9, 9, 9, 0]),
+
+            Format::BC1_RGBA_UNORM => FormatInfo::compressed(Unorm, 8, 4, 4, 4),
+            Format::BC1_RGBA_UNORM_SRGB => FormatInfo::compressed(Srgb, 8, 4, 4, 4),
+            Format::BC2_UNORM => FormatInfo::compressed(Unorm, 16, 4, 4, 4),
+            Format::BC2_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 4, 4, 4),
+            Format::BC3_UNORM => FormatInfo::compressed(Unorm, 16, 4, 4, 4),
+            Format::BC3_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 4, 4, 4),
+            Format::BC4_UNORM => FormatInfo::compressed(Unorm, 8, 4, 4, 1),
+            Format::BC4_SNORM => FormatInfo::compressed(Snorm, 8, 4, 4, 1),
+            Format::BC5_UNORM => FormatInfo::compressed(Unorm, 16, 4, 4, 2),
+            Format::BC5_SNORM => FormatInfo::compressed(Snorm, 16, 4, 4, 2),
+            Format::BC6H_UFLOAT => FormatInfo::compressed(Ufloat, 16, 4, 4, 3),
+            Format::BC6H_SFLOAT => FormatInfo::compressed(Sfloat, 16, 4, 4, 3),
+            Format::BC7_UNORM => FormatInfo::compressed(Unorm, 16, 4, 4, 4),
+            Format::BC7_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 4, 4, 4),
+
+            Format::ETC2_R8G8B8_UNORM => FormatInfo::compressed(Unorm, 8, 4, 4, 3),
+            Format::ETC2_R8G8B8_UNORM_SRGB => FormatInfo::compressed(Srgb, 8, 4, 4, 3),
+            Format::ETC2_R8G8B8A1_UNORM => FormatInfo::compressed(Unorm, 8, 4, 4, 4),
+            Format::ETC2_R8G8B8A1_UNORM_SRGB => FormatInfo::compressed(Srgb, 8, 4, 4, 4),
+            Format::ETC2_R8G8B8A8_UNORM => FormatInfo::compressed(Unorm, 16, 4, 4, 4),
+            Format::ETC2_R8G8B8A8_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 4, 4, 4),
+            Format::EAC_R11_UNORM => FormatInfo::compressed(Unorm, 8, 4, 4, 1),
+            Format::EAC_R11_SNORM => FormatInfo::compressed(Snorm, 8, 4, 4, 1),
+            Format::EAC_R11G11_UNORM => FormatInfo::compressed(Unorm, 16, 4, 4, 2),
+            Format::EAC_R11G11_SNORM => FormatInfo::compressed(Snorm, 16, 4, 4, 2),
+
+            Format::ASTC_4x4_UNORM => FormatInfo::compressed(Unorm, 16, 4, 4, 4),
+            Format::ASTC_4x4_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 4, 4, 4),
+
+            Format::ASTC_5x4_UNORM => FormatInfo::compressed(Unorm, 16, 5, 4, 4),
+            Format::ASTC_5x4_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 5, 4, 4),
+
+            Format::ASTC_5x5_UNORM => FormatInfo::compressed(Unorm, 16, 5, 5, 4),
+            Format::ASTC_5x5_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 5, 5, 4),
+
+            Format::ASTC_6x5_UNORM => FormatInfo::compressed(Unorm, 16, 6, 5, 4),
+            Format::ASTC_6x5_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 6, 5, 4),
+
+            Format::ASTC_6x6_UNORM => FormatInfo::compressed(Unorm, 16, 6, 6, 4),
+            Format::ASTC_6x6_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 6, 6, 4),
+
+            Format::ASTC_8x5_UNORM => FormatInfo::compressed(Unorm, 16, 8, 5, 4),
+            Format::ASTC_8x5_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 8, 5, 4),
+
+            Format::ASTC_8x6_UNORM => FormatInfo::compressed(Unorm, 16, 8, 6, 4),
+            Format::ASTC_8x6_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 8, 6, 4),
+
+            Format::ASTC_8x8_UNORM => FormatInfo::compressed(Unorm, 16, 8, 8, 4),
+            Format::ASTC_8x8_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 8, 8, 4),
+
+            Format::ASTC_10x5_UNORM => FormatInfo::compressed(Unorm, 16, 10, 5, 4),
+            Format::ASTC_10x5_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 10, 5, 4),
+
+            Format::ASTC_10x6_UNORM => FormatInfo::compressed(Unorm, 16, 10, 6, 4),
+            Format::ASTC_10x6_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 10, 6, 4),
+
+            Format::ASTC_10x8_UNORM => FormatInfo::compressed(Unorm, 16, 10, 8, 4),
+            Format::ASTC_10x8_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 10, 8, 4),
+
+            Format::ASTC_10x10_UNORM => FormatInfo::compressed(Unorm, 16, 10, 10, 4),
+            Format::ASTC_10x10_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 10, 10, 4),
+
+            Format::ASTC_12x10_UNORM => FormatInfo::compressed(Unorm, 16, 12, 10, 4),
+            Format::ASTC_12x10_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 12, 10, 4),
+
+            Format::ASTC_12x12_UNORM => FormatInfo::compressed(Unorm, 16, 12, 12, 4),
+            Format::ASTC_12x12_UNORM_SRGB => FormatInfo::compressed(Srgb, 16, 12, 12, 4),
+
+            Format::Unknown(raw) => match Format::from_raw(raw) {
+                Format::Unknown(_) => FormatInfo::unknown(),
+                format => format.info(),
+            },
+        }
+    }
+
+    /// Size in bytes of a texel block, which is a single texel for uncompressed formats.
+    pub const fn block_size(self) -> u32 {
+        self.info().block_size
+    }
+
+    /// Width and height in texels of a texel block.
+    pub const fn block_extent(self) -> crate::Extent2D {
+        let info = self.info();
+
+        crate::Extent2D {
+            width: info.block_width,
+            height: info.block_height,
+        }
+    }
+
+    pub const fn component_count(self) -> u32 {
+        self.info().components
+    }
+
+    /// Bit width of each component in the order they are named, 0 past the last component.
+    ///
+    /// Compressed formats report 0 for every component.
+    pub const fn component_bits(self) -> [u32; 4] {
+        self.info().bits
+    }
+
+    pub const fn numeric_class(self) -> NumericClass {
+        self.info().class
+    }
+
+    pub const fn has_depth(self) -> bool {
+        self.info().depth
+    }
+
+    pub const fn has_stencil(self) -> bool {
+        self.info().stencil
+    }
+
+    pub const fn is_compressed(self) -> bool {
+        let info = self.info();
+        info.block_width > 1 || info.block_height > 1
+    }
+
+    pub const fn is_srgb(self) -> bool {
+        matches!(self.info().class, NumericClass::Srgb)
+    }
+
+    /// The sRGB encoded variant of the format, itself if it already is one.
+    pub const fn to_srgb(self) -> Option<Self> {
+        match self.srgb_pair() {
+            Some((_, srgb)) => Some(srgb),
+            None => None,
+        }
+    }
+
+    /// The linear variant of an sRGB format, itself for other formats.
+    pub const fn to_linear(self) -> Self {
+        match self.srgb_pair() {
+            Some((linear, _)) => linear,
+            None => self,
+        }
+    }
+
+    /// The linear and sRGB variants of a format that has both.
+    const fn srgb_pair(self) -> Option<(Self, Self)> {
+        macro_rules! pairs {
+            ($($linear:ident => $srgb:ident,)*) => {
+                match self {
+                    $(Format::$linear | Format::$srgb => Some((Format::$linear, Format::$srgb)),)*
+                    Format::Unknown(raw) => match Format::from_raw(raw) {
+                        Format::Unknown(_) => None,
+                        format => format.srgb_pair(),
+                    },
+                    _ => None,
+                }
+            };
+        }
+
+        pairs! {
+            R8G8B8A8_UNORM => R8G8B8A8_UNORM_SRGB,
+            B8G8R8A8_UNORM => B8G8R8A8_UNORM_SRGB,
+            BC1_RGBA_UNORM => BC1_RGBA_UNORM_SRGB,
+            BC2_UNORM => BC2_UNORM_SRGB,
+            BC3_UNORM => BC3_UNORM_SRGB,
+            BC7_UNORM => BC7_UNORM_SRGB,
+            ETC2_R8G8B8_UNORM => ETC2_R8G8B8_UNORM_SRGB,
+            ETC2_R8G8B8A1_UNORM => ETC2_R8G8B8A1_UNORM_SRGB,
+            ETC2_R8G8B8A8_UNORM => ETC2_R8G8B8A8_UNORM_SRGB,
+            ASTC_4x4_UNORM => ASTC_4x4_UNORM_SRGB,
+            ASTC_5x4_UNORM => ASTC_5x4_UNORM_SRGB,
+            ASTC_5x5_UNORM => ASTC_5x5_UNORM_SRGB,
+            ASTC_6x5_UNORM => ASTC_6x5_UNORM_SRGB,
+            ASTC_6x6_UNORM => ASTC_6x6_UNORM_SRGB,
+            ASTC_8x5_UNORM => ASTC_8x5_UNORM_SRGB,
+            ASTC_8x6_UNORM => ASTC_8x6_UNORM_SRGB,
+            ASTC_8x8_UNORM => ASTC_8x8_UNORM_SRGB,
+            ASTC_10x5_UNORM => ASTC_10x5_UNORM_SRGB,
+            ASTC_10x6_UNORM => ASTC_10x6_UNORM_SRGB,
+            ASTC_10x8_UNORM => ASTC_10x8_UNORM_SRGB,
+            ASTC_10x10_UNORM => ASTC_10x10_UNORM_SRGB,
+            ASTC_12x10_UNORM => ASTC_12x10_UNORM_SRGB,
+            ASTC_12x12_UNORM => ASTC_12x12_UNORM_SRGB,
+        }
+    }
+
+    /// Whether the selected backend can represent the format.
+    pub const fn is_supported(self) -> bool {
+        self.raw() >= 0
+    }
+
+    /// Bytes needed to store the first `mip_levels` mip levels of `layers` 2D images, with each
+    /// mip level padded to whole texel blocks. Unknown formats need 0 bytes.
+    pub const fn required_bytes(
+        self,
+        extent: crate::Extent2D,
+        mip_levels: u32,
+        layers: u32,
+    ) -> u64 {
+        let info = self.info();
+
+        let mut total = 0u64;
+        let mut level = 0;
+        while level < mip_levels {
+            let width = max(extent.width >> level, 1);
+            let height = max(extent.height >> level, 1);
+
+            let blocks_x = width.div_ceil(info.block_width) as u64;
+            let blocks_y = height.div_ceil(info.block_height) as u64;
+
+            total += blocks_x * blocks_y * info.block_size as u64;
+            level += 1;
+        }
+
+        total * layers as u64
+    }
+}
+
+const fn max(a: u32, b: u32) -> u32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/*
+   Format Capabilities
+*/
+
+macro_rules! api_format_feature {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::FormatFeatureConstants as crate::api::traits::constants::FormatFeatureConstants>::$name
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct FormatFeatureFlags: i32 {
+        const SAMPLED = api_format_feature!(SAMPLED);
+        /// Can be sampled with linear filtering.
+        const FILTERABLE = api_format_feature!(FILTERABLE);
+        const COLOR_ATTACHMENT = api_format_feature!(COLOR_ATTACHMENT);
+        /// Can be blended into as a color attachment.
+        const BLENDABLE = api_format_feature!(BLENDABLE);
+        const DEPTH_STENCIL_ATTACHMENT = api_format_feature!(DEPTH_STENCIL_ATTACHMENT);
+        const STORAGE = api_format_feature!(STORAGE);
+        const VERTEX_BUFFER = api_format_feature!(VERTEX_BUFFER);
+        const BLIT_SRC = api_format_feature!(BLIT_SRC);
+        const BLIT_DST = api_format_feature!(BLIT_DST);
+    }
+}
+
+/// What a device supports for a format.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FormatCapabilities {
+    pub optimal_tiling: FormatFeatureFlags,
+    pub linear_tiling: FormatFeatureFlags,
+    /// Features of buffers holding the format, such as `VERTEX_BUFFER`.
+    pub buffer: FormatFeatureFlags,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Extent2D;
+    use NumericClass::*;
+
+    /// A format's block size, block extent, component count, class, depth and stencil.
+    type Expected = (Format, u32, (u32, u32), u32, NumericClass, bool, bool);
+
+    #[rustfmt::skip]
+    const FORMATS: &[Expected] = &[
+        (Format::R8_UINT, 1, (1, 1), 1, Uint, false, false),
+        (Format::R8_SINT, 1, (1, 1), 1, Sint, false, false),
+        (Format::R8_UNORM, 1, (1, 1), 1, Unorm, false, false),
+        (Format::R8_SNORM, 1, (1, 1), 1, Snorm, false, false),
+        (Format::R8G8_UINT, 2, (1, 1), 2, Uint, false, false),
+        (Format::R8G8_SINT, 2, (1, 1), 2, Sint, false, false),
+        (Format::R8G8_UNORM, 2, (1, 1), 2, Unorm, false, false),
+        (Format::R8G8_SNORM, 2, (1, 1), 2, Snorm, false, false),
+        (Format::R8G8B8A8_UINT, 4, (1, 1), 4, Uint, false, false),
+        (Format::R8G8B8A8_SINT, 4, (1, 1), 4, Sint, false, false),
+        (Format::R8G8B8A8_UNORM, 4, (1, 1), 4, Unorm, false, false),
+        (Format::R8G8B8A8_SNORM, 4, (1, 1), 4, Snorm, false, false),
+        (Format::R8G8B8A8_UNORM_SRGB, 4, (1, 1), 4, Srgb, false, false),
+        (Format::B8G8R8A8_UNORM, 4, (1, 1), 4, Unorm, false, false),
+        (Format::B8G8R8A8_UNORM_SRGB, 4, (1, 1), 4, Srgb, false, false),
+        (Format::R16_UINT, 2, (1, 1), 1, Uint, false, false),
+        (Format::R16_SINT, 2, (1, 1), 1, Sint, false, false),
+        (Format::R16_UNORM, 2, (1, 1), 1, Unorm, false, false),
+        (Format::R16_SNORM, 2, (1, 1), 1, Snorm, false, false),
+        (Format::R16G16_UINT, 4, (1, 1), 2, Uint, false, false),
+        (Format::R16G16_SINT, 4, (1, 1), 2, Sint, false, false),
+        (Format::R16G16_UNORM, 4, (1, 1), 2, Unorm, false, false),
+        (Format::R16G16_SNORM, 4, (1, 1), 2, Snorm, false, false),
+        (Format::R16G16B16A16_UINT, 8, (1, 1), 4, Uint, false, false),
+        (Format::R16G16B16A16_SINT, 8, (1, 1), 4, Sint, false, false),
+        (Format::R16G16B16A16_UNORM, 8, (1, 1), 4, Unorm, false, false),
+        (Format::R16G16B16A16_SNORM, 8, (1, 1), 4, Snorm, false, false),
+        (Format::R16G16B16A16_SFLOAT, 8, (1, 1), 4, Sfloat, false, false),
+        (Format::R32_UINT, 4, (1, 1), 1, Uint, false, false),
+        (Format::R32_SINT, 4, (1, 1), 1, Sint, false, false),
+        (Format::R32_SFLOAT, 4, (1, 1), 1, Sfloat, false, false),
+        (Format::R32G32_UINT, 8, (1, 1), 2, Uint, false, false),
+        (Format::R32G32_SINT, 8, (1, 1), 2, Sint, false, false),
+        (Format::R32G32_SFLOAT, 8, (1, 1), 2, Sfloat, false, false),
+        (Format::R32G32B32_UINT, 12, (1, 1), 3, Uint, false, false),
+        (Format::R32G32B32_SINT, 12, (1, 1), 3, Sint, false, false),
+        (Format::R32G32B32_SFLOAT, 12, (1, 1), 3, Sfloat, false, false),
+        (Format::R32G32B32A32_UINT, 16, (1, 1), 4, Uint, false, false),
+        (Format::R32G32B32A32_SINT, 16, (1, 1), 4, Sint, false, false),
+        (Format::R32G32B32A32_SFLOAT, 16, (1, 1), 4, Sfloat, false, false),
+        (Format::R10G10B10A2_UINT, 4, (1, 1), 4, Uint, false, false),
+        (Format::R10G10B10A2_UNORM, 4, (1, 1), 4, Unorm, false, false),
+        (Format::D16_UNORM, 2, (1, 1), 1, Unorm, true, false),
+        (Format::D32_SFLOAT, 4, (1, 1), 1, Sfloat, true, false),
+        (Format::D24_UNORM_S8_UINT, 4, (1, 1), 2, Unorm, true, true),
+        (Format::D32_SFLOAT_S8_UINT, 8, (1, 1), 2, Sfloat, true, true),
+        (Format::B10G11R11_UFLOAT, 4, (1, 1), 3, Ufloat, false, false),
+        (Format::E5B9G9R9_UFLOAT, 4, (1, 1), 3, Ufloat, false, false),
+        (Format::BC1_RGBA_UNORM, 8, (4, 4), 4, Unorm, false, false),
+        (Format::BC1_RGBA_UNORM_SRGB, 8, (4, 4), 4, Srgb, false, false),
+        (Format::BC2_UNORM, 16, (4, 4), 4, Unorm, false, false),
+        (Format::BC2_UNORM_SRGB, 16, (4, 4), 4, Srgb, false, false),
+        (Format::BC3_UNORM, 16, (4, 4), 4, Unorm, false, false),
+        (Format::BC3_UNORM_SRGB, 16, (4, 4), 4, Srgb, false, false),
+        (Format::BC4_UNORM, 8, (4, 4), 1, Unorm, false, false),
+        (Format::BC4_SNORM, 8, (4, 4), 1, Snorm, false, false),
+        (Format::BC5_UNORM, 16, (4, 4), 2, Unorm, false, false),
+        (Format::BC5_SNORM, 16, (4, 4), 2, Snorm, false, false),
+        (Format::BC6H_UFLOAT, 16, (4, 4), 3, Ufloat, false, false),
+        (Format::BC6H_SFLOAT, 16, (4, 4), 3, Sfloat, false, false),
+        (Format::BC7_UNORM, 16, (4, 4), 4, Unorm, false, false),
+        (Format::BC7_UNORM_SRGB, 16, (4, 4), 4, Srgb, false, false),
+        (Format::ETC2_R8G8B8_UNORM, 8, (4, 4), 3, Unorm, false, false),
+        (Format::ETC2_R8G8B8_UNORM_SRGB, 8, (4, 4), 3, Srgb, false, false),
+        (Format::ETC2_R8G8B8A1_UNORM, 8, (4, 4), 4, Unorm, false, false),
+        (Format::ETC2_R8G8B8A1_UNORM_SRGB, 8, (4, 4), 4, Srgb, false, false),
+        (Format::ETC2_R8G8B8A8_UNORM, 16, (4, 4), 4, Unorm, false, false),
+        (Format::ETC2_R8G8B8A8_UNORM_SRGB, 16, (4, 4), 4, Srgb, false, false),
+        (Format::EAC_R11_UNORM, 8, (4, 4), 1, Unorm, false, false),
+        (Format::EAC_R11_SNORM, 8, (4, 4), 1, Snorm, false, false),
+        (Format::EAC_R11G11_UNORM, 16, (4, 4), 2, Unorm, false, false),
+        (Format::EAC_R11G11_SNORM, 16, (4, 4), 2, Snorm, false, false),
+        (Format::ASTC_4x4_UNORM, 16, (4, 4), 4, Unorm, false, false),
+        (Format::ASTC_4x4_UNORM_SRGB, 16, (4, 4), 4, Srgb, false, false),
+        (Format::ASTC_5x4_UNORM, 16, (5, 4), 4, Unorm, false, false),
+        (Format::ASTC_5x4_UNORM_SRGB, 16, (5, 4), 4, Srgb, false, false),
+        (Format::ASTC_5x5_UNORM, 16, (5, 5), 4, Unorm, false, false),
+        (Format::ASTC_5x5_UNORM_SRGB, 16, (5, 5), 4, Srgb, false, false),
+        (Format::ASTC_6x5_UNORM, 16, (6, 5), 4, Unorm, false, false),
+        (Format::ASTC_6x5_UNORM_SRGB, 16, (6, 5), 4, Srgb, false, false),
+        (Format::ASTC_6x6_UNORM, 16, (6, 6), 4, Unorm, false, false),
+        (Format::ASTC_6x6_UNORM_SRGB, 16, (6, 6), 4, Srgb, false, false),
+        (Format::ASTC_8x5_UNORM, 16, (8, 5), 4, Unorm, false, false),
+        (Format::ASTC_8x5_UNORM_SRGB, 16, (8, 5), 4, Srgb, false, false),
+        (Format::ASTC_8x6_UNORM, 16, (8, 6), 4, Unorm, false, false),
+        (Format::ASTC_8x6_UNORM_SRGB, 16, (8, 6), 4, Srgb, false, false),
+        (Format::ASTC_8x8_UNORM, 16, (8, 8), 4, Unorm, false, false),
+        (Format::ASTC_8x8_UNORM_SRGB, 16, (8, 8), 4, Srgb, false, false),
+        (Format::ASTC_10x5_UNORM, 16, (10, 5), 4, Unorm, false, false),
+        (Format::ASTC_10x5_UNORM_SRGB, 16, (10, 5), 4, Srgb, false, false),
+        (Format::ASTC_10x6_UNORM, 16, (10, 6), 4, Unorm, false, false),
+        (Format::ASTC_10x6_UNORM_SRGB, 16, (10, 6), 4, Srgb, false, false),
+        (Format::ASTC_10x8_UNORM, 16, (10, 8), 4, Unorm, false, false),
+        (Format::ASTC_10x8_UNORM_SRGB, 16, (10, 8), 4, Srgb, false, false),
+        (Format::ASTC_10x10_UNORM, 16, (10, 10), 4, Unorm, false, false),
+        (Format::ASTC_10x10_UNORM_SRGB, 16, (10, 10), 4, Srgb, false, false),
+        (Format::ASTC_12x10_UNORM, 16, (12, 10), 4, Unorm, false, false),
+        (Format::ASTC_12x10_UNORM_SRGB, 16, (12, 10), 4, Srgb, false, false),
+        (Format::ASTC_12x12_UNORM, 16, (12, 12), 4, Unorm, false, false),
+        (Format::ASTC_12x12_UNORM_SRGB, 16, (12, 12), 4, Srgb, false, false),
+    ];
+
+    /// Linear formats and their sRGB variants.
+    const SRGB_PAIRS: &[(Format, Format)] = &[
+        (Format::R8G8B8A8_UNORM, Format::R8G8B8A8_UNORM_SRGB),
+        (Format::B8G8R8A8_UNORM, Format::B8G8R8A8_UNORM_SRGB),
+        (Format::BC1_RGBA_UNORM, Format::BC1_RGBA_UNORM_SRGB),
+        (Format::BC2_UNORM, Format::BC2_UNORM_SRGB),
+        (Format::BC3_UNORM, Format::BC3_UNORM_SRGB),
+        (Format::BC7_UNORM, Format::BC7_UNORM_SRGB),
+        (Format::ETC2_R8G8B8_UNORM, Format::ETC2_R8G8B8_UNORM_SRGB),
+        (
+            Format::ETC2_R8G8B8A1_UNORM,
+            Format::ETC2_R8G8B8A1_UNORM_SRGB,
+        ),
+        (
+            Format::ETC2_R8G8B8A8_UNORM,
+            Format::ETC2_R8G8B8A8_UNORM_SRGB,
+        ),
+        (Format::ASTC_4x4_UNORM, Format::ASTC_4x4_UNORM_SRGB),
+        (Format::ASTC_5x4_UNORM, Format::ASTC_5x4_UNORM_SRGB),
+        (Format::ASTC_5x5_UNORM, Format::ASTC_5x5_UNORM_SRGB),
+        (Format::ASTC_6x5_UNORM, Format::ASTC_6x5_UNORM_SRGB),
+        (Format::ASTC_6x6_UNORM, Format::ASTC_6x6_UNORM_SRGB),
+        (Format::ASTC_8x5_UNORM, Format::ASTC_8x5_UNORM_SRGB),
+        (Format::ASTC_8x6_UNORM, Format::ASTC_8x6_UNORM_SRGB),
+        (Format::ASTC_8x8_UNORM, Format::ASTC_8x8_UNORM_SRGB),
+        (Format::ASTC_10x5_UNORM, Format::ASTC_10x5_UNORM_SRGB),
+        (Format::ASTC_10x6_UNORM, Format::ASTC_10x6_UNORM_SRGB),
+        (Format::ASTC_10x8_UNORM, Format::ASTC_10x8_UNORM_SRGB),
+        (Format::ASTC_10x10_UNORM, Format::ASTC_10x10_UNORM_SRGB),
+        (Format::ASTC_12x10_UNORM, Format::ASTC_12x10_UNORM_SRGB),
+        (Format::ASTC_12x12_UNORM, Format::ASTC_12x12_UNORM_SRGB),
+    ];
+
+    fn extent(width: u32, height: u32) -> Extent2D {
+        Extent2D { width, height }
+    }
+
+    #[test]
+    fn table_covers_every_format() {
+        assert_eq!(FORMATS.len(), Format::KNOWN.len());
+
+        for format in Format::KNOWN {
+            assert!(
+                FORMATS.iter().any(|entry| entry.0 == *format),
+                "{format:?} is missing from the table"
+            );
+        }
+    }
+
+    #[test]
+    fn describes_every_format() {
+        for &(format, block_size, (width, height), components, class, depth, stencil) in FORMATS {
+            assert_eq!(format.block_size(), block_size, "{format:?}");
+            assert_eq!(format.block_extent().width, width, "{format:?}");
+            assert_eq!(format.block_extent().height, height, "{format:?}");
+            assert_eq!(format.component_count(), components, "{format:?}");
+            assert_eq!(format.numeric_class(), class, "{format:?}");
+            assert_eq!(format.has_depth(), depth, "{format:?}");
+            assert_eq!(format.has_stencil(), stencil, "{format:?}");
+            assert_eq!(format.is_compressed(), width > 1, "{format:?}");
+            assert_eq!(format.is_srgb(), class == Srgb, "{format:?}");
+
+            let bits = format.component_bits();
+            if format.is_compressed() {
+                assert_eq!(bits, [0; 4], "{format:?}");
+            } else {
+                let named = bits.iter().take_while(|bits| **bits != 0).count();
+                assert_eq!(named as u32, components, "{format:?}");
+                assert!(bits.iter().sum::<u32>() <= block_size * 8, "{format:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn converts_between_srgb_and_linear() {
+        for &(linear, srgb) in SRGB_PAIRS {
+            assert_eq!(linear.to_srgb(), Some(srgb));
+            assert_eq!(srgb.to_srgb(), Some(srgb));
+            assert_eq!(srgb.to_linear(), linear);
+            assert_eq!(linear.to_linear(), linear);
+
+            assert_eq!(linear.block_size(), srgb.block_size());
+            assert_eq!(linear.block_extent().width, srgb.block_extent().width);
+            assert_eq!(linear.block_extent().height, srgb.block_extent().height);
+        }
+
+        for format in Format::KNOWN {
+            let paired = SRGB_PAIRS
+                .iter()
+                .any(|&(linear, srgb)| *format == linear || *format == srgb);
+
+            if !paired {
+                assert_eq!(format.to_srgb(), None, "{format:?}");
+                assert_eq!(format.to_linear(), *format, "{format:?}");
+                assert!(!format.is_srgb(), "{format:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn required_bytes_of_every_format() {
+        for &(format, block_size, (width, height), ..) in FORMATS {
+            let blocks = 30u64.div_ceil(width as u64) * 17u64.div_ceil(height as u64);
+
+            assert_eq!(
+                format.required_bytes(extent(30, 17), 1, 1),
+                blocks * block_size as u64,
+                "{format:?}"
+            );
+            assert_eq!(
+                format.required_bytes(extent(30, 17), 1, 6),
+                6 * blocks * block_size as u64,
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn required_bytes_of_mip_chains() {
+        // 4x4, 2x2 and 1x1
+        assert_eq!(
+            Format::R8G8B8A8_UNORM.required_bytes(extent(4, 4), 3, 1),
+            (16 + 4 + 1) * 4
+        );
+        // 8x2, 4x1, 2x1 and 1x1
+        assert_eq!(
+            Format::R16_UINT.required_bytes(extent(8, 2), 4, 2),
+            (16 + 4 + 2 + 1) * 2 * 2
+        );
+        assert_eq!(Format::R8_UNORM.required_bytes(extent(4, 4), 0, 1), 0);
+    }
+
+    #[test]
+    fn one_texel_bc7_image_fills_a_block() {
+        assert_eq!(Format::BC7_UNORM.required_bytes(extent(1, 1), 1, 1), 16);
+        assert_eq!(
+            Format::BC7_UNORM_SRGB.required_bytes(extent(1, 1), 1, 1),
+            16
+        );
+
+        // 4x4, 2x2 and 1x1 each take a whole block
+        assert_eq!(Format::BC7_UNORM.required_bytes(extent(4, 4), 3, 1), 48);
+        assert_eq!(Format::BC1_RGBA_UNORM.required_bytes(extent(1, 1), 1, 1), 8);
+    }
+
+    #[test]
+    fn astc_pads_partial_blocks() {
+        // 3 by 2 blocks of 6x5 texels
+        assert_eq!(
+            Format::ASTC_6x5_UNORM.required_bytes(extent(13, 7), 1, 1),
+            96
+        );
+
+        // 30x17 takes 3 by 3 blocks of 10x8 texels, and 15x8 takes 2 by 1
+        assert_eq!(
+            Format::ASTC_10x8_UNORM_SRGB.required_bytes(extent(30, 17), 2, 1),
+            (9 + 2) * 16
+        );
+
+        // smaller than a single 12x12 block
+        assert_eq!(
+            Format::ASTC_12x12_UNORM.required_bytes(extent(5, 3), 1, 1),
+            16
+        );
+    }
+
+    #[test]
+    fn unknown_formats_with_known_values_match_their_variant() {
+        let unknown = Format::Unknown(Format::BC7_UNORM.raw());
+
+        assert_eq!(unknown, Format::BC7_UNORM);
+        assert_eq!(unknown.block_size(), 16);
+        assert_eq!(unknown.to_srgb(), Some(Format::BC7_UNORM_SRGB));
+        assert!(matches!(
+            Format::from_raw(Format::D16_UNORM.raw()),
+            Format::D16_UNORM
+        ));
+
+        let raw = (0..)
+            .find(|raw| Format::KNOWN.iter().all(|format| format.raw() != *raw))
+            .unwrap();
+        let unknown = Format::from_raw(raw);
+
+        assert!(matches!(unknown, Format::Unknown(value) if value == raw));
+        assert_eq!(unknown.numeric_class(), NumericClass::Unknown);
+        assert_eq!(unknown.required_bytes(extent(4, 4), 1, 1), 0);
+        assert_eq!(unknown.to_srgb(), None);
+    }
 }
@@ -1,9 +1,74 @@
 // Copyright (c) 2024 Jacob R. Green
 // All rights reserved.
 
+/// Implements `raw` and `from_raw` for a `#[repr(i32)]` enum whose `Unknown(i32)` variant keeps
+/// a native value xgpu has no variant for, listing every other variant.
+///
+/// Equality and hashing compare native values, so an `Unknown` holding a known value is equal
+/// to that value's variant.
+macro_rules! impl_raw {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        impl $name {
+            /// Every variant but `Unknown`.
+            pub const KNOWN: &'static [Self] = &[$(Self::$variant),*];
+
+            /// The value the backend uses for this variant.
+            pub const fn raw(self) -> i32 {
+                match self {
+                    Self::Unknown(raw) => raw,
+                    // a primitive representation enum starts with its discriminant
+                    _ => unsafe { *(&self as *const Self as *const i32) },
+                }
+            }
+
+            /// The variant the backend uses `raw` for, or `Unknown` if there's none.
+            pub const fn from_raw(raw: i32) -> Self {
+                let mut i = 0;
+                while i < Self::KNOWN.len() {
+                    if Self::KNOWN[i].raw() == raw {
+                        return Self::KNOWN[i];
+                    }
+                    i += 1;
+                }
+
+                Self::Unknown(raw)
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.raw() == other.raw()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl std::hash::Hash for $name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.raw().hash(state);
+            }
+        }
+    };
+}
+
 mod format;
 pub use format::*;
 
+mod debug;
+pub use debug::*;
+
+mod device;
+pub use device::*;
+
+mod render;
+pub use render::*;
+
+mod resource;
+pub use resource::*;
+
+mod sync;
+pub use sync::*;
+
 #[derive(Debug, Copy, Clone)]
 pub struct Extent2D {
     pub width: u32,
@@ -28,7 +93,7 @@ macro_rules! api_device_type {
     };
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone)]
 #[repr(i32)]
 pub enum DeviceType {
     Other = api_device_type!(OTHER),
@@ -36,8 +101,18 @@ pub enum DeviceType {
     DiscreteGpu = api_device_type!(DISCRETE_GPU),
     VirtualGpu = api_device_type!(VIRTUAL_GPU),
     Cpu = api_device_type!(CPU),
+    /// A device type the backend reported that xgpu doesn't know about.
+    Unknown(i32) = i32::MAX,
 }
 
+impl_raw!(DeviceType {
+    Other,
+    IntegratedGpu,
+    DiscreteGpu,
+    VirtualGpu,
+    Cpu,
+});
+
 impl DeviceType {
     pub fn is_gpu(self) -> bool {
         match self {
@@ -49,25 +124,75 @@ impl DeviceType {
 
 #[derive(Debug, Copy, Clone)]
 #[non_exhaustive]
-#[repr(u32)]
 pub enum Vendor {
-    Nvidia = 0x10DE,
-    Amd = 0x1022,
-    Intel = 0x8086,
+    Nvidia,
+    Amd,
+    Intel,
+    Arm,
+    Qualcomm,
+    Apple,
+    ImgTec,
+    Broadcom,
+    Microsoft,
+    /// Mesa's software drivers, such as llvmpipe and lavapipe.
+    Mesa,
+    /// A PCI or Khronos vendor ID xgpu doesn't know about.
+    Unknown(u32),
 }
 
 impl Into<u32> for Vendor {
     fn into(self) -> u32 {
-        self as u32
+        match self {
+            Vendor::Nvidia => 0x10DE,
+            Vendor::Amd => 0x1002,
+            Vendor::Intel => 0x8086,
+            Vendor::Arm => 0x13B5,
+            Vendor::Qualcomm => 0x5143,
+            Vendor::Apple => 0x106B,
+            Vendor::ImgTec => 0x1010,
+            Vendor::Broadcom => 0x14E4,
+            Vendor::Microsoft => 0x1414,
+            Vendor::Mesa => 0x10005,
+            Vendor::Unknown(id) => id,
+        }
     }
 }
 
 impl From<u32> for Vendor {
     fn from(vendor: u32) -> Self {
-        unsafe { std::mem::transmute(vendor) }
+        match vendor {
+            // 0x1022 is AMD's CPU and chipset vendor ID
+            0x1002 | 0x1022 => Vendor::Amd,
+            0x10DE => Vendor::Nvidia,
+            0x8086 => Vendor::Intel,
+            0x13B5 => Vendor::Arm,
+            0x5143 => Vendor::Qualcomm,
+            0x106B => Vendor::Apple,
+            0x1010 => Vendor::ImgTec,
+            0x14E4 => Vendor::Broadcom,
+            0x1414 => Vendor::Microsoft,
+            0x10005 => Vendor::Mesa,
+            id => Vendor::Unknown(id),
+        }
     }
 }
 
+// compares normalized IDs, so an `Unknown` holding a known ID is equal to that ID's vendor
+impl PartialEq for Vendor {
+    fn eq(&self, other: &Self) -> bool {
+        let normalize = |vendor: Vendor| -> u32 {
+            match vendor {
+                Vendor::Unknown(id) => Vendor::from(id).into(),
+                vendor => vendor.into(),
+            }
+        };
+
+        normalize(*self) == normalize(*other)
+    }
+}
+
+impl Eq for Vendor {}
+
 // #[derive(Debug, Copy, Clone)]
 // #[repr(i32)]
 // pub enum CommandBufferLevel {
@@ -89,8 +214,17 @@ pub enum PresentMode {
     Mailbox = api_present_mode!(MAILBOX),
     Fifo = api_present_mode!(FIFO),
     FifoRelaxed = api_present_mode!(FIFO_RELAXED),
+    /// A present mode the backend reported that xgpu doesn't know about.
+    Unknown(i32) = i32::MAX,
 }
 
+impl_raw!(PresentMode {
+    Immediate,
+    Mailbox,
+    Fifo,
+    FifoRelaxed,
+});
+
 macro_rules! api_color_space {
     ($name:tt) => {
         <<crate::api::Api as crate::api::traits::GraphicsApi>::ColorSpaceConstants as crate::api::traits::constants::ColorSpaceConstants>::$name
@@ -106,8 +240,17 @@ pub enum Colorspace {
     SRGB_EXT_LINEAR = api_color_space!(SRGB_EXT_LINEAR),
     HDR10_ST2084 = api_color_space!(HDR10_ST2084),
     HDR10_HLG = api_color_space!(HDR10_HLG),
+    /// A colorspace the backend reported that xgpu doesn't know about.
+    Unknown(i32) = i32::MAX,
 }
 
+impl_raw!(Colorspace {
+    SRGB_NONLINEAR,
+    SRGB_EXT_LINEAR,
+    HDR10_ST2084,
+    HDR10_HLG,
+});
+
 impl Default for Colorspace {
     fn default() -> Self {
         Self::SRGB_NONLINEAR
@@ -300,3 +443,61 @@ impl Default for PrimitiveTopology {
         Self::TriangleList
     }
 }
+
+macro_rules! api_descriptor_type {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::DescriptorTypeConstants as crate::api::traits::constants::DescriptorTypeConstants>::$name
+    };
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(i32)]
+pub enum DescriptorType {
+    Sampler = api_descriptor_type!(SAMPLER),
+    CombinedImageSampler = api_descriptor_type!(COMBINED_IMAGE_SAMPLER),
+    SampledImage = api_descriptor_type!(SAMPLED_IMAGE),
+    StorageImage = api_descriptor_type!(STORAGE_IMAGE),
+    UniformTexelBuffer = api_descriptor_type!(UNIFORM_TEXEL_BUFFER),
+    StorageTexelBuffer = api_descriptor_type!(STORAGE_TEXEL_BUFFER),
+    UniformBuffer = api_descriptor_type!(UNIFORM_BUFFER),
+    StorageBuffer = api_descriptor_type!(STORAGE_BUFFER),
+    InputAttachment = api_descriptor_type!(INPUT_ATTACHMENT),
+    AccelerationStructure = api_descriptor_type!(ACCELERATION_STRUCTURE),
+}
+
+macro_rules! api_shader_stage {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::ShaderStageConstants as crate::api::traits::constants::ShaderStageConstants>::$name
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ShaderStageFlags: i32 {
+        const VERTEX = api_shader_stage!(VERTEX);
+        const TESSELLATION_CONTROL = api_shader_stage!(TESSELLATION_CONTROL);
+        const TESSELLATION_EVALUATION = api_shader_stage!(TESSELLATION_EVALUATION);
+        const GEOMETRY = api_shader_stage!(GEOMETRY);
+        const FRAGMENT = api_shader_stage!(FRAGMENT);
+        const COMPUTE = api_shader_stage!(COMPUTE);
+    }
+}
+
+macro_rules! api_vertex_input_rate {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::VertexInputRateConstants as crate::api::traits::constants::VertexInputRateConstants>::$name
+    };
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(i32)]
+pub enum VertexInputRate {
+    Vertex = api_vertex_input_rate!(VERTEX),
+    Instance = api_vertex_input_rate!(INSTANCE),
+}
+
+impl Default for VertexInputRate {
+    fn default() -> Self {
+        Self::Vertex
+    }
+}
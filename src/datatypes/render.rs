@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+macro_rules! api_attachment_load_op {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::AttachmentLoadOpConstants as crate::api::traits::constants::AttachmentLoadOpConstants>::$name
+    };
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(i32)]
+pub enum AttachmentLoadOp {
+    Load = api_attachment_load_op!(LOAD),
+    Clear = api_attachment_load_op!(CLEAR),
+    DontCare = api_attachment_load_op!(DONT_CARE),
+}
+
+impl Default for AttachmentLoadOp {
+    fn default() -> Self {
+        Self::DontCare
+    }
+}
+
+macro_rules! api_attachment_store_op {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::AttachmentStoreOpConstants as crate::api::traits::constants::AttachmentStoreOpConstants>::$name
+    };
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(i32)]
+pub enum AttachmentStoreOp {
+    Store = api_attachment_store_op!(STORE),
+    DontCare = api_attachment_store_op!(DONT_CARE),
+}
+
+impl Default for AttachmentStoreOp {
+    fn default() -> Self {
+        Self::Store
+    }
+}
+
+/// A color clear value, interpreted according to the attachment's numeric format.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ClearColor {
+    Float([f32; 4]),
+    Int([i32; 4]),
+    Uint([u32; 4]),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ClearValue {
+    Color(ClearColor),
+    DepthStencil { depth: f32, stencil: u32 },
+}
+
+impl Default for ClearValue {
+    fn default() -> Self {
+        Self::Color(ClearColor::Float([0.0; 4]))
+    }
+}
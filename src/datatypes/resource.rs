@@ -0,0 +1,77 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+macro_rules! api_image_aspect {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::ImageAspectConstants as crate::api::traits::constants::ImageAspectConstants>::$name
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ImageAspectFlags: i32 {
+        const COLOR = api_image_aspect!(COLOR);
+        const DEPTH = api_image_aspect!(DEPTH);
+        const STENCIL = api_image_aspect!(STENCIL);
+    }
+}
+
+impl Default for ImageAspectFlags {
+    fn default() -> Self {
+        Self::COLOR
+    }
+}
+
+macro_rules! api_buffer_usage {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::BufferUsageConstants as crate::api::traits::constants::BufferUsageConstants>::$name
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct BufferUsageFlags: i32 {
+        const TRANSFER_SRC = api_buffer_usage!(TRANSFER_SRC);
+        const TRANSFER_DST = api_buffer_usage!(TRANSFER_DST);
+        const UNIFORM = api_buffer_usage!(UNIFORM);
+        const STORAGE = api_buffer_usage!(STORAGE);
+        const INDEX = api_buffer_usage!(INDEX);
+        const VERTEX = api_buffer_usage!(VERTEX);
+        const INDIRECT = api_buffer_usage!(INDIRECT);
+    }
+}
+
+macro_rules! api_image_usage {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::ImageUsageConstants as crate::api::traits::constants::ImageUsageConstants>::$name
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ImageUsageFlags: i32 {
+        const TRANSFER_SRC = api_image_usage!(TRANSFER_SRC);
+        const TRANSFER_DST = api_image_usage!(TRANSFER_DST);
+        const SAMPLED = api_image_usage!(SAMPLED);
+        const STORAGE = api_image_usage!(STORAGE);
+        const COLOR_ATTACHMENT = api_image_usage!(COLOR_ATTACHMENT);
+        const DEPTH_STENCIL_ATTACHMENT = api_image_usage!(DEPTH_STENCIL_ATTACHMENT);
+    }
+}
+
+/// Where a resource's memory lives.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryLocation {
+    /// Fastest for the device, not visible to the host.
+    DeviceLocal,
+    /// Host visible memory written by the host and read by the device.
+    Upload,
+    /// Host visible memory written by the device and read by the host.
+    Readback,
+}
+
+impl Default for MemoryLocation {
+    fn default() -> Self {
+        Self::DeviceLocal
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+macro_rules! api_image_layout {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::ImageLayoutConstants as crate::api::traits::constants::ImageLayoutConstants>::$name
+    };
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(i32)]
+pub enum ImageLayout {
+    Undefined = api_image_layout!(UNDEFINED),
+    General = api_image_layout!(GENERAL),
+    ColorAttachment = api_image_layout!(COLOR_ATTACHMENT),
+    DepthStencilAttachment = api_image_layout!(DEPTH_STENCIL_ATTACHMENT),
+    DepthStencilReadOnly = api_image_layout!(DEPTH_STENCIL_READ_ONLY),
+    ShaderReadOnly = api_image_layout!(SHADER_READ_ONLY),
+    TransferSrc = api_image_layout!(TRANSFER_SRC),
+    TransferDst = api_image_layout!(TRANSFER_DST),
+    Preinitialized = api_image_layout!(PREINITIALIZED),
+    PresentSrc = api_image_layout!(PRESENT_SRC),
+}
+
+impl Default for ImageLayout {
+    fn default() -> Self {
+        Self::Undefined
+    }
+}
+
+macro_rules! api_access {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::AccessConstants as crate::api::traits::constants::AccessConstants>::$name
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct AccessFlags: u64 {
+        const INDIRECT_COMMAND_READ = api_access!(INDIRECT_COMMAND_READ);
+        const INDEX_READ = api_access!(INDEX_READ);
+        const VERTEX_ATTRIBUTE_READ = api_access!(VERTEX_ATTRIBUTE_READ);
+        const UNIFORM_READ = api_access!(UNIFORM_READ);
+        const INPUT_ATTACHMENT_READ = api_access!(INPUT_ATTACHMENT_READ);
+        const SHADER_READ = api_access!(SHADER_READ);
+        const SHADER_WRITE = api_access!(SHADER_WRITE);
+        const COLOR_ATTACHMENT_READ = api_access!(COLOR_ATTACHMENT_READ);
+        const COLOR_ATTACHMENT_WRITE = api_access!(COLOR_ATTACHMENT_WRITE);
+        const DEPTH_STENCIL_ATTACHMENT_READ = api_access!(DEPTH_STENCIL_ATTACHMENT_READ);
+        const DEPTH_STENCIL_ATTACHMENT_WRITE = api_access!(DEPTH_STENCIL_ATTACHMENT_WRITE);
+        const TRANSFER_READ = api_access!(TRANSFER_READ);
+        const TRANSFER_WRITE = api_access!(TRANSFER_WRITE);
+        const HOST_READ = api_access!(HOST_READ);
+        const HOST_WRITE = api_access!(HOST_WRITE);
+        const MEMORY_READ = api_access!(MEMORY_READ);
+        const MEMORY_WRITE = api_access!(MEMORY_WRITE);
+    }
+}
+
+macro_rules! api_pipeline_stage {
+    ($name:tt) => {
+        <<crate::api::Api as crate::api::traits::GraphicsApi>::PipelineStageConstants as crate::api::traits::constants::PipelineStageConstants>::$name
+    };
+}
+
+bitflags::bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct PipelineStageFlags: u64 {
+        const TOP_OF_PIPE = api_pipeline_stage!(TOP_OF_PIPE);
+        const DRAW_INDIRECT = api_pipeline_stage!(DRAW_INDIRECT);
+        const VERTEX_INPUT = api_pipeline_stage!(VERTEX_INPUT);
+        const VERTEX_SHADER = api_pipeline_stage!(VERTEX_SHADER);
+        const TESSELLATION_CONTROL_SHADER = api_pipeline_stage!(TESSELLATION_CONTROL_SHADER);
+        const TESSELLATION_EVALUATION_SHADER = api_pipeline_stage!(TESSELLATION_EVALUATION_SHADER);
+        const GEOMETRY_SHADER = api_pipeline_stage!(GEOMETRY_SHADER);
+        const FRAGMENT_SHADER = api_pipeline_stage!(FRAGMENT_SHADER);
+        const EARLY_FRAGMENT_TESTS = api_pipeline_stage!(EARLY_FRAGMENT_TESTS);
+        const LATE_FRAGMENT_TESTS = api_pipeline_stage!(LATE_FRAGMENT_TESTS);
+        const COLOR_ATTACHMENT_OUTPUT = api_pipeline_stage!(COLOR_ATTACHMENT_OUTPUT);
+        const COMPUTE_SHADER = api_pipeline_stage!(COMPUTE_SHADER);
+        const TRANSFER = api_pipeline_stage!(TRANSFER);
+        const BOTTOM_OF_PIPE = api_pipeline_stage!(BOTTOM_OF_PIPE);
+        const HOST = api_pipeline_stage!(HOST);
+        const ALL_GRAPHICS = api_pipeline_stage!(ALL_GRAPHICS);
+        const ALL_COMMANDS = api_pipeline_stage!(ALL_COMMANDS);
+    }
+}
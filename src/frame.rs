@@ -0,0 +1,344 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::prelude::*;
+use crate::{
+    Colorspace, CommandBuffer, CommandBufferAllocateInfo, CommandPool, CommandPoolCreateInfo,
+    CompositeAlphaMode, Context, Device, Extent2D, Fence, FenceCreateInfo, Format, Image,
+    ImageUsageFlags, PipelineStageFlags, PresentInfo, PresentMode, Queue, Semaphore,
+    SemaphoreCreateInfo, SubmitInfo, Surface, Swapchain, SwapchainCreateInfo,
+};
+
+#[derive(Debug, Clone)]
+pub struct FrameManagerCreateInfo<'a> {
+    /// Number of frames the CPU may record ahead of the GPU.
+    pub frames_in_flight: usize,
+    /// The swapchain to create. Its extent is replaced by the surface's current extent whenever
+    /// the swapchain is recreated.
+    pub swapchain: SwapchainCreateInfo<'a>,
+}
+
+/// Swapchain settings kept for recreating it.
+#[derive(Debug, Clone)]
+struct SwapchainSettings {
+    min_image_count: u32,
+    format: Format,
+    colorspace: Colorspace,
+    extent: Extent2D,
+    usage: ImageUsageFlags,
+    composite_alpha: CompositeAlphaMode,
+    present_mode: PresentMode,
+    label: Option<String>,
+}
+
+impl SwapchainSettings {
+    fn create_info(&self) -> SwapchainCreateInfo {
+        SwapchainCreateInfo {
+            min_image_count: self.min_image_count,
+            format: self.format,
+            colorspace: self.colorspace,
+            extent: self.extent,
+            usage: self.usage,
+            composite_alpha: self.composite_alpha,
+            present_mode: self.present_mode,
+            old_swapchain: None,
+            label: self.label.as_deref(),
+        }
+    }
+}
+
+/// Objects owned by one slot of the ring.
+#[derive(Debug)]
+struct FrameSlot {
+    // keeps the command buffer's pool alive
+    _command_pool: CommandPool,
+    command_buffer: CommandBuffer,
+    /// Signaled when the slot's last submission completes.
+    fence: Fence,
+    image_available: Semaphore,
+}
+
+/// The frame being recorded, handed out by [`FrameManager::begin_frame`].
+#[derive(Debug)]
+pub struct Frame<'a> {
+    /// Already begun, and ended and submitted by [`FrameManager::end_frame`].
+    pub command_buffer: &'a CommandBuffer,
+    pub image_index: u32,
+    pub image: &'a Image,
+    /// Signaled when the image is acquired; the submission waits on it.
+    pub image_available: &'a Semaphore,
+    /// Signaled when the submission completes; presentation waits on it.
+    pub render_finished: &'a Semaphore,
+    /// Signaled when the submission completes.
+    pub fence: &'a Fence,
+    /// Number of frames begun before this one.
+    pub number: u64,
+    /// Set when the swapchain was recreated since the previous frame, invalidating anything
+    /// created from its images.
+    pub swapchain_recreated: bool,
+}
+
+/// A ring of per-frame command buffers and synchronization objects around a swapchain.
+///
+/// Beginning frame N waits for frame N minus the ring size to finish on the GPU, so its
+/// objects can be reused. Out of date swapchains are recreated right away, suboptimal ones when
+/// the next frame begins.
+#[derive(Debug)]
+pub struct FrameManager {
+    context: Context,
+    device: Device,
+    surface: Surface,
+    queue: Queue,
+    swapchain: Swapchain,
+    settings: SwapchainSettings,
+    slots: Vec<FrameSlot>,
+    /// Signaled when rendering to each swapchain image completes.
+    render_finished: Vec<Semaphore>,
+    /// Number of the frame that last presented each swapchain image.
+    presented: Vec<Option<u64>>,
+    /// `render_finished` semaphores of replaced swapchains, with the number of the first frame
+    /// after the replacement. Presentation may still be waiting on them.
+    retired: Vec<(u64, Vec<Semaphore>)>,
+    frame_number: u64,
+    /// Image index of the frame being recorded.
+    current: Option<u32>,
+    recreated: bool,
+    /// Set when the last present reported the swapchain as suboptimal.
+    suboptimal: bool,
+}
+
+impl FrameManager {
+    /// Creates the swapchain and per-frame objects, presenting on the context's first queue.
+    pub fn new(
+        context: Context,
+        device: Device,
+        surface: Surface,
+        create_info: FrameManagerCreateInfo,
+    ) -> crate::Result<Self> {
+        assert!(create_info.frames_in_flight > 0);
+
+        let queue = context.queues()[0].clone();
+
+        let swapchain_info = create_info.swapchain;
+        let settings = SwapchainSettings {
+            min_image_count: swapchain_info.min_image_count,
+            format: swapchain_info.format,
+            colorspace: swapchain_info.colorspace,
+            extent: swapchain_info.extent,
+            usage: swapchain_info.usage,
+            composite_alpha: swapchain_info.composite_alpha,
+            present_mode: swapchain_info.present_mode,
+            label: swapchain_info.label.map(str::to_owned),
+        };
+
+        let swapchain = Swapchain::new(context.clone(), surface.clone(), &settings.create_info())?;
+        let render_finished = create_semaphores(&context, swapchain.images().len())?;
+        let presented = vec![None; render_finished.len()];
+
+        let slots = (0..create_info.frames_in_flight)
+            .map(|_| {
+                let command_pool = CommandPool::new(
+                    context.clone(),
+                    CommandPoolCreateInfo {
+                        queue_family_index: queue.family_index(),
+                        transient: false,
+                        reset: true,
+                        label: None,
+                    },
+                )?;
+
+                let command_buffer = CommandBuffer::allocate(
+                    command_pool.clone(),
+                    CommandBufferAllocateInfo::default(),
+                )?;
+
+                Ok(FrameSlot {
+                    _command_pool: command_pool,
+                    command_buffer,
+                    // signaled so the first wait on each slot returns immediately
+                    fence: Fence::new(
+                        context.clone(),
+                        FenceCreateInfo {
+                            signaled: true,
+                            label: None,
+                        },
+                    )?,
+                    image_available: Semaphore::new(
+                        context.clone(),
+                        SemaphoreCreateInfo::default(),
+                    )?,
+                })
+            })
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self {
+            context,
+            device,
+            surface,
+            queue,
+            swapchain,
+            settings,
+            slots,
+            render_finished,
+            presented,
+            retired: Vec::new(),
+            frame_number: 0,
+            current: None,
+            recreated: false,
+            suboptimal: false,
+        })
+    }
+
+    pub fn swapchain(&self) -> &Swapchain {
+        &self.swapchain
+    }
+
+    pub fn frames_in_flight(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Waits for the frame's slot to be free, acquires a swapchain image and begins the
+    /// command buffer.
+    pub fn begin_frame(&mut self) -> crate::Result<Frame> {
+        assert!(self.current.is_none(), "frame already begun");
+
+        let slot = (self.frame_number % self.slots.len() as u64) as usize;
+        self.slots[slot].fence.wait(None)?;
+
+        if self.suboptimal {
+            match self.recreate_swapchain() {
+                // a suboptimal swapchain still works while the surface has no area
+                Err(crate::Error::SwapchainOutOfDate) => {}
+                result => result?,
+            }
+        }
+
+        let image_available = self.slots[slot].image_available.clone();
+        let image_index = match self.acquire(&image_available) {
+            Err(crate::Error::SwapchainOutOfDate) => {
+                self.recreate_swapchain()?;
+                self.acquire(&image_available)?
+            }
+            result => result?,
+        };
+
+        // presents execute in queue order, so reacquiring an image means the presents submitted
+        // before its last one no longer wait on their semaphores
+        if let Some(presented) = self.presented[image_index as usize] {
+            self.retired.retain(|(replaced, _)| *replaced > presented);
+        }
+
+        // the fence is reset in end_frame, right before the submission that signals it
+        let slot = &self.slots[slot];
+        slot.command_buffer.begin()?;
+
+        self.current = Some(image_index);
+
+        Ok(Frame {
+            command_buffer: &slot.command_buffer,
+            image_index,
+            image: &self.swapchain.images()[image_index as usize],
+            image_available: &slot.image_available,
+            render_finished: &self.render_finished[image_index as usize],
+            fence: &slot.fence,
+            number: self.frame_number,
+            swapchain_recreated: std::mem::take(&mut self.recreated),
+        })
+    }
+
+    /// Ends and submits the frame's command buffer, then presents its image.
+    pub fn end_frame(&mut self) -> crate::Result<()> {
+        let image_index = self.current.take().expect("no frame begun");
+
+        let slot = &self.slots[(self.frame_number % self.slots.len() as u64) as usize];
+        let render_finished = &self.render_finished[image_index as usize];
+
+        slot.command_buffer.end()?;
+        slot.fence.reset()?;
+
+        self.queue.submit(
+            &SubmitInfo {
+                wait_semaphores: &[(
+                    &slot.image_available,
+                    PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+                )],
+                command_buffers: &[&slot.command_buffer],
+                signal_semaphores: &[render_finished],
+            },
+            Some(&slot.fence),
+        )?;
+
+        let present = self.queue.present(&PresentInfo {
+            wait_semaphores: &[render_finished],
+            swapchain: &self.swapchain,
+            image_index,
+        });
+
+        self.presented[image_index as usize] = Some(self.frame_number);
+        self.frame_number += 1;
+
+        match present {
+            Ok(suboptimal) => {
+                self.suboptimal = suboptimal;
+                Ok(())
+            }
+            Err(crate::Error::SwapchainOutOfDate) => self.recreate_swapchain(),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Recreates the swapchain at the surface's current extent, after waiting for the GPU to
+    /// finish with the old one.
+    ///
+    /// Fails with `Error::SwapchainOutOfDate` while the surface has no area, e.g. when its
+    /// window is minimized.
+    pub fn recreate_swapchain(&mut self) -> crate::Result<()> {
+        self.queue.wait_idle()?;
+
+        let capabilities = self.device.get_surface_capabilities(self.surface.clone())?;
+        let extent = capabilities.current_extent();
+
+        if extent.width == 0 || extent.height == 0 {
+            return Err(crate::Error::SwapchainOutOfDate);
+        }
+
+        self.settings.extent = extent;
+        self.swapchain = Swapchain::new(
+            self.context.clone(),
+            self.surface.clone(),
+            &SwapchainCreateInfo {
+                old_swapchain: Some(&self.swapchain),
+                ..self.settings.create_info()
+            },
+        )?;
+
+        // waiting for the queue doesn't cover presentation, which may still use the semaphores
+        let render_finished = create_semaphores(&self.context, self.swapchain.images().len())?;
+        let retired = std::mem::replace(&mut self.render_finished, render_finished);
+        self.retired.push((self.frame_number, retired));
+
+        self.presented = vec![None; self.render_finished.len()];
+        self.recreated = true;
+        self.suboptimal = false;
+
+        Ok(())
+    }
+
+    fn acquire(&self, semaphore: &Semaphore) -> crate::Result<u32> {
+        self.swapchain
+            .acquire_next_image(None, Some(semaphore.clone()), None)
+    }
+}
+
+impl Drop for FrameManager {
+    fn drop(&mut self) {
+        // objects still in use by the GPU can't be destroyed
+        let _ = self.queue.wait_idle();
+    }
+}
+
+fn create_semaphores(context: &Context, count: usize) -> crate::Result<Vec<Semaphore>> {
+    (0..count)
+        .map(|_| Semaphore::new(context.clone(), SemaphoreCreateInfo::default()))
+        .collect()
+}
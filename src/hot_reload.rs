@@ -0,0 +1,394 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+//! Development time shader reloading.
+//!
+//! Shader files are polled for changes with [`ShaderReloader::poll`], which rebuilds the changed
+//! shaders and every pipeline using them. Rebuilt objects are held back until
+//! [`ShaderReloader::apply`] publishes them all at once, so the caller can choose a point where
+//! no command buffer still references the old pipelines.
+
+use crate::api::traits::{GraphicsPipeline as _, Shader as _};
+use crate::{
+    BlendAttachmentState, BlendState, Context, Format, GraphicsPipeline,
+    GraphicsPipelineCreateInfo, PipelineCache, PipelineLayout, PipelineTarget, PrimitiveTopology,
+    RasterizationState, RenderPass, RenderingFormats, Shader, ShaderCode, ShaderStage,
+    ShaderStages, VertexAttributeDescription, VertexBindingDescription, VertexInputState,
+};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PipelineId(usize);
+
+/// How a watched file is turned into a shader.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderSourceKind {
+    Spirv,
+    #[cfg(feature = "naga")]
+    Glsl(crate::GlslStage),
+    #[cfg(feature = "naga")]
+    Wgsl,
+}
+
+impl ShaderSourceKind {
+    /// Picks the kind from the file extension: `spv`, and with the `naga` feature `vert`,
+    /// `frag`, `comp` and `wgsl`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let kind = match path.extension()?.to_str()? {
+            "spv" => Self::Spirv,
+            #[cfg(feature = "naga")]
+            "vert" => Self::Glsl(crate::GlslStage::Vertex),
+            #[cfg(feature = "naga")]
+            "frag" => Self::Glsl(crate::GlslStage::Fragment),
+            #[cfg(feature = "naga")]
+            "comp" => Self::Glsl(crate::GlslStage::Compute),
+            #[cfg(feature = "naga")]
+            "wgsl" => Self::Wgsl,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+}
+
+/// A shader or pipeline that failed to rebuild. The previous object stays in use.
+#[derive(Debug)]
+pub struct ReloadError {
+    pub path: PathBuf,
+    pub error: crate::Error,
+}
+
+/*
+   Watched Objects
+*/
+
+struct WatchedShader {
+    path: PathBuf,
+    kind: ShaderSourceKind,
+    modified: Option<SystemTime>,
+    current: Shader,
+    pending: Option<Shader>,
+}
+
+/// An owned copy of a `PipelineTarget`.
+enum StoredTarget {
+    RenderPass {
+        render_pass: RenderPass,
+        subpass: u32,
+    },
+    Dynamic {
+        color: Vec<Format>,
+        depth: Option<Format>,
+        stencil: Option<Format>,
+    },
+}
+
+impl StoredTarget {
+    fn new(target: PipelineTarget) -> Self {
+        match target {
+            PipelineTarget::RenderPass {
+                render_pass,
+                subpass,
+            } => Self::RenderPass {
+                render_pass,
+                subpass,
+            },
+            PipelineTarget::Dynamic(formats) => Self::Dynamic {
+                color: formats.color.to_vec(),
+                depth: formats.depth,
+                stencil: formats.stencil,
+            },
+        }
+    }
+
+    fn target(&self) -> PipelineTarget<'_> {
+        match self {
+            Self::RenderPass {
+                render_pass,
+                subpass,
+            } => PipelineTarget::RenderPass {
+                render_pass: render_pass.clone(),
+                subpass: *subpass,
+            },
+            Self::Dynamic {
+                color,
+                depth,
+                stencil,
+            } => PipelineTarget::Dynamic(RenderingFormats {
+                color,
+                depth: *depth,
+                stencil: *stencil,
+            }),
+        }
+    }
+}
+
+/// An owned copy of a `GraphicsPipelineCreateInfo`.
+struct StoredPipeline {
+    shaders: ShaderStages,
+    vertex_bindings: Vec<VertexBindingDescription>,
+    vertex_attributes: Vec<VertexAttributeDescription>,
+    topology: PrimitiveTopology,
+    rasterization: RasterizationState,
+    blend_attachments: Vec<BlendAttachmentState>,
+    layout: PipelineLayout,
+    target: StoredTarget,
+    cache: Option<PipelineCache>,
+    label: Option<String>,
+    current: GraphicsPipeline,
+    pending: Option<(ShaderStages, GraphicsPipeline)>,
+}
+
+impl StoredPipeline {
+    fn create_info(&self, shaders: ShaderStages) -> GraphicsPipelineCreateInfo<'_> {
+        GraphicsPipelineCreateInfo {
+            shaders,
+            vertex_input: VertexInputState {
+                bindings: &self.vertex_bindings,
+                attributes: &self.vertex_attributes,
+            },
+            topology: self.topology,
+            rasterization: self.rasterization.clone(),
+            blend: BlendState {
+                attachments: &self.blend_attachments,
+            },
+            layout: self.layout.clone(),
+            target: self.target.target(),
+            cache: self.cache.clone(),
+            label: self.label.as_deref(),
+        }
+    }
+
+    /// The stages of the newest pipeline, published or not.
+    fn latest_shaders(&self) -> &ShaderStages {
+        self.pending
+            .as_ref()
+            .map(|(shaders, _)| shaders)
+            .unwrap_or(&self.shaders)
+    }
+}
+
+fn stages_mut(stages: &mut ShaderStages) -> [&mut Option<ShaderStage>; 5] {
+    [
+        &mut stages.vertex,
+        &mut stages.tess_ctrl,
+        &mut stages.tess_eval,
+        &mut stages.geometry,
+        &mut stages.fragment,
+    ]
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/*
+   Shader Reloader
+*/
+
+pub struct ShaderReloader {
+    context: Context,
+    shaders: Vec<WatchedShader>,
+    pipelines: Vec<StoredPipeline>,
+}
+
+impl ShaderReloader {
+    pub fn new(context: Context) -> Self {
+        Self {
+            context,
+            shaders: Vec::new(),
+            pipelines: Vec::new(),
+        }
+    }
+
+    /// Loads a shader and watches its file for changes, the kind being taken from the extension.
+    pub fn watch_shader(&mut self, path: impl AsRef<Path>) -> crate::Result<Shader> {
+        let path = path.as_ref();
+        let kind = ShaderSourceKind::from_path(path).ok_or(crate::Error::Unsupported(
+            "unrecognized shader file extension",
+        ))?;
+
+        self.watch_shader_with_kind(path, kind)
+    }
+
+    pub fn watch_shader_with_kind(
+        &mut self,
+        path: impl AsRef<Path>,
+        kind: ShaderSourceKind,
+    ) -> crate::Result<Shader> {
+        let path = path.as_ref().to_path_buf();
+        let modified = modified(&path);
+        let shader = self.load_shader(&path, kind)?;
+
+        self.shaders.push(WatchedShader {
+            path,
+            kind,
+            modified,
+            current: shader.clone(),
+            pending: None,
+        });
+
+        Ok(shader)
+    }
+
+    /// Creates a pipeline that is rebuilt whenever one of its watched shaders changes.
+    pub fn create_pipeline(
+        &mut self,
+        create_info: GraphicsPipelineCreateInfo,
+    ) -> crate::Result<PipelineId> {
+        let current = GraphicsPipeline::new(self.context.clone(), create_info.clone())?;
+
+        self.pipelines.push(StoredPipeline {
+            shaders: create_info.shaders,
+            vertex_bindings: create_info.vertex_input.bindings.to_vec(),
+            vertex_attributes: create_info.vertex_input.attributes.to_vec(),
+            topology: create_info.topology,
+            rasterization: create_info.rasterization,
+            blend_attachments: create_info.blend.attachments.to_vec(),
+            layout: create_info.layout,
+            target: StoredTarget::new(create_info.target),
+            cache: create_info.cache,
+            label: create_info.label.map(str::to_string),
+            current,
+            pending: None,
+        });
+
+        Ok(PipelineId(self.pipelines.len() - 1))
+    }
+
+    /// The published pipeline.
+    pub fn pipeline(&self, id: PipelineId) -> &GraphicsPipeline {
+        &self.pipelines[id.0].current
+    }
+
+    /// Whether rebuilt objects are waiting for [`Self::apply`].
+    pub fn has_pending(&self) -> bool {
+        self.shaders.iter().any(|shader| shader.pending.is_some())
+            || self
+                .pipelines
+                .iter()
+                .any(|pipeline| pipeline.pending.is_some())
+    }
+
+    /// Rebuilds shaders whose files changed since the last poll, and the pipelines using them.
+    ///
+    /// Nothing is published until [`Self::apply`] is called.
+    pub fn poll(&mut self) -> Vec<ReloadError> {
+        let mut errors = Vec::new();
+
+        for index in 0..self.shaders.len() {
+            let watched = &mut self.shaders[index];
+            let modified = modified(&watched.path);
+
+            if modified == watched.modified {
+                continue;
+            }
+            // a failed build waits for the next save rather than retrying every poll
+            watched.modified = modified;
+
+            let (path, kind) = (watched.path.clone(), watched.kind);
+
+            match self.load_shader(&path, kind) {
+                Ok(shader) => {
+                    log::info!("Reloaded shader {}", path.display());
+                    errors.extend(self.rebuild_pipelines(index, shader));
+                }
+                Err(error) => errors.push(ReloadError { path, error }),
+            }
+        }
+
+        for error in &errors {
+            log::error!("Failed to reload {}: {}", error.path.display(), error.error);
+        }
+
+        errors
+    }
+
+    /// Publishes every rebuilt shader and pipeline at once.
+    pub fn apply(&mut self) {
+        for shader in &mut self.shaders {
+            if let Some(pending) = shader.pending.take() {
+                shader.current = pending;
+            }
+        }
+
+        for pipeline in &mut self.pipelines {
+            if let Some((shaders, pending)) = pipeline.pending.take() {
+                pipeline.shaders = shaders;
+                pipeline.current = pending;
+            }
+        }
+    }
+
+    fn load_shader(&self, path: &Path, kind: ShaderSourceKind) -> crate::Result<Shader> {
+        let context = self.context.clone();
+
+        match kind {
+            ShaderSourceKind::Spirv => {
+                let bytes = std::fs::read(path)?;
+                Shader::from_code(context, ShaderCode::Dynamic(&bytes))
+            }
+            #[cfg(feature = "naga")]
+            ShaderSourceKind::Glsl(stage) => {
+                let source = std::fs::read_to_string(path)?;
+                let code = ShaderCode::Glsl {
+                    source: &source,
+                    stage,
+                    defines: &[],
+                };
+                Shader::from_code(context, code)
+            }
+            #[cfg(feature = "naga")]
+            ShaderSourceKind::Wgsl => {
+                let source = std::fs::read_to_string(path)?;
+                Shader::from_code(context, ShaderCode::Wgsl(&source))
+            }
+        }
+    }
+
+    /// Rebuilds the pipelines using the previous version of a shader. The shader is only kept
+    /// if every affected pipeline builds.
+    fn rebuild_pipelines(&mut self, shader_index: usize, shader: Shader) -> Vec<ReloadError> {
+        let watched = &self.shaders[shader_index];
+        let previous = watched.pending.clone().unwrap_or(watched.current.clone());
+
+        let mut rebuilt = Vec::new();
+
+        for (index, pipeline) in self.pipelines.iter().enumerate() {
+            let mut shaders = pipeline.latest_shaders().clone();
+            let mut uses_shader = false;
+
+            for stage in stages_mut(&mut shaders).into_iter().flatten() {
+                if stage.shader == previous {
+                    stage.shader = shader.clone();
+                    uses_shader = true;
+                }
+            }
+
+            if !uses_shader {
+                continue;
+            }
+
+            let create_info = pipeline.create_info(shaders.clone());
+            match GraphicsPipeline::new(self.context.clone(), create_info) {
+                Ok(new_pipeline) => rebuilt.push((index, shaders, new_pipeline)),
+                Err(error) => {
+                    return vec![ReloadError {
+                        path: watched.path.clone(),
+                        error,
+                    }]
+                }
+            }
+        }
+
+        for (index, shaders, new_pipeline) in rebuilt {
+            self.pipelines[index].pending = Some((shaders, new_pipeline));
+        }
+        self.shaders[shader_index].pending = Some(shader);
+
+        Vec::new()
+    }
+}
@@ -4,8 +4,17 @@
 // todo - remove
 #![allow(warnings)]
 
+pub mod adapter;
 pub mod api;
 pub mod convert;
+pub mod frame;
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
+pub mod readback;
+pub mod render_graph;
+pub mod spirv;
+pub mod texture;
+pub mod upload;
 mod util;
 
 pub mod prelude {
@@ -36,10 +45,13 @@ pub type CommandPool = get_api_type!(CommandPool);
 pub type CommandBuffer = get_api_type!(CommandBuffer);
 
 pub type Fence = get_api_type!(Fence);
+pub type Semaphore = get_api_type!(Semaphore);
 
 // pub type CommandBufferRecordContext = <<api::Api as GraphicsApi>::CommandBuffer as api::traits::CommandBuffer<api::Api>>::RecordContext;
 // pub type RenderPassRecordContext = <<<api::Api as GraphicsApi>::CommandBuffer as api::traits::CommandBuffer<api::Api>>::RecordContext as api::traits::CommandBufferRecordContext>::RenderPassRecordContext;
 //
+pub type Buffer = get_api_type!(Buffer);
+
 pub type Swapchain = get_api_type!(Swapchain);
 pub type Image = get_api_type!(Image);
 pub type ImageView = get_api_type!(ImageView);
@@ -48,17 +60,58 @@ pub type RenderPass = get_api_type!(RenderPass);
 pub type Framebuffer = get_api_type!(Framebuffer);
 
 pub type PipelineLayout = <api::Api as GraphicsApi>::PipelineLayout;
+pub type PipelineCache = <api::Api as GraphicsApi>::PipelineCache;
+
+#[cfg(feature = "naga")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GlslStage {
+    Vertex,
+    Fragment,
+    Compute,
+}
 
 pub enum ShaderCode<'a> {
     Static(&'static [u8]),
     Dynamic(&'a [u8]),
+    /// GLSL source compiled to SPIR-V when the shader is created.
+    #[cfg(feature = "naga")]
+    Glsl {
+        source: &'a str,
+        stage: GlslStage,
+        /// Preprocessor `#define`s as name / value pairs.
+        defines: &'a [(&'a str, &'a str)],
+    },
+    /// WGSL source compiled to SPIR-V when the shader is created.
+    #[cfg(feature = "naga")]
+    Wgsl(&'a str),
 }
 
-impl<'a> Into<&'a [u8]> for ShaderCode<'a> {
-    fn into(self) -> &'a [u8] {
+impl<'a> ShaderCode<'a> {
+    /// The precompiled code, or `None` for source variants.
+    pub fn bytes(&self) -> Option<&'a [u8]> {
         match self {
-            ShaderCode::Static(code) => code,
-            ShaderCode::Dynamic(code) => code,
+            ShaderCode::Static(code) => Some(code),
+            ShaderCode::Dynamic(code) => Some(code),
+            #[cfg(feature = "naga")]
+            _ => None,
+        }
+    }
+
+    /// The code as validated SPIR-V words in host byte order, compiling source variants.
+    ///
+    /// Borrows the code when it is already aligned and in host order, and copies it otherwise.
+    pub fn spirv(&self) -> crate::Result<std::borrow::Cow<'a, [u32]>> {
+        match self {
+            ShaderCode::Static(code) => Ok(spirv::words_from_code(code)?),
+            ShaderCode::Dynamic(code) => Ok(spirv::words_from_code(code)?),
+            #[cfg(feature = "naga")]
+            ShaderCode::Glsl {
+                source,
+                stage,
+                defines,
+            } => Ok(spirv::compile_glsl(source, *stage, defines)?.into()),
+            #[cfg(feature = "naga")]
+            ShaderCode::Wgsl(source) => Ok(spirv::compile_wgsl(source)?.into()),
         }
     }
 }
@@ -68,7 +121,11 @@ pub type Shader = get_api_type!(Shader);
 pub type GraphicsPipeline = <api::Api as GraphicsApi>::GraphicsPipeline;
 
 #[derive(Debug, Default)]
-pub struct RootCreateInfo {}
+pub struct RootCreateInfo {
+    /// Receives every message reported by the validation layer.
+    pub debug_callback: Option<DebugCallback>,
+    pub validation_error_policy: ValidationErrorPolicy,
+}
 
 #[derive(Debug)]
 #[cfg(target_os = "windows")]
@@ -87,42 +144,61 @@ impl SurfaceCreateInfo {
 }
 
 #[derive(Debug, Default)]
-pub struct ContextCreateInfo {}
+pub struct ContextCreateInfo<'a> {
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
+}
 
 #[derive(Debug)]
-pub struct CommandPoolCreateInfo {
+pub struct CommandPoolCreateInfo<'a> {
+    /// Family of the queue the pool's command buffers are submitted to.
+    pub queue_family_index: u32,
     pub transient: bool,
     pub reset: bool,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct CommandBufferAllocateInfo {
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CommandBufferAllocateInfo<'a> {
     // pub level: CommandBufferLevel,
     // pub count: u32,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
 }
 
-#[derive(Debug)]
-pub struct RenderPassBeginInfo {
+#[derive(Debug, Clone)]
+pub struct RenderPassBeginInfo<'a> {
     // pub render_pass: RenderPass,
-    // pub framebuffer: Framebuffer,
-    // pub render_area: Rect2D,
-    // pub clear_values: Vec<ClearValue>,
+    pub framebuffer: &'a Framebuffer,
+    pub render_area: Rect2D,
+    /// Indexed by attachment, only read for attachments cleared on load.
+    pub clear_values: &'a [ClearValue],
 }
 
 #[derive(Debug, Clone)]
-pub struct SwapchainCreateInfo {
+pub struct SwapchainCreateInfo<'a> {
     pub min_image_count: u32,
     pub format: Format,
     pub colorspace: Colorspace,
     pub extent: Extent2D,
+    /// Usage of the images besides being color attachments, e.g. `TRANSFER_SRC` to read them
+    /// back.
+    pub usage: ImageUsageFlags,
     pub composite_alpha: CompositeAlphaMode,
     pub present_mode: PresentMode,
+    /// A swapchain for the same surface being replaced, which is retired by the new one.
+    pub old_swapchain: Option<&'a Swapchain>,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
 }
 
 #[derive(Debug, Clone)]
-pub struct ImageViewCreateInfo {
+pub struct ImageViewCreateInfo<'a> {
     // pub view_type: ImageViewType,
     pub format: Format,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
     // pub components: ComponentMapping,
     // pub subresource_range: ImageSubresourceRange,
 }
@@ -135,32 +211,55 @@ pub struct ImageViewCreateInfo {
 pub struct RenderPassCreateInfo<'a> {
     pub attachments: &'a [AttachmentDescription],
     pub subpasses: &'a [SubpassDescription<'a>],
+    pub dependencies: &'a [SubpassDependency],
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
 }
 
 #[derive(Debug, Clone)]
 pub struct AttachmentDescription {
     pub format: Format,
     // pub samples: SampleCountFlags,
-    // pub load_op: AttachmentLoadOp,
-    // pub store_op: AttachmentStoreOp,
-    // pub stencil_load_op: AttachmentLoadOp,
-    // pub stencil_store_op: AttachmentStoreOp,
-    // pub initial_layout: ImageLayout,
-    // pub final_layout: ImageLayout,
+    pub load_op: AttachmentLoadOp,
+    pub store_op: AttachmentStoreOp,
+    pub stencil_load_op: AttachmentLoadOp,
+    pub stencil_store_op: AttachmentStoreOp,
+    /// The layout the attachment is in when the render pass begins. `ImageLayout::Undefined`
+    /// discards its contents, so it can't be combined with `AttachmentLoadOp::Load`.
+    pub initial_layout: ImageLayout,
+    /// The layout the attachment is moved to when the render pass ends.
+    pub final_layout: ImageLayout,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct SubpassDescription<'a> {
     pub input_attachments: &'a [AttachmentReference],
     pub color_attachments: &'a [AttachmentReference],
-    // pub resolve_attachments: Vec<AttachmentReference>,
-    // pub depth_stencil_attachment: Option<AttachmentReference>,
-    // pub preserve_attachments: Vec<u32>,
+    pub depth_stencil_attachment: Option<AttachmentReference>,
+    /// Attachments the subpass doesn't use whose contents must survive it.
+    pub preserve_attachments: &'a [u32],
+}
+
+/// An execution and memory dependency between two subpasses.
+///
+/// A `None` subpass refers to commands outside the render pass.
+#[derive(Debug, Default, Clone)]
+pub struct SubpassDependency {
+    pub src_subpass: Option<u32>,
+    pub dst_subpass: Option<u32>,
+    pub src_stages: PipelineStageFlags,
+    pub src_access: AccessFlags,
+    pub dst_stages: PipelineStageFlags,
+    pub dst_access: AccessFlags,
+    /// Only orders accesses to the same framebuffer region, as needed for input attachments.
+    pub by_region: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct AttachmentReference {
     pub attachment: u32,
+    /// The layout the attachment is in during the subpass.
+    pub layout: ImageLayout,
 }
 
 #[derive(Debug, Clone)]
@@ -168,18 +267,126 @@ pub struct FramebufferCreateInfo<'a> {
     pub render_pass: RenderPass,
     pub extent: Extent2D,
     pub attachments: &'a [ImageView],
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DescriptorSetLayoutBinding {
+    pub binding: u32,
+    pub descriptor_type: DescriptorType,
+    pub count: u32,
+    pub stages: ShaderStageFlags,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DescriptorSetLayoutDescription {
+    pub bindings: Vec<DescriptorSetLayoutBinding>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PushConstantRange {
+    pub stages: ShaderStageFlags,
+    pub offset: u32,
+    pub size: u32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PipelineLayoutCreateInfo<'a> {
+    /// One description per descriptor set, indexed by set number.
+    pub set_layouts: &'a [DescriptorSetLayoutDescription],
+    pub push_constant_ranges: &'a [PushConstantRange],
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PipelineCacheCreateInfo<'a> {
+    /// Data previously returned by `PipelineCache::data`.
+    ///
+    /// Data written by a different device or driver is discarded.
+    pub initial_data: Option<&'a [u8]>,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SpecializationValue {
+    Bool(bool),
+    I32(i32),
+    U32(u32),
+    F32(f32),
+}
+
+impl SpecializationValue {
+    /// The value as laid out in specialization data, booleans being 32 bits wide.
+    pub fn to_ne_bytes(self) -> [u8; 4] {
+        match self {
+            SpecializationValue::Bool(value) => (value as u32).to_ne_bytes(),
+            SpecializationValue::I32(value) => value.to_ne_bytes(),
+            SpecializationValue::U32(value) => value.to_ne_bytes(),
+            SpecializationValue::F32(value) => value.to_ne_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SpecializationConstant {
+    pub id: u32,
+    pub value: SpecializationValue,
 }
 
 #[derive(Debug, Clone)]
-pub struct PipelineLayoutCreateInfo {}
+pub struct ShaderStage {
+    pub shader: Shader,
+    pub entry_point: String,
+    pub specialization_constants: Vec<SpecializationConstant>,
+}
+
+impl ShaderStage {
+    pub fn new(shader: Shader) -> Self {
+        Self {
+            shader,
+            entry_point: "main".to_string(),
+            specialization_constants: Vec::new(),
+        }
+    }
+}
+
+impl From<Shader> for ShaderStage {
+    fn from(shader: Shader) -> Self {
+        Self::new(shader)
+    }
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct ShaderStages {
-    pub vertex: Option<Shader>,
-    pub tess_ctrl: Option<Shader>,
-    pub tess_eval: Option<Shader>,
-    pub geometry: Option<Shader>,
-    pub fragment: Option<Shader>,
+    pub vertex: Option<ShaderStage>,
+    pub tess_ctrl: Option<ShaderStage>,
+    pub tess_eval: Option<ShaderStage>,
+    pub geometry: Option<ShaderStage>,
+    pub fragment: Option<ShaderStage>,
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct VertexBindingDescription {
+    pub binding: u32,
+    pub stride: u32,
+    pub input_rate: VertexInputRate,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct VertexAttributeDescription {
+    pub location: u32,
+    pub binding: u32,
+    pub format: Format,
+    pub offset: u32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct VertexInputState<'a> {
+    pub bindings: &'a [VertexBindingDescription],
+    pub attributes: &'a [VertexAttributeDescription],
 }
 
 #[derive(Debug, Default, Clone)]
@@ -206,24 +413,214 @@ pub struct BlendState<'a> {
     pub attachments: &'a [BlendAttachmentState],
 }
 
+/// The attachment formats of a pipeline used with `CommandBuffer::begin_rendering`.
+#[derive(Debug, Default, Clone)]
+pub struct RenderingFormats<'a> {
+    pub color: &'a [Format],
+    pub depth: Option<Format>,
+    pub stencil: Option<Format>,
+}
+
+/// What a graphics pipeline renders into.
+#[derive(Debug, Clone)]
+pub enum PipelineTarget<'a> {
+    RenderPass {
+        render_pass: RenderPass,
+        subpass: u32,
+    },
+    /// Rendering begun with `CommandBuffer::begin_rendering`, which needs the device to support
+    /// dynamic rendering.
+    Dynamic(RenderingFormats<'a>),
+}
+
 #[derive(Debug, Clone)]
 pub struct GraphicsPipelineCreateInfo<'a> {
     pub shaders: ShaderStages,
+    pub vertex_input: VertexInputState<'a>,
     pub topology: PrimitiveTopology,
     pub rasterization: RasterizationState,
     pub blend: BlendState<'a>,
     pub layout: PipelineLayout,
-    pub render_pass: RenderPass,
-    pub subpass: u32,
+    pub target: PipelineTarget<'a>,
+    pub cache: Option<PipelineCache>,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
 }
 
 #[derive(Debug, Clone)]
-pub struct FenceCreateInfo {
+pub struct FenceCreateInfo<'a> {
     pub signaled: bool,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SemaphoreCreateInfo<'a> {
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BufferCreateInfo<'a> {
+    pub size: u64,
+    pub usage: BufferUsageFlags,
+    pub location: MemoryLocation,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
 }
 
 #[derive(Debug, Clone)]
-pub struct SemaphoreCreateInfo {}
+pub struct ImageCreateInfo<'a> {
+    pub format: Format,
+    pub extent: Extent2D,
+    pub mip_levels: u32,
+    pub array_layers: u32,
+    /// Whether the layers can be viewed as cube faces, which requires a multiple of 6 layers.
+    pub cube: bool,
+    pub usage: ImageUsageFlags,
+    pub location: MemoryLocation,
+    /// Debug name attached to the created object.
+    pub label: Option<&'a str>,
+}
+
+/*
+   Copies
+*/
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct BufferCopy {
+    pub src_offset: u64,
+    pub dst_offset: u64,
+    pub size: u64,
+}
+
+/// Copies tightly packed texels between a buffer and a region of one image subresource.
+#[derive(Debug, Copy, Clone)]
+pub struct BufferImageCopy {
+    pub buffer_offset: u64,
+    pub aspect: ImageAspectFlags,
+    pub mip_level: u32,
+    pub base_array_layer: u32,
+    pub layer_count: u32,
+    pub image_offset: Offset2D,
+    pub image_extent: Extent2D,
+}
+
+/*
+   Barriers
+*/
+
+#[derive(Debug, Copy, Clone)]
+pub struct ImageSubresourceRange {
+    pub aspect: ImageAspectFlags,
+    pub base_mip_level: u32,
+    pub level_count: u32,
+    pub base_array_layer: u32,
+    pub layer_count: u32,
+}
+
+impl Default for ImageSubresourceRange {
+    /// The first mip level and array layer of a color image.
+    fn default() -> Self {
+        Self {
+            aspect: ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        }
+    }
+}
+
+/// Orders all memory accesses in the source scope before those in the destination scope.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct MemoryBarrier {
+    pub src_stages: PipelineStageFlags,
+    pub src_access: AccessFlags,
+    pub dst_stages: PipelineStageFlags,
+    pub dst_access: AccessFlags,
+}
+
+#[derive(Debug, Clone)]
+pub struct BufferBarrier<'a> {
+    pub buffer: &'a Buffer,
+    pub src_stages: PipelineStageFlags,
+    pub src_access: AccessFlags,
+    pub dst_stages: PipelineStageFlags,
+    pub dst_access: AccessFlags,
+    pub offset: u64,
+    /// The number of bytes covered, or `None` for the rest of the buffer.
+    pub size: Option<u64>,
+    /// Source and destination queue families when transferring ownership between them.
+    pub queue_transfer: Option<(u32, u32)>,
+}
+
+/// A memory dependency on an image, optionally moving it to a new layout.
+#[derive(Debug, Clone)]
+pub struct ImageBarrier<'a> {
+    pub image: &'a Image,
+    pub src_stages: PipelineStageFlags,
+    pub src_access: AccessFlags,
+    pub dst_stages: PipelineStageFlags,
+    pub dst_access: AccessFlags,
+    /// `ImageLayout::Undefined` discards the image's contents.
+    pub old_layout: ImageLayout,
+    pub new_layout: ImageLayout,
+    pub subresource_range: ImageSubresourceRange,
+    /// Source and destination queue families when transferring ownership between them.
+    pub queue_transfer: Option<(u32, u32)>,
+}
+
+/// Barriers recorded together by `CommandBuffer::pipeline_barrier`.
+#[derive(Debug, Default, Clone)]
+pub struct DependencyInfo<'a> {
+    pub memory_barriers: &'a [MemoryBarrier],
+    pub buffer_barriers: &'a [BufferBarrier<'a>],
+    pub image_barriers: &'a [ImageBarrier<'a>],
+}
+
+/*
+   Dynamic Rendering
+*/
+
+#[derive(Debug, Clone)]
+pub struct RenderingAttachment<'a> {
+    pub view: &'a ImageView,
+    /// The layout the view is in while rendering.
+    pub layout: ImageLayout,
+    pub load_op: AttachmentLoadOp,
+    pub store_op: AttachmentStoreOp,
+    /// Used when `load_op` is `AttachmentLoadOp::Clear`.
+    pub clear_value: ClearValue,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderingInfo<'a> {
+    pub render_area: Rect2D,
+    pub color_attachments: &'a [RenderingAttachment<'a>],
+    pub depth_attachment: Option<RenderingAttachment<'a>>,
+    pub stencil_attachment: Option<RenderingAttachment<'a>>,
+}
+
+/*
+   Submission
+*/
+
+#[derive(Debug, Default, Clone)]
+pub struct SubmitInfo<'a> {
+    /// Semaphores to wait on, each blocking the given stages of the submitted work.
+    pub wait_semaphores: &'a [(&'a Semaphore, PipelineStageFlags)],
+    pub command_buffers: &'a [&'a CommandBuffer],
+    /// Semaphores signaled once the command buffers complete.
+    pub signal_semaphores: &'a [&'a Semaphore],
+}
+
+#[derive(Debug, Clone)]
+pub struct PresentInfo<'a> {
+    pub wait_semaphores: &'a [&'a Semaphore],
+    pub swapchain: &'a Swapchain,
+    pub image_index: u32,
+}
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -236,4 +633,50 @@ pub enum Error {
     #[cfg(target_os = "windows")]
     #[error("Windows error: {0}")]
     WindowsError(#[from] windows::core::Error),
+
+    #[error("SPIR-V error: {0}")]
+    SpirvError(#[from] spirv::SpirvError),
+
+    #[error("Shader interface mismatch: {0}")]
+    LinkError(#[from] spirv::LinkError),
+
+    #[cfg(feature = "naga")]
+    #[error("Shader compile error: {0}")]
+    ShaderCompileError(#[from] spirv::ShaderCompileError),
+
+    #[error("Render graph error: {0}")]
+    RenderGraphError(#[from] render_graph::RenderGraphError),
+
+    #[error("Texture error: {0}")]
+    TextureError(#[from] texture::TextureError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[cfg(feature = "png")]
+    #[error("PNG error: {0}")]
+    PngError(#[from] png::EncodingError),
+
+    #[error("Unsupported: {0}")]
+    Unsupported(&'static str),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(&'static str),
+
+    #[error("Missing device extension: {0}")]
+    MissingExtension(&'static str),
+
+    #[error("Unknown {kind} value: {value}")]
+    UnknownValue { kind: &'static str, value: i64 },
+
+    /// The swapchain no longer matches its surface and must be recreated.
+    #[error("Swapchain is out of date")]
+    SwapchainOutOfDate,
+
+    #[error("Timed out")]
+    Timeout,
+
+    #[cfg(feature = "vulkan")]
+    #[error("Vulkan call failed with result {0}")]
+    VulkanResult(i32),
 }
@@ -0,0 +1,396 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::prelude::*;
+use crate::{
+    AccessFlags, Buffer, BufferBarrier, BufferCreateInfo, BufferImageCopy, BufferUsageFlags,
+    CommandBuffer, CommandBufferAllocateInfo, CommandPool, CommandPoolCreateInfo, Context,
+    DependencyInfo, Extent2D, Fence, FenceCreateInfo, Format, Image, ImageAspectFlags,
+    ImageBarrier, ImageLayout, ImageSubresourceRange, MemoryLocation, Offset2D, PipelineStageFlags,
+    Queue, SubmitInfo,
+};
+
+/// The subresource of an image to read back.
+#[derive(Debug, Clone)]
+pub struct ReadbackInfo<'a> {
+    /// Must have been created with `ImageUsageFlags::TRANSFER_SRC`, which swapchains take in
+    /// `SwapchainCreateInfo::usage`.
+    pub image: &'a Image,
+    /// The layout the image is in, which it's returned to afterwards. Images in
+    /// `ImageLayout::Undefined` are left in `ImageLayout::TransferSrc`.
+    pub layout: ImageLayout,
+    pub mip_level: u32,
+    pub array_layer: u32,
+}
+
+/// Texels read back from an image.
+#[derive(Debug, Clone)]
+pub struct ImageData {
+    pub format: Format,
+    pub extent: Extent2D,
+    /// Bytes between the starts of consecutive rows of texel blocks in `data`.
+    pub row_pitch: u64,
+    pub data: Vec<u8>,
+}
+
+/// Copies a color image subresource into host memory, blocking until the copy completes.
+///
+/// The image must not be in use by the device, e.g. a swapchain image must be read after the
+/// frame rendering it has finished.
+pub fn read_image(
+    context: &Context,
+    queue: &Queue,
+    info: &ReadbackInfo,
+) -> crate::Result<ImageData> {
+    let image = info.image;
+    let format = image.format();
+
+    if format.has_depth() || format.has_stencil() || format.block_size() == 0 {
+        return Err(crate::Error::Unsupported(
+            "only color images can be read back",
+        ));
+    }
+
+    let extent = Extent2D {
+        width: (image.extent().width >> info.mip_level).max(1),
+        height: (image.extent().height >> info.mip_level).max(1),
+    };
+
+    let block = format.block_extent();
+    let row_pitch = extent.width.div_ceil(block.width) as u64 * format.block_size() as u64;
+    let size = format.required_bytes(extent, 1, 1);
+
+    let buffer = Buffer::new(
+        context.clone(),
+        BufferCreateInfo {
+            size,
+            usage: BufferUsageFlags::TRANSFER_DST,
+            location: MemoryLocation::Readback,
+            label: Some("image readback"),
+        },
+    )?;
+
+    let command_pool = CommandPool::new(
+        context.clone(),
+        CommandPoolCreateInfo {
+            queue_family_index: queue.family_index(),
+            transient: true,
+            reset: false,
+            label: None,
+        },
+    )?;
+
+    let command_buffer =
+        CommandBuffer::allocate(command_pool.clone(), CommandBufferAllocateInfo::default())?;
+
+    let subresource_range = ImageSubresourceRange {
+        aspect: ImageAspectFlags::COLOR,
+        base_mip_level: info.mip_level,
+        level_count: 1,
+        base_array_layer: info.array_layer,
+        layer_count: 1,
+    };
+
+    command_buffer.begin()?;
+
+    command_buffer.pipeline_barrier(&DependencyInfo {
+        image_barriers: &[ImageBarrier {
+            image,
+            src_stages: PipelineStageFlags::ALL_COMMANDS,
+            src_access: AccessFlags::MEMORY_WRITE,
+            dst_stages: PipelineStageFlags::TRANSFER,
+            dst_access: AccessFlags::TRANSFER_READ,
+            old_layout: info.layout,
+            new_layout: ImageLayout::TransferSrc,
+            subresource_range,
+            queue_transfer: None,
+        }],
+        ..Default::default()
+    })?;
+
+    command_buffer.copy_image_to_buffer(
+        image,
+        ImageLayout::TransferSrc,
+        &buffer,
+        &[BufferImageCopy {
+            buffer_offset: 0,
+            aspect: ImageAspectFlags::COLOR,
+            mip_level: info.mip_level,
+            base_array_layer: info.array_layer,
+            layer_count: 1,
+            image_offset: Offset2D { x: 0, y: 0 },
+            image_extent: extent,
+        }],
+    )?;
+
+    command_buffer.pipeline_barrier(&DependencyInfo {
+        buffer_barriers: &[BufferBarrier {
+            buffer: &buffer,
+            src_stages: PipelineStageFlags::TRANSFER,
+            src_access: AccessFlags::TRANSFER_WRITE,
+            dst_stages: PipelineStageFlags::HOST,
+            dst_access: AccessFlags::HOST_READ,
+            offset: 0,
+            size: None,
+            queue_transfer: None,
+        }],
+        image_barriers: &[ImageBarrier {
+            image,
+            src_stages: PipelineStageFlags::TRANSFER,
+            src_access: AccessFlags::empty(),
+            dst_stages: PipelineStageFlags::BOTTOM_OF_PIPE,
+            dst_access: AccessFlags::empty(),
+            old_layout: ImageLayout::TransferSrc,
+            // an undefined layout can't be returned to
+            new_layout: match info.layout {
+                ImageLayout::Undefined => ImageLayout::TransferSrc,
+                layout => layout,
+            },
+            subresource_range,
+            queue_transfer: None,
+        }],
+        ..Default::default()
+    })?;
+
+    command_buffer.end()?;
+
+    let fence = Fence::new(
+        context.clone(),
+        FenceCreateInfo {
+            signaled: false,
+            label: None,
+        },
+    )?;
+
+    queue.submit(
+        &SubmitInfo {
+            command_buffers: &[&command_buffer],
+            ..Default::default()
+        },
+        Some(&fence),
+    )?;
+    fence.wait(None)?;
+
+    let mut data = vec![0; size as usize];
+    buffer.read(0, &mut data)?;
+
+    Ok(ImageData {
+        format,
+        extent,
+        row_pitch,
+        data,
+    })
+}
+
+impl ImageData {
+    /// Number of rows of texel blocks, which for uncompressed formats is the height.
+    pub fn rows(&self) -> u32 {
+        self.extent
+            .height
+            .div_ceil(self.format.block_extent().height)
+    }
+
+    /// The bytes of row `y` of texel blocks, which for uncompressed formats is a row of texels.
+    ///
+    /// Panics unless `y` is less than [`Self::rows`].
+    pub fn row(&self, y: u32) -> &[u8] {
+        assert!(y < self.rows(), "row {y} is out of bounds");
+
+        let start = (y as u64 * self.row_pitch) as usize;
+        &self.data[start..start + self.row_pitch as usize]
+    }
+
+    /// Converts the texels to tightly packed 8 bit RGBA.
+    ///
+    /// Supports the RGBA8, BGRA8, RGBA16F and RGB10A2 formats, including sRGB variants which
+    /// are kept sRGB encoded. Float formats are treated as linear and sRGB encoded.
+    pub fn to_rgba8(&self) -> crate::Result<Vec<u8>> {
+        let convert: fn(&[u8], &mut [u8]) = match self.format {
+            Format::R8G8B8A8_UNORM | Format::R8G8B8A8_UNORM_SRGB => |src, dst| {
+                dst.copy_from_slice(src);
+            },
+            Format::B8G8R8A8_UNORM | Format::B8G8R8A8_UNORM_SRGB => |src, dst| {
+                dst.copy_from_slice(&[src[2], src[1], src[0], src[3]]);
+            },
+            Format::R16G16B16A16_SFLOAT => |src, dst| {
+                for (i, channel) in dst.iter_mut().enumerate() {
+                    let value = f16_to_f32(u16::from_le_bytes([src[i * 2], src[i * 2 + 1]]));
+
+                    *channel = match i {
+                        3 => unorm8(value),
+                        _ => unorm8(linear_to_srgb(value)),
+                    };
+                }
+            },
+            Format::R10G10B10A2_UNORM => |src, dst| {
+                let texel = u32::from_le_bytes([src[0], src[1], src[2], src[3]]);
+
+                for (i, channel) in dst.iter_mut().enumerate() {
+                    *channel = match i {
+                        3 => ((texel >> 30) * 255 / 3) as u8,
+                        _ => unorm8(((texel >> (i * 10)) & 0x3FF) as f32 / 1023.0),
+                    };
+                }
+            },
+            _ => {
+                return Err(crate::Error::Unsupported(
+                    "the image format can't be converted to RGBA8",
+                ))
+            }
+        };
+
+        let texel_size = self.format.block_size() as usize;
+        let mut rgba = vec![0; self.extent.width as usize * self.extent.height as usize * 4];
+
+        for (y, dst_row) in rgba
+            .chunks_exact_mut(self.extent.width as usize * 4)
+            .enumerate()
+        {
+            let src_row = self.row(y as u32);
+
+            for (src, dst) in src_row
+                .chunks_exact(texel_size)
+                .zip(dst_row.chunks_exact_mut(4))
+            {
+                convert(src, dst);
+            }
+        }
+
+        Ok(rgba)
+    }
+
+    /// Writes the image to a PNG file as 8 bit RGBA.
+    #[cfg(feature = "png")]
+    pub fn save_png(&self, path: impl AsRef<std::path::Path>) -> crate::Result<()> {
+        let rgba = self.to_rgba8()?;
+
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+        let mut encoder = png::Encoder::new(file, self.extent.width, self.extent.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&rgba)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+}
+
+fn unorm8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1F) as i32;
+    let mantissa = (bits & 0x3FF) as f32;
+
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1F if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1F => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image_data(format: Format, width: u32, height: u32, data: Vec<u8>) -> ImageData {
+        let block = format.block_extent();
+
+        ImageData {
+            format,
+            extent: Extent2D { width, height },
+            row_pitch: width.div_ceil(block.width) as u64 * format.block_size() as u64,
+            data,
+        }
+    }
+
+    #[test]
+    fn splits_compressed_images_into_block_rows() {
+        // BC7 stores 4x4 texel blocks of 16 bytes
+        let data: Vec<u8> = (0..64).collect();
+        let image = image_data(Format::BC7_UNORM, 8, 8, data.clone());
+
+        assert_eq!(image.rows(), 2);
+        assert_eq!(image.row(0), &data[..32]);
+        assert_eq!(image.row(1), &data[32..]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_rows_past_the_last_block_row() {
+        let image = image_data(Format::BC7_UNORM, 8, 8, vec![0; 64]);
+        image.row(7);
+    }
+
+    #[test]
+    fn converts_rgba8_and_bgra8() {
+        let texels = vec![1, 2, 3, 4, 5, 6, 7, 8];
+
+        let rgba = image_data(Format::R8G8B8A8_UNORM_SRGB, 1, 2, texels.clone());
+        assert_eq!(rgba.to_rgba8().unwrap(), texels);
+
+        let bgra = image_data(Format::B8G8R8A8_UNORM, 2, 1, texels);
+        assert_eq!(bgra.to_rgba8().unwrap(), [3, 2, 1, 4, 7, 6, 5, 8]);
+    }
+
+    #[test]
+    fn converts_rgba16f_to_srgb() {
+        // 0.0, 1.0, 0.5 and 0.5
+        let texel = [0x0000u16, 0x3C00, 0x3800, 0x3800];
+        let data = texel.iter().flat_map(|half| half.to_le_bytes()).collect();
+
+        let image = image_data(Format::R16G16B16A16_SFLOAT, 1, 1, data);
+
+        // the color channels are sRGB encoded, alpha isn't
+        assert_eq!(image.to_rgba8().unwrap(), [0, 255, 188, 128]);
+    }
+
+    #[test]
+    fn converts_rgb10a2() {
+        let texel: u32 = 1023 | (512 << 20) | (2 << 30);
+        let image = image_data(
+            Format::R10G10B10A2_UNORM,
+            1,
+            1,
+            texel.to_le_bytes().to_vec(),
+        );
+
+        assert_eq!(image.to_rgba8().unwrap(), [255, 0, 128, 170]);
+    }
+
+    #[test]
+    fn rejects_unconvertible_formats() {
+        let image = image_data(Format::BC7_UNORM, 4, 4, vec![0; 16]);
+
+        assert!(matches!(
+            image.to_rgba8(),
+            Err(crate::Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn converts_half_floats() {
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert_eq!(f16_to_f32(0x3C00), 1.0);
+        assert_eq!(f16_to_f32(0xC000), -2.0);
+        assert_eq!(f16_to_f32(0x7BFF), 65504.0);
+        // the smallest subnormal
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0xFC00), f32::NEG_INFINITY);
+        assert!(f16_to_f32(0x7E00).is_nan());
+    }
+}
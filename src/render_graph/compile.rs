@@ -0,0 +1,741 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use super::*;
+use crate::api::traits::CommandBuffer as _;
+use crate::{DependencyInfo, Image, ImageBarrier, ImageSubresourceRange};
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RenderGraphError {
+    #[error("pass '{pass}' reads '{resource}' before anything writes it")]
+    ReadBeforeWrite { pass: String, resource: String },
+}
+
+/// A usage transition an image goes through before a pass.
+///
+/// A barrier without a `before` usage discards the image's previous contents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceBarrier {
+    pub resource: ResourceId,
+    pub before: Option<ResourceUsage>,
+    pub after: ResourceUsage,
+    /// For the first barrier of a transient resource reusing a physical image, the last usage
+    /// of the previous resource on that image, which is waited on before its contents are
+    /// discarded.
+    pub aliased: Option<ResourceUsage>,
+}
+
+impl ResourceBarrier {
+    /// The usage whose accesses must complete before the barrier.
+    pub fn src_usage(&self) -> Option<ResourceUsage> {
+        self.before.or(self.aliased)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledPass {
+    pub pass: PassId,
+    pub name: String,
+    /// Barriers recorded before the pass runs.
+    pub barriers: Vec<ResourceBarrier>,
+}
+
+/// The compiled form of a render graph.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    /// The passes that run, in order.
+    pub passes: Vec<ScheduledPass>,
+    pub culled: Vec<PassId>,
+    /// Barriers moving exported images to their final usage after the last pass.
+    pub final_barriers: Vec<ResourceBarrier>,
+    /// The images backing transient resources, shared by resources with disjoint lifetimes.
+    pub physical_images: Vec<TransientImageDesc>,
+    physical_indices: Vec<Option<usize>>,
+}
+
+impl Schedule {
+    /// Index into `physical_images` of the image backing a transient resource.
+    ///
+    /// Returns `None` for imported resources and transient resources that were culled.
+    pub fn physical_image(&self, resource: ResourceId) -> Option<usize> {
+        self.physical_indices.get(resource.0).copied().flatten()
+    }
+}
+
+pub struct CompiledGraph {
+    schedule: Schedule,
+    executors: Vec<Option<PassExecutor>>,
+}
+
+impl CompiledGraph {
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Records every scheduled pass into the command buffer.
+    ///
+    /// `images` returns the image backing a resource, for transient resources the one created
+    /// for their [`Schedule::physical_image`].
+    pub fn execute<F>(&mut self, command_buffer: &CommandBuffer, images: F) -> crate::Result<()>
+    where
+        F: Fn(ResourceId) -> Image,
+    {
+        for step in &self.schedule.passes {
+            record_barriers(command_buffer, &images, &step.barriers)?;
+
+            if let Some(executor) = &mut self.executors[step.pass.0] {
+                executor(&PassContext {
+                    command_buffer,
+                    schedule: &self.schedule,
+                    pass: step.pass,
+                });
+            }
+        }
+
+        record_barriers(command_buffer, &images, &self.schedule.final_barriers)
+    }
+}
+
+fn record_barriers<F>(
+    command_buffer: &CommandBuffer,
+    images: &F,
+    barriers: &[ResourceBarrier],
+) -> crate::Result<()>
+where
+    F: Fn(ResourceId) -> Image,
+{
+    if barriers.is_empty() {
+        return Ok(());
+    }
+
+    let resolved: Vec<Image> = barriers
+        .iter()
+        .map(|barrier| images(barrier.resource))
+        .collect();
+
+    let image_barriers: Vec<ImageBarrier> = barriers
+        .iter()
+        .zip(&resolved)
+        .map(|(barrier, image)| ImageBarrier {
+            image,
+            src_stages: barrier
+                .src_usage()
+                .map(ResourceUsage::stages)
+                .unwrap_or(PipelineStageFlags::TOP_OF_PIPE),
+            src_access: barrier
+                .src_usage()
+                .map(ResourceUsage::access)
+                .unwrap_or_default(),
+            dst_stages: barrier.after.stages(),
+            dst_access: barrier.after.access(),
+            old_layout: barrier
+                .before
+                .map(ResourceUsage::layout)
+                .unwrap_or(ImageLayout::Undefined),
+            new_layout: barrier.after.layout(),
+            subresource_range: ImageSubresourceRange {
+                aspect: barrier.after.aspect(image.format()),
+                ..Default::default()
+            },
+            queue_transfer: None,
+        })
+        .collect();
+
+    command_buffer.pipeline_barrier(&DependencyInfo {
+        image_barriers: &image_barriers,
+        ..Default::default()
+    })
+}
+
+impl RenderGraph {
+    pub fn compile(self) -> Result<CompiledGraph, RenderGraphError> {
+        let dependencies = self.dependencies()?;
+        let live = self.live_passes(&dependencies);
+        let order = sort(&dependencies, &live);
+
+        let mut schedule = Schedule {
+            culled: (0..self.passes.len())
+                .filter(|&pass| !live[pass])
+                .map(PassId)
+                .collect(),
+            ..Default::default()
+        };
+
+        // barriers wait on the previous users of reused physical images, so alias first
+        let previous = self.alias_transients(&order, &mut schedule);
+        self.place_barriers(&order, &previous, &mut schedule);
+
+        Ok(CompiledGraph {
+            schedule,
+            executors: self.passes.into_iter().map(|pass| pass.executor).collect(),
+        })
+    }
+
+    /// The passes each pass must run after.
+    fn dependencies(&self) -> Result<Vec<BTreeSet<usize>>, RenderGraphError> {
+        let mut dependencies = vec![BTreeSet::new(); self.passes.len()];
+        let mut last_writer: Vec<Option<usize>> = vec![None; self.resources.len()];
+        let mut readers: Vec<Vec<usize>> = vec![Vec::new(); self.resources.len()];
+
+        for (index, pass) in self.passes.iter().enumerate() {
+            // reads see the contents from before this pass, so handle them first
+            let reads = pass
+                .accesses
+                .iter()
+                .filter(|access| !access.usage.is_write());
+            let writes = pass
+                .accesses
+                .iter()
+                .filter(|access| access.usage.is_write());
+
+            for access in reads {
+                let resource = access.resource.0;
+
+                match last_writer[resource] {
+                    Some(writer) => {
+                        dependencies[index].insert(writer);
+                    }
+                    None if !self.has_initial_contents(access.resource) => {
+                        return Err(RenderGraphError::ReadBeforeWrite {
+                            pass: pass.name.clone(),
+                            resource: self.resources[resource].name.clone(),
+                        });
+                    }
+                    None => {}
+                }
+
+                readers[resource].push(index);
+            }
+
+            for access in writes {
+                let resource = access.resource.0;
+
+                dependencies[index].extend(last_writer[resource]);
+                dependencies[index].extend(readers[resource].drain(..));
+                dependencies[index].remove(&index);
+
+                last_writer[resource] = Some(index);
+            }
+        }
+
+        Ok(dependencies)
+    }
+
+    fn has_initial_contents(&self, resource: ResourceId) -> bool {
+        matches!(
+            self.resources[resource.0].kind,
+            ResourceKind::Imported {
+                initial_usage: Some(_),
+                ..
+            }
+        )
+    }
+
+    fn is_exported(&self, resource: ResourceId) -> bool {
+        matches!(
+            self.resources[resource.0].kind,
+            ResourceKind::Imported {
+                final_usage: Some(_),
+                ..
+            }
+        )
+    }
+
+    /// Passes with side effects or writing exported images, and everything they depend on.
+    fn live_passes(&self, dependencies: &[BTreeSet<usize>]) -> Vec<bool> {
+        let mut live = vec![false; self.passes.len()];
+
+        let mut stack: Vec<usize> = (0..self.passes.len())
+            .filter(|&index| {
+                let pass = &self.passes[index];
+                pass.side_effects
+                    || pass
+                        .accesses
+                        .iter()
+                        .any(|access| access.usage.is_write() && self.is_exported(access.resource))
+            })
+            .collect();
+
+        while let Some(index) = stack.pop() {
+            if !std::mem::replace(&mut live[index], true) {
+                stack.extend(dependencies[index].iter().copied());
+            }
+        }
+
+        live
+    }
+
+    /// `previous` holds, for each transient resource reusing a physical image, the resource
+    /// that used the image before it.
+    fn place_barriers(&self, order: &[usize], previous: &[Option<usize>], schedule: &mut Schedule) {
+        let mut state: Vec<Option<ResourceUsage>> = self
+            .resources
+            .iter()
+            .map(|resource| match resource.kind {
+                ResourceKind::Imported { initial_usage, .. } => initial_usage,
+                ResourceKind::Transient(_) => None,
+            })
+            .collect();
+
+        for &index in order {
+            let pass = &self.passes[index];
+            let mut barriers = Vec::new();
+
+            for access in &pass.accesses {
+                let before = state[access.resource.0];
+
+                let needed = match before {
+                    Some(before) => {
+                        before != access.usage || before.is_write() || access.usage.is_write()
+                    }
+                    None => true,
+                };
+
+                // the previous resource on the image finished before this one's first access
+                let aliased = match before {
+                    Some(_) => None,
+                    None => previous[access.resource.0].and_then(|previous| state[previous]),
+                };
+
+                let barrier = ResourceBarrier {
+                    resource: access.resource,
+                    before,
+                    after: access.usage,
+                    aliased,
+                };
+
+                if needed && !barriers.contains(&barrier) {
+                    barriers.push(barrier);
+                }
+
+                state[access.resource.0] = Some(access.usage);
+            }
+
+            schedule.passes.push(ScheduledPass {
+                pass: PassId(index),
+                name: pass.name.clone(),
+                barriers,
+            });
+        }
+
+        for (index, resource) in self.resources.iter().enumerate() {
+            if let ResourceKind::Imported {
+                final_usage: Some(final_usage),
+                ..
+            } = resource.kind
+            {
+                if state[index] != Some(final_usage) {
+                    schedule.final_barriers.push(ResourceBarrier {
+                        resource: ResourceId(index),
+                        before: state[index],
+                        after: final_usage,
+                        aliased: None,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Assigns each live transient resource a physical image, reusing whole images with the
+    /// same description whose previous users are finished. Memory isn't shared between images
+    /// of different descriptions.
+    ///
+    /// Returns the resource that used each reused image before the resource now assigned it.
+    fn alias_transients(&self, order: &[usize], schedule: &mut Schedule) -> Vec<Option<usize>> {
+        let mut lifetimes: Vec<Option<(usize, usize)>> = vec![None; self.resources.len()];
+
+        for (position, &index) in order.iter().enumerate() {
+            for access in &self.passes[index].accesses {
+                let lifetime = &mut lifetimes[access.resource.0];
+                *lifetime = Some(match *lifetime {
+                    Some((first, _)) => (first, position),
+                    None => (position, position),
+                });
+            }
+        }
+
+        let mut transients: Vec<(usize, TransientImageDesc, (usize, usize))> = self
+            .resources
+            .iter()
+            .enumerate()
+            .filter_map(|(index, resource)| match resource.kind {
+                ResourceKind::Transient(desc) => Some((index, desc, lifetimes[index]?)),
+                ResourceKind::Imported { .. } => None,
+            })
+            .collect();
+
+        transients.sort_by_key(|(_, _, (first, _))| *first);
+
+        // the position each physical image is last used at, and the resource using it then
+        let mut last_uses: Vec<(usize, usize)> = Vec::new();
+        let mut previous = vec![None; self.resources.len()];
+        schedule.physical_indices = vec![None; self.resources.len()];
+
+        for (resource, desc, (first, last)) in transients {
+            let reusable =
+                schedule.physical_images.iter().zip(&last_uses).position(
+                    |(image, &(last_use, _))| last_use < first && image.is_compatible(&desc),
+                );
+
+            let physical = match reusable {
+                Some(physical) => {
+                    previous[resource] = Some(last_uses[physical].1);
+                    physical
+                }
+                None => {
+                    schedule.physical_images.push(desc);
+                    last_uses.push((0, resource));
+                    schedule.physical_images.len() - 1
+                }
+            };
+
+            last_uses[physical] = (last, resource);
+            schedule.physical_indices[resource] = Some(physical);
+        }
+
+        previous
+    }
+}
+
+/// Orders the live passes so each runs after its dependencies, preferring declaration order.
+fn sort(dependencies: &[BTreeSet<usize>], live: &[bool]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = dependencies
+        .iter()
+        .map(|dependencies| dependencies.iter().filter(|&&dep| live[dep]).count())
+        .collect();
+
+    let mut ready: BTreeSet<usize> = (0..dependencies.len())
+        .filter(|&index| live[index] && remaining[index] == 0)
+        .collect();
+
+    let mut order = Vec::new();
+
+    while let Some(index) = ready.pop_first() {
+        order.push(index);
+
+        for (dependent, dependencies) in dependencies.iter().enumerate() {
+            if live[dependent] && dependencies.contains(&index) {
+                remaining[dependent] -= 1;
+                if remaining[dependent] == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desc() -> TransientImageDesc {
+        TransientImageDesc {
+            format: Format::R8G8B8A8_UNORM,
+            extent: Extent2D {
+                width: 64,
+                height: 64,
+            },
+        }
+    }
+
+    fn order(schedule: &Schedule) -> Vec<PassId> {
+        schedule.passes.iter().map(|pass| pass.pass).collect()
+    }
+
+    #[test]
+    fn culls_passes_without_exported_writes() {
+        let mut graph = RenderGraph::new();
+        let unused = graph.create_image("unused", desc());
+        let captured = graph.create_image("captured", desc());
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        let dead = graph.add_pass("dead", |pass| {
+            pass.write(unused, ResourceUsage::ColorAttachment);
+        });
+        let readback = graph.add_pass("readback", |pass| {
+            pass.write(captured, ResourceUsage::ColorAttachment)
+                .side_effects();
+        });
+        let main = graph.add_pass("main", |pass| {
+            pass.write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let compiled = graph.compile().unwrap();
+        let schedule = compiled.schedule();
+
+        assert_eq!(schedule.culled, vec![dead]);
+        assert_eq!(order(schedule), vec![readback, main]);
+    }
+
+    #[test]
+    fn orders_passes_after_their_dependencies() {
+        let mut graph = RenderGraph::new();
+        let shadow = graph.create_image("shadow", desc());
+        let color = graph.create_image("color", desc());
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        let shadow_pass = graph.add_pass("shadow", |pass| {
+            pass.write(shadow, ResourceUsage::ColorAttachment);
+        });
+        let lighting = graph.add_pass("lighting", |pass| {
+            pass.read(shadow, ResourceUsage::Sampled)
+                .write(color, ResourceUsage::ColorAttachment);
+        });
+        let tonemap = graph.add_pass("tonemap", |pass| {
+            pass.read(color, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let compiled = graph.compile().unwrap();
+
+        assert!(compiled.schedule().culled.is_empty());
+        assert_eq!(
+            order(compiled.schedule()),
+            vec![shadow_pass, lighting, tonemap]
+        );
+    }
+
+    #[test]
+    fn places_barriers_between_usages() {
+        let mut graph = RenderGraph::new();
+        let color = graph.create_image("color", desc());
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        graph.add_pass("draw", |pass| {
+            pass.write(color, ResourceUsage::ColorAttachment);
+        });
+        graph.add_pass("blit", |pass| {
+            pass.read(color, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let compiled = graph.compile().unwrap();
+        let schedule = compiled.schedule();
+
+        assert_eq!(
+            schedule.passes[0].barriers,
+            vec![ResourceBarrier {
+                resource: color,
+                before: None,
+                after: ResourceUsage::ColorAttachment,
+                aliased: None,
+            }]
+        );
+        assert_eq!(
+            schedule.passes[1].barriers,
+            vec![
+                ResourceBarrier {
+                    resource: color,
+                    before: Some(ResourceUsage::ColorAttachment),
+                    after: ResourceUsage::Sampled,
+                    aliased: None,
+                },
+                ResourceBarrier {
+                    resource: backbuffer,
+                    before: None,
+                    after: ResourceUsage::ColorAttachment,
+                    aliased: None,
+                },
+            ]
+        );
+        assert_eq!(
+            schedule.final_barriers,
+            vec![ResourceBarrier {
+                resource: backbuffer,
+                before: Some(ResourceUsage::ColorAttachment),
+                after: ResourceUsage::Present,
+                aliased: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn skips_barriers_between_reads() {
+        let mut graph = RenderGraph::new();
+        let texture = graph.import_image("texture", Some(ResourceUsage::Sampled), None);
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        graph.add_pass("draw", |pass| {
+            pass.read(texture, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let compiled = graph.compile().unwrap();
+
+        assert!(compiled.schedule().passes[0]
+            .barriers
+            .iter()
+            .all(|barrier| barrier.resource != texture));
+    }
+
+    #[test]
+    fn rejects_reads_before_writes() {
+        let mut graph = RenderGraph::new();
+        let color = graph.create_image("color", desc());
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        graph.add_pass("blit", |pass| {
+            pass.read(color, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let error = graph.compile().err().unwrap();
+
+        assert_eq!(
+            error,
+            RenderGraphError::ReadBeforeWrite {
+                pass: "blit".to_string(),
+                resource: "color".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn aliases_transients_with_disjoint_lifetimes() {
+        let mut graph = RenderGraph::new();
+        let first = graph.create_image("first", desc());
+        let second = graph.create_image("second", desc());
+        let third = graph.create_image("third", desc());
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        graph.add_pass("a", |pass| {
+            pass.write(first, ResourceUsage::ColorAttachment);
+        });
+        graph.add_pass("b", |pass| {
+            pass.read(first, ResourceUsage::Sampled)
+                .write(second, ResourceUsage::ColorAttachment);
+        });
+        graph.add_pass("c", |pass| {
+            pass.read(second, ResourceUsage::Sampled)
+                .write(third, ResourceUsage::ColorAttachment);
+        });
+        graph.add_pass("d", |pass| {
+            pass.read(third, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let compiled = graph.compile().unwrap();
+        let schedule = compiled.schedule();
+
+        // first and second overlap in pass b, but first has finished by the time third starts
+        assert_eq!(schedule.physical_images.len(), 2);
+        assert_eq!(
+            schedule.physical_image(first),
+            schedule.physical_image(third)
+        );
+        assert_ne!(
+            schedule.physical_image(first),
+            schedule.physical_image(second)
+        );
+        assert_eq!(schedule.physical_image(backbuffer), None);
+
+        // third waits on first's last read of the shared image
+        assert!(schedule.passes[2].barriers.contains(&ResourceBarrier {
+            resource: third,
+            before: None,
+            after: ResourceUsage::ColorAttachment,
+            aliased: Some(ResourceUsage::Sampled),
+        }));
+    }
+
+    #[test]
+    fn keeps_incompatible_transients_apart() {
+        let mut graph = RenderGraph::new();
+        let first = graph.create_image("first", desc());
+        let second = graph.create_image(
+            "second",
+            TransientImageDesc {
+                format: Format::R16G16B16A16_SFLOAT,
+                ..desc()
+            },
+        );
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        graph.add_pass("a", |pass| {
+            pass.write(first, ResourceUsage::ColorAttachment);
+        });
+        graph.add_pass("b", |pass| {
+            pass.read(first, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+        graph.add_pass("c", |pass| {
+            pass.write(second, ResourceUsage::ColorAttachment);
+        });
+        graph.add_pass("d", |pass| {
+            pass.read(second, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let compiled = graph.compile().unwrap();
+
+        assert_eq!(compiled.schedule().physical_images.len(), 2);
+    }
+
+    #[test]
+    fn covers_stencil_aspects() {
+        assert_eq!(
+            ResourceUsage::DepthStencilAttachment.aspect(Format::D24_UNORM_S8_UINT),
+            ImageAspectFlags::DEPTH | ImageAspectFlags::STENCIL
+        );
+        assert_eq!(
+            ResourceUsage::DepthStencilRead.aspect(Format::D32_SFLOAT),
+            ImageAspectFlags::DEPTH
+        );
+        assert_eq!(
+            ResourceUsage::Sampled.aspect(Format::D24_UNORM_S8_UINT),
+            ImageAspectFlags::COLOR
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_writes_declared_as_reads() {
+        let mut graph = RenderGraph::new();
+        let color = graph.create_image("color", desc());
+
+        graph.add_pass("draw", |pass| {
+            pass.read(color, ResourceUsage::ColorAttachment);
+        });
+    }
+
+    #[test]
+    fn merges_repeated_declarations() {
+        let mut graph = RenderGraph::new();
+        let texture = graph.import_image("texture", Some(ResourceUsage::TransferDst), None);
+        let backbuffer = graph.import_image("backbuffer", None, Some(ResourceUsage::Present));
+
+        graph.add_pass("draw", |pass| {
+            pass.read(texture, ResourceUsage::Sampled)
+                .read(texture, ResourceUsage::Sampled)
+                .write(backbuffer, ResourceUsage::ColorAttachment);
+        });
+
+        let compiled = graph.compile().unwrap();
+
+        let texture_barriers: Vec<_> = compiled.schedule().passes[0]
+            .barriers
+            .iter()
+            .filter(|barrier| barrier.resource == texture)
+            .map(|barrier| (barrier.before, barrier.after))
+            .collect();
+        assert_eq!(
+            texture_barriers,
+            [(Some(ResourceUsage::TransferDst), ResourceUsage::Sampled)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_conflicting_declarations() {
+        let mut graph = RenderGraph::new();
+        let texture = graph.create_image("texture", desc());
+
+        graph.add_pass("copy", |pass| {
+            pass.read(texture, ResourceUsage::Sampled)
+                .read(texture, ResourceUsage::TransferSrc);
+        });
+    }
+}
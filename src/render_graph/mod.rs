@@ -0,0 +1,296 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+//! Frame render graph.
+//!
+//! Passes declare the images they read and write. Compiling the graph orders the passes, culls
+//! the ones that contribute nothing to an exported image, works out the barriers each pass needs
+//! and lets transient images with disjoint lifetimes reuse the same physical image. The result is a plain [`Schedule`]
+//! that can be inspected without a device.
+//!
+//! A pass depends on the last pass declared before it that wrote an image it reads, and a write
+//! depends on every earlier access to the image.
+
+mod compile;
+
+pub use compile::*;
+
+use crate::{
+    AccessFlags, CommandBuffer, Extent2D, Format, ImageAspectFlags, ImageLayout, PipelineStageFlags,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResourceId(usize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PassId(usize);
+
+/// How a pass uses an image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResourceUsage {
+    ColorAttachment,
+    DepthStencilAttachment,
+    DepthStencilRead,
+    InputAttachment,
+    Sampled,
+    StorageRead,
+    StorageWrite,
+    TransferSrc,
+    TransferDst,
+    Present,
+}
+
+impl ResourceUsage {
+    pub fn is_write(self) -> bool {
+        matches!(
+            self,
+            Self::ColorAttachment
+                | Self::DepthStencilAttachment
+                | Self::StorageWrite
+                | Self::TransferDst
+        )
+    }
+
+    /// The stages that access the image.
+    pub fn stages(self) -> PipelineStageFlags {
+        let shaders = PipelineStageFlags::VERTEX_SHADER
+            | PipelineStageFlags::FRAGMENT_SHADER
+            | PipelineStageFlags::COMPUTE_SHADER;
+
+        match self {
+            Self::ColorAttachment => PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+            Self::DepthStencilAttachment => {
+                PipelineStageFlags::EARLY_FRAGMENT_TESTS | PipelineStageFlags::LATE_FRAGMENT_TESTS
+            }
+            Self::DepthStencilRead => {
+                PipelineStageFlags::EARLY_FRAGMENT_TESTS
+                    | PipelineStageFlags::LATE_FRAGMENT_TESTS
+                    | PipelineStageFlags::FRAGMENT_SHADER
+            }
+            Self::InputAttachment => PipelineStageFlags::FRAGMENT_SHADER,
+            Self::Sampled | Self::StorageRead | Self::StorageWrite => shaders,
+            Self::TransferSrc | Self::TransferDst => PipelineStageFlags::TRANSFER,
+            Self::Present => PipelineStageFlags::BOTTOM_OF_PIPE,
+        }
+    }
+
+    pub fn access(self) -> AccessFlags {
+        match self {
+            Self::ColorAttachment => {
+                AccessFlags::COLOR_ATTACHMENT_READ | AccessFlags::COLOR_ATTACHMENT_WRITE
+            }
+            Self::DepthStencilAttachment => {
+                AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ
+                    | AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE
+            }
+            Self::DepthStencilRead => {
+                AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ | AccessFlags::SHADER_READ
+            }
+            Self::InputAttachment => AccessFlags::INPUT_ATTACHMENT_READ,
+            Self::Sampled | Self::StorageRead => AccessFlags::SHADER_READ,
+            Self::StorageWrite => AccessFlags::SHADER_READ | AccessFlags::SHADER_WRITE,
+            Self::TransferSrc => AccessFlags::TRANSFER_READ,
+            Self::TransferDst => AccessFlags::TRANSFER_WRITE,
+            Self::Present => AccessFlags::empty(),
+        }
+    }
+
+    pub fn layout(self) -> ImageLayout {
+        match self {
+            Self::ColorAttachment => ImageLayout::ColorAttachment,
+            Self::DepthStencilAttachment => ImageLayout::DepthStencilAttachment,
+            Self::DepthStencilRead => ImageLayout::DepthStencilReadOnly,
+            Self::InputAttachment | Self::Sampled => ImageLayout::ShaderReadOnly,
+            Self::StorageRead | Self::StorageWrite => ImageLayout::General,
+            Self::TransferSrc => ImageLayout::TransferSrc,
+            Self::TransferDst => ImageLayout::TransferDst,
+            Self::Present => ImageLayout::PresentSrc,
+        }
+    }
+
+    /// The aspects of an image in `format` the usage covers.
+    pub fn aspect(self, format: Format) -> ImageAspectFlags {
+        match self {
+            Self::DepthStencilAttachment | Self::DepthStencilRead if format.has_stencil() => {
+                ImageAspectFlags::DEPTH | ImageAspectFlags::STENCIL
+            }
+            Self::DepthStencilAttachment | Self::DepthStencilRead => ImageAspectFlags::DEPTH,
+            _ => ImageAspectFlags::COLOR,
+        }
+    }
+}
+
+/// An image owned by the graph for the duration of a frame.
+#[derive(Debug, Copy, Clone)]
+pub struct TransientImageDesc {
+    pub format: Format,
+    pub extent: Extent2D,
+}
+
+impl TransientImageDesc {
+    /// Whether an image created for `other` can also back this one.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        self.format == other.format
+            && self.extent.width == other.extent.width
+            && self.extent.height == other.extent.height
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ResourceKind {
+    Imported {
+        initial_usage: Option<ResourceUsage>,
+        final_usage: Option<ResourceUsage>,
+    },
+    Transient(TransientImageDesc),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Resource {
+    name: String,
+    kind: ResourceKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceAccess {
+    pub resource: ResourceId,
+    pub usage: ResourceUsage,
+}
+
+/// Passed to a pass while the compiled graph is executed.
+pub struct PassContext<'a> {
+    pub command_buffer: &'a CommandBuffer,
+    pub schedule: &'a Schedule,
+    pub pass: PassId,
+}
+
+impl PassContext<'_> {
+    /// The physical image backing a transient resource.
+    pub fn physical_image(&self, resource: ResourceId) -> Option<usize> {
+        self.schedule.physical_image(resource)
+    }
+}
+
+type PassExecutor = Box<dyn FnMut(&PassContext)>;
+
+pub(crate) struct Pass {
+    name: String,
+    accesses: Vec<ResourceAccess>,
+    side_effects: bool,
+    executor: Option<PassExecutor>,
+}
+
+/// Declares the resources a pass accesses.
+pub struct PassBuilder<'a> {
+    pass: &'a mut Pass,
+}
+
+impl PassBuilder<'_> {
+    /// Declares a read of the image's current contents.
+    ///
+    /// Panics if `usage` writes the image, or the pass already declared the image with another
+    /// usage.
+    pub fn read(&mut self, resource: ResourceId, usage: ResourceUsage) -> &mut Self {
+        assert!(!usage.is_write(), "{usage:?} writes, declare it with write");
+        self.access(resource, usage)
+    }
+
+    /// Declares a write, which later reads of the image depend on.
+    ///
+    /// Panics if `usage` only reads the image, or the pass already declared the image with
+    /// another usage.
+    pub fn write(&mut self, resource: ResourceId, usage: ResourceUsage) -> &mut Self {
+        assert!(
+            usage.is_write(),
+            "{usage:?} doesn't write, declare it with read"
+        );
+        self.access(resource, usage)
+    }
+
+    fn access(&mut self, resource: ResourceId, usage: ResourceUsage) -> &mut Self {
+        // an image is in a single layout for the whole pass
+        match self.pass.accesses.iter().find(|a| a.resource == resource) {
+            Some(existing) => assert_eq!(
+                existing.usage, usage,
+                "pass '{}' declares {resource:?} with two usages",
+                self.pass.name
+            ),
+            None => self.pass.accesses.push(ResourceAccess { resource, usage }),
+        }
+
+        self
+    }
+
+    /// Keeps the pass even when nothing it writes is used, e.g. for readbacks.
+    pub fn side_effects(&mut self) -> &mut Self {
+        self.pass.side_effects = true;
+        self
+    }
+
+    pub fn execute<F: FnMut(&PassContext) + 'static>(&mut self, executor: F) -> &mut Self {
+        self.pass.executor = Some(Box::new(executor));
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct RenderGraph {
+    resources: Vec<Resource>,
+    passes: Vec<Pass>,
+}
+
+impl RenderGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an image that outlives the frame, such as a swapchain image.
+    ///
+    /// An image with a final usage is exported, so passes writing it are never culled. An image
+    /// without an initial usage starts with undefined contents.
+    pub fn import_image(
+        &mut self,
+        name: &str,
+        initial_usage: Option<ResourceUsage>,
+        final_usage: Option<ResourceUsage>,
+    ) -> ResourceId {
+        self.add_resource(
+            name,
+            ResourceKind::Imported {
+                initial_usage,
+                final_usage,
+            },
+        )
+    }
+
+    /// Adds an image only used within the frame. Transient images with the same description
+    /// that are not alive at the same time are backed by the same physical image.
+    pub fn create_image(&mut self, name: &str, desc: TransientImageDesc) -> ResourceId {
+        self.add_resource(name, ResourceKind::Transient(desc))
+    }
+
+    pub fn add_pass<F>(&mut self, name: &str, setup: F) -> PassId
+    where
+        F: FnOnce(&mut PassBuilder),
+    {
+        let mut pass = Pass {
+            name: name.to_string(),
+            accesses: Vec::new(),
+            side_effects: false,
+            executor: None,
+        };
+
+        setup(&mut PassBuilder { pass: &mut pass });
+
+        self.passes.push(pass);
+        PassId(self.passes.len() - 1)
+    }
+
+    fn add_resource(&mut self, name: &str, kind: ResourceKind) -> ResourceId {
+        self.resources.push(Resource {
+            name: name.to_string(),
+            kind,
+        });
+        ResourceId(self.resources.len() - 1)
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::GlslStage;
+use naga::back::spv::WriterFlags;
+use naga::valid::{Capabilities, ValidationFlags, Validator};
+
+/// A shader source compile error.
+///
+/// Lines and columns start at 1; both are 0 when the error has no source location.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{line}:{column}: {message}")]
+pub struct ShaderCompileError {
+    pub message: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl ShaderCompileError {
+    fn new(message: impl ToString, location: Option<naga::SourceLocation>) -> Self {
+        let (line, column) = location
+            .map(|location| (location.line_number, location.line_position))
+            .unwrap_or_default();
+
+        Self {
+            message: message.to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+impl From<GlslStage> for naga::ShaderStage {
+    fn from(stage: GlslStage) -> Self {
+        match stage {
+            GlslStage::Vertex => naga::ShaderStage::Vertex,
+            GlslStage::Fragment => naga::ShaderStage::Fragment,
+            GlslStage::Compute => naga::ShaderStage::Compute,
+        }
+    }
+}
+
+/// Compiles GLSL source to SPIR-V, the entry point being named `main`.
+///
+/// Only the first error reported by the frontend is returned.
+pub fn compile_glsl(
+    source: &str,
+    stage: GlslStage,
+    defines: &[(&str, &str)],
+) -> Result<Vec<u32>, ShaderCompileError> {
+    let mut options = naga::front::glsl::Options::from(naga::ShaderStage::from(stage));
+    for (name, value) in defines {
+        options.defines.insert(name.to_string(), value.to_string());
+    }
+
+    let module = naga::front::glsl::Frontend::default()
+        .parse(&options, source)
+        .map_err(|error| match error.errors.first() {
+            Some(first) => ShaderCompileError::new(&first.kind, Some(first.meta.location(source))),
+            None => ShaderCompileError::new(error, None),
+        })?;
+
+    // GLSL written for Vulkan already uses its clip space, so Y isn't flipped
+    let mut options = naga::back::spv::Options::default();
+    options.flags.remove(WriterFlags::ADJUST_COORDINATE_SPACE);
+
+    write_spirv(&module, source, &options)
+}
+
+/// Compiles WGSL source to SPIR-V, keeping the entry point names declared in the source.
+///
+/// WGSL's Y up clip space is flipped to Vulkan's.
+pub fn compile_wgsl(source: &str) -> Result<Vec<u32>, ShaderCompileError> {
+    let module = naga::front::wgsl::parse_str(source)
+        .map_err(|error| ShaderCompileError::new(error.message(), error.location(source)))?;
+
+    write_spirv(&module, source, &naga::back::spv::Options::default())
+}
+
+fn write_spirv(
+    module: &naga::Module,
+    source: &str,
+    options: &naga::back::spv::Options,
+) -> Result<Vec<u32>, ShaderCompileError> {
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(module)
+        .map_err(|error| ShaderCompileError::new(error.as_inner(), error.location(source)))?;
+
+    naga::back::spv::write_vec(module, &info, options, None)
+        .map_err(|error| ShaderCompileError::new(error, None))
+}
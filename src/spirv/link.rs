@@ -0,0 +1,132 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use super::{EntryPoint, InterfaceType, InterfaceVariable};
+use crate::api::traits::Shader as _;
+use crate::{ShaderStageFlags, ShaderStages};
+
+/// A mismatch between the interfaces of two linked stages.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LinkError {
+    #[error("{consumer:?} shader reads location {location} component {component}, which the {producer:?} shader does not write")]
+    MissingOutput {
+        producer: ShaderStageFlags,
+        consumer: ShaderStageFlags,
+        location: u32,
+        component: u32,
+    },
+    #[error("{consumer:?} shader input at location {location} does not match the {producer:?} shader output: expected {output:?}, found {input:?}")]
+    TypeMismatch {
+        producer: ShaderStageFlags,
+        consumer: ShaderStageFlags,
+        location: u32,
+        output: InterfaceType,
+        input: InterfaceType,
+    },
+    #[error("fragment shader does not write color attachment {location}")]
+    MissingColorOutput { location: u32 },
+}
+
+/// Checks that every stage's inputs are written by the previous stage, and that the fragment
+/// stage writes each of the subpass's color attachments.
+///
+/// Stages without reflection data are skipped.
+pub fn check_stage_interfaces(
+    stages: &ShaderStages,
+    color_attachment_count: u32,
+) -> Result<(), LinkError> {
+    let stages = [
+        (ShaderStageFlags::VERTEX, &stages.vertex),
+        (ShaderStageFlags::TESSELLATION_CONTROL, &stages.tess_ctrl),
+        (ShaderStageFlags::TESSELLATION_EVALUATION, &stages.tess_eval),
+        (ShaderStageFlags::GEOMETRY, &stages.geometry),
+        (ShaderStageFlags::FRAGMENT, &stages.fragment),
+    ];
+
+    let entry_points: Vec<(ShaderStageFlags, &EntryPoint)> = stages
+        .into_iter()
+        .filter_map(|(flags, stage)| {
+            let stage = stage.as_ref()?;
+            let entry_point = stage.shader.reflection()?.entry_point(&stage.entry_point)?;
+            Some((flags, entry_point))
+        })
+        .collect();
+
+    for pair in entry_points.windows(2) {
+        let (producer, outputs) = (pair[0].0, &pair[0].1.outputs);
+        let (consumer, inputs) = (pair[1].0, &pair[1].1.inputs);
+
+        for input in inputs {
+            check_input(producer, consumer, outputs, input)?;
+        }
+    }
+
+    let fragment = entry_points
+        .last()
+        .filter(|(flags, _)| *flags == ShaderStageFlags::FRAGMENT);
+
+    if let Some((_, fragment)) = fragment {
+        for location in 0..color_attachment_count {
+            let written = fragment.outputs.iter().any(|output| {
+                (output.location..output.location + output.ty.location_count()).contains(&location)
+            });
+
+            if !written {
+                return Err(LinkError::MissingColorOutput { location });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the stage's inputs or outputs carry an extra per-vertex array dimension.
+fn is_arrayed(stage: ShaderStageFlags, input: bool) -> bool {
+    if stage == ShaderStageFlags::TESSELLATION_CONTROL {
+        true
+    } else if stage == ShaderStageFlags::TESSELLATION_EVALUATION
+        || stage == ShaderStageFlags::GEOMETRY
+    {
+        input
+    } else {
+        false
+    }
+}
+
+fn check_input(
+    producer: ShaderStageFlags,
+    consumer: ShaderStageFlags,
+    outputs: &[InterfaceVariable],
+    input: &InterfaceVariable,
+) -> Result<(), LinkError> {
+    let output = outputs
+        .iter()
+        .find(|output| output.location == input.location && output.component == input.component)
+        .ok_or(LinkError::MissingOutput {
+            producer,
+            consumer,
+            location: input.location,
+            component: input.component,
+        })?;
+
+    // per-vertex array dimensions differ between stages and are not compared
+    let compare_arrays = !is_arrayed(producer, false) && !is_arrayed(consumer, true);
+
+    let compatible = output.ty.kind == input.ty.kind
+        && output.ty.width == input.ty.width
+        && output.ty.columns == input.ty.columns
+        && input.ty.components <= output.ty.components
+        && (!compare_arrays || output.ty.array_length == input.ty.array_length);
+
+    if !compatible {
+        return Err(LinkError::TypeMismatch {
+            producer,
+            consumer,
+            location: input.location,
+            output: output.ty,
+            input: input.ty,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+//! Backend independent SPIR-V utilities.
+
+#[cfg(feature = "naga")]
+mod compile;
+mod link;
+mod pipeline;
+mod reflect;
+
+#[cfg(feature = "naga")]
+pub use compile::*;
+pub use link::*;
+pub use pipeline::*;
+pub use reflect::*;
+
+use std::borrow::Cow;
+
+pub const MAGIC_NUMBER: u32 = 0x0723_0203;
+pub const HEADER_WORDS: usize = 5;
+
+/// The newest SPIR-V version accepted, as `(major, minor)`.
+pub const MAX_VERSION: (u32, u32) = (1, 6);
+
+/// Magic bytes of a DXBC container, which wraps DXIL and older D3D bytecode.
+const DXBC_MAGIC: [u8; 4] = *b"DXBC";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SpirvError {
+    #[error("SPIR-V module is truncated")]
+    Truncated,
+    #[error("SPIR-V module length {0} is not a multiple of four bytes")]
+    InvalidLength(usize),
+    #[error("SPIR-V module has invalid magic number {0:#010x}")]
+    InvalidMagic(u32),
+    #[error("shader code is {0}, not SPIR-V")]
+    NotSpirv(&'static str),
+    #[error("unsupported SPIR-V version {major}.{minor}")]
+    UnsupportedVersion { major: u32, minor: u32 },
+    #[error("SPIR-V module has an id bound of zero")]
+    InvalidBound,
+    #[error("malformed SPIR-V instruction at word {0}")]
+    MalformedInstruction(usize),
+    #[error("shader has no SPIR-V reflection data")]
+    MissingReflection,
+    #[error("entry point '{0}' not found in shader")]
+    EntryPointNotFound(String),
+    #[error("descriptor set {set} binding {binding} is declared with conflicting types")]
+    ConflictingBinding { set: u32, binding: u32 },
+    #[error("vertex input at location {0} has no matching vertex format")]
+    UnsupportedVertexInput(u32),
+    #[error("vertex inputs packed into location {0} can't be read as a single attribute")]
+    UnsupportedPackedVertexInput(u32),
+    #[error("SPIR-V type {0} is nested too deeply or refers to itself")]
+    TypeTooDeep(u32),
+    #[error("size of SPIR-V type {0} overflows")]
+    TypeSizeOverflow(u32),
+    #[error("SPIR-V array type {0} is sized by a specialization constant, which reflection can't resolve")]
+    SpecConstantArrayLength(u32),
+}
+
+/*
+   Header
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub major_version: u32,
+    pub minor_version: u32,
+    pub generator: u32,
+    /// All ids in the module are less than this bound.
+    pub bound: u32,
+}
+
+impl Header {
+    /// Validates the header of a module already in host word order.
+    pub fn read(words: &[u32]) -> Result<Self, SpirvError> {
+        if words.len() < HEADER_WORDS {
+            return Err(SpirvError::Truncated);
+        }
+
+        if words[0] != MAGIC_NUMBER {
+            return Err(SpirvError::InvalidMagic(words[0]));
+        }
+
+        let version = words[1];
+        let major_version = (version >> 16) & 0xff;
+        let minor_version = (version >> 8) & 0xff;
+
+        let reserved_bits = version & 0xff00_00ff;
+        if reserved_bits != 0 || major_version != 1 || minor_version > MAX_VERSION.1 {
+            return Err(SpirvError::UnsupportedVersion {
+                major: major_version,
+                minor: minor_version,
+            });
+        }
+
+        if words[3] == 0 {
+            return Err(SpirvError::InvalidBound);
+        }
+
+        Ok(Self {
+            major_version,
+            minor_version,
+            generator: words[2],
+            bound: words[3],
+        })
+    }
+}
+
+/// Reinterprets SPIR-V bytes as words, copying only when the bytes are misaligned or were
+/// written with the opposite byte order.
+///
+/// The header is validated, and well known non SPIR-V formats are reported by name.
+pub fn words_from_code(code: &[u8]) -> Result<Cow<'_, [u32]>, SpirvError> {
+    if code.starts_with(&DXBC_MAGIC) {
+        return Err(SpirvError::NotSpirv("DXBC / DXIL"));
+    }
+
+    if code.len() % 4 != 0 {
+        return Err(SpirvError::InvalidLength(code.len()));
+    }
+
+    if code.len() < HEADER_WORDS * 4 {
+        return Err(SpirvError::Truncated);
+    }
+
+    let magic = u32::from_ne_bytes(code[0..4].try_into().unwrap());
+
+    let words = if magic == MAGIC_NUMBER && code.as_ptr().align_offset(4) == 0 {
+        // SAFETY: the pointer is aligned and the length is a whole number of words
+        let words =
+            unsafe { std::slice::from_raw_parts(code.as_ptr() as *const u32, code.len() / 4) };
+        Cow::Borrowed(words)
+    } else {
+        let swapped = magic.swap_bytes() == MAGIC_NUMBER;
+
+        let words = code.chunks_exact(4).map(|chunk| {
+            let word = u32::from_ne_bytes(chunk.try_into().unwrap());
+            if swapped {
+                word.swap_bytes()
+            } else {
+                word
+            }
+        });
+
+        Cow::Owned(words.collect())
+    };
+
+    Header::read(&words)?;
+
+    Ok(words)
+}
+
+/*
+   Instructions
+*/
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Instruction<'a> {
+    pub opcode: u16,
+    pub operands: &'a [u32],
+}
+
+/// Iterates the instruction stream of a module, header excluded.
+pub(crate) struct Instructions<'a> {
+    words: &'a [u32],
+    offset: usize,
+}
+
+impl<'a> Instructions<'a> {
+    pub(crate) fn new(words: &'a [u32]) -> Result<Self, SpirvError> {
+        if words.len() < HEADER_WORDS {
+            return Err(SpirvError::Truncated);
+        }
+
+        if words[0] != MAGIC_NUMBER {
+            return Err(SpirvError::InvalidMagic(words[0]));
+        }
+
+        Ok(Self {
+            words,
+            offset: HEADER_WORDS,
+        })
+    }
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = Result<Instruction<'a>, SpirvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = *self.words.get(self.offset)?;
+        let word_count = (first >> 16) as usize;
+
+        if word_count == 0 || self.offset + word_count > self.words.len() {
+            // stop iterating after reporting the error
+            let offset = self.offset;
+            self.offset = self.words.len();
+            return Some(Err(SpirvError::MalformedInstruction(offset)));
+        }
+
+        let instruction = Instruction {
+            opcode: (first & 0xffff) as u16,
+            operands: &self.words[self.offset + 1..self.offset + word_count],
+        };
+
+        self.offset += word_count;
+        Some(Ok(instruction))
+    }
+}
+
+/// Reads a nul terminated literal string, returning it along with the number of words it used.
+pub(crate) fn read_string(words: &[u32]) -> (String, usize) {
+    let mut bytes = Vec::new();
+
+    for (index, word) in words.iter().enumerate() {
+        for byte in word.to_le_bytes() {
+            if byte == 0 {
+                return (String::from_utf8_lossy(&bytes).into_owned(), index + 1);
+            }
+            bytes.push(byte);
+        }
+    }
+
+    (String::from_utf8_lossy(&bytes).into_owned(), words.len())
+}
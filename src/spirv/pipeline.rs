@@ -0,0 +1,290 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use super::{InterfaceType, InterfaceVariable, ShaderReflection, SpirvError};
+use crate::api::traits::Shader as _;
+use crate::{
+    DescriptorSetLayoutBinding, DescriptorSetLayoutDescription, PipelineLayoutCreateInfo,
+    PushConstantRange, ShaderStage, ShaderStageFlags, ShaderStages, VertexAttributeDescription,
+    VertexBindingDescription, VertexInputRate, VertexInputState,
+};
+
+/// Pipeline layout and vertex input derived from the reflection of every stage of a pipeline.
+#[derive(Debug, Default, Clone)]
+pub struct PipelineReflection {
+    set_layouts: Vec<DescriptorSetLayoutDescription>,
+    push_constant_ranges: Vec<PushConstantRange>,
+    vertex_bindings: Vec<VertexBindingDescription>,
+    vertex_attributes: Vec<VertexAttributeDescription>,
+}
+
+impl PipelineReflection {
+    /// Merges the resources used by each stage.
+    ///
+    /// Bindings shared between stages are visible to all of them. The vertex inputs are laid out
+    /// tightly packed in location order in a single per-vertex buffer at binding 0.
+    pub fn new(stages: &ShaderStages) -> crate::Result<Self> {
+        let mut reflection = Self::default();
+        let mut push_constants = PushConstantRange {
+            stages: ShaderStageFlags::empty(),
+            offset: 0,
+            size: 0,
+        };
+
+        let stages = [
+            (ShaderStageFlags::VERTEX, &stages.vertex),
+            (ShaderStageFlags::TESSELLATION_CONTROL, &stages.tess_ctrl),
+            (ShaderStageFlags::TESSELLATION_EVALUATION, &stages.tess_eval),
+            (ShaderStageFlags::GEOMETRY, &stages.geometry),
+            (ShaderStageFlags::FRAGMENT, &stages.fragment),
+        ];
+
+        for (stage_flags, stage) in stages {
+            let Some(stage) = stage else {
+                continue;
+            };
+
+            let module = stage_reflection(stage)?;
+
+            for binding in &module.descriptor_bindings {
+                reflection.add_binding(stage_flags, binding)?;
+            }
+
+            if let Some(size) = module.push_constant_size {
+                push_constants.stages |= stage_flags;
+                push_constants.size = push_constants.size.max(size);
+            }
+
+            if stage_flags == ShaderStageFlags::VERTEX {
+                let entry_point = module
+                    .entry_point(&stage.entry_point)
+                    .ok_or_else(|| SpirvError::EntryPointNotFound(stage.entry_point.clone()))?;
+
+                reflection.add_vertex_inputs(&entry_point.inputs)?;
+            }
+        }
+
+        if !push_constants.stages.is_empty() {
+            reflection.push_constant_ranges.push(push_constants);
+        }
+
+        Ok(reflection)
+    }
+
+    pub fn set_layouts(&self) -> &[DescriptorSetLayoutDescription] {
+        &self.set_layouts
+    }
+
+    pub fn push_constant_ranges(&self) -> &[PushConstantRange] {
+        &self.push_constant_ranges
+    }
+
+    pub fn layout_create_info(&self) -> PipelineLayoutCreateInfo<'_> {
+        PipelineLayoutCreateInfo {
+            set_layouts: &self.set_layouts,
+            push_constant_ranges: &self.push_constant_ranges,
+            label: None,
+        }
+    }
+
+    pub fn vertex_input(&self) -> VertexInputState<'_> {
+        VertexInputState {
+            bindings: &self.vertex_bindings,
+            attributes: &self.vertex_attributes,
+        }
+    }
+
+    fn add_binding(
+        &mut self,
+        stage: ShaderStageFlags,
+        binding: &super::DescriptorBinding,
+    ) -> Result<(), SpirvError> {
+        let set = binding.set as usize;
+        if self.set_layouts.len() <= set {
+            self.set_layouts
+                .resize_with(set + 1, DescriptorSetLayoutDescription::default);
+        }
+
+        let bindings = &mut self.set_layouts[set].bindings;
+
+        match bindings.iter_mut().find(|b| b.binding == binding.binding) {
+            Some(existing) if existing.descriptor_type != binding.descriptor_type => {
+                return Err(SpirvError::ConflictingBinding {
+                    set: binding.set,
+                    binding: binding.binding,
+                });
+            }
+            Some(existing) => {
+                existing.stages |= stage;
+                existing.count = existing.count.max(binding.count);
+            }
+            None => {
+                bindings.push(DescriptorSetLayoutBinding {
+                    binding: binding.binding,
+                    descriptor_type: binding.descriptor_type,
+                    count: binding.count,
+                    stages: stage,
+                });
+                bindings.sort_by_key(|b| b.binding);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Expects `inputs` sorted by location and component, as reflection reports them.
+    fn add_vertex_inputs(&mut self, inputs: &[InterfaceVariable]) -> Result<(), SpirvError> {
+        let mut offset = 0;
+
+        for group in inputs.chunk_by(|a, b| a.location == b.location) {
+            let location = group[0].location;
+            let ty = match group {
+                [input] if input.component == 0 => input.ty,
+                _ => packed_input_type(group)?,
+            };
+
+            let format = ty
+                .column_format()
+                .ok_or(SpirvError::UnsupportedVertexInput(location))?;
+
+            // matrices and arrays take one attribute per column / element
+            for index in 0..ty.columns * ty.array_length {
+                self.vertex_attributes.push(VertexAttributeDescription {
+                    location: location + index,
+                    binding: 0,
+                    format,
+                    offset,
+                });
+                offset += ty.column_size();
+            }
+        }
+
+        if !self.vertex_attributes.is_empty() {
+            self.vertex_bindings.push(VertexBindingDescription {
+                binding: 0,
+                stride: offset,
+                input_rate: VertexInputRate::Vertex,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The type of a single attribute holding inputs packed into one location with the `Component`
+/// decoration, which must be scalars or vectors of one type filling the location from its first
+/// component without gaps.
+fn packed_input_type(inputs: &[InterfaceVariable]) -> Result<InterfaceType, SpirvError> {
+    let first = inputs[0].ty;
+    let mut components = 0;
+
+    for input in inputs {
+        let ty = input.ty;
+
+        if input.component != components
+            || ty.kind != first.kind
+            || ty.width != first.width
+            || ty.columns != 1
+            || ty.array_length != 1
+        {
+            return Err(SpirvError::UnsupportedPackedVertexInput(input.location));
+        }
+
+        components += ty.components;
+    }
+
+    Ok(InterfaceType {
+        components,
+        ..first
+    })
+}
+
+fn stage_reflection(stage: &ShaderStage) -> Result<&ShaderReflection, SpirvError> {
+    stage
+        .shader
+        .reflection()
+        .ok_or(SpirvError::MissingReflection)
+}
+
+impl ShaderStages {
+    /// Reflects every stage, see [`PipelineReflection::new`].
+    pub fn reflect(&self) -> crate::Result<PipelineReflection> {
+        PipelineReflection::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spirv::ScalarKind;
+    use crate::Format;
+
+    fn input(
+        location: u32,
+        component: u32,
+        kind: ScalarKind,
+        components: u32,
+    ) -> InterfaceVariable {
+        InterfaceVariable {
+            name: None,
+            location,
+            component,
+            ty: InterfaceType {
+                kind,
+                width: 32,
+                components,
+                columns: 1,
+                array_length: 1,
+            },
+        }
+    }
+
+    fn attributes(reflection: &PipelineReflection) -> Vec<(u32, Format, u32)> {
+        reflection
+            .vertex_attributes
+            .iter()
+            .map(|attribute| (attribute.location, attribute.format, attribute.offset))
+            .collect()
+    }
+
+    #[test]
+    fn merges_inputs_packed_into_one_location() {
+        let mut reflection = PipelineReflection::default();
+        reflection
+            .add_vertex_inputs(&[
+                input(0, 0, ScalarKind::Float, 3),
+                input(0, 3, ScalarKind::Float, 1),
+                input(1, 0, ScalarKind::UInt, 1),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            attributes(&reflection),
+            [
+                (0, Format::R32G32B32A32_SFLOAT, 0),
+                (1, Format::R32_UINT, 16),
+            ]
+        );
+        assert_eq!(reflection.vertex_bindings[0].stride, 20);
+    }
+
+    #[test]
+    fn rejects_packed_inputs_that_cant_be_merged() {
+        let mismatched_kinds = [
+            input(0, 0, ScalarKind::Float, 2),
+            input(0, 2, ScalarKind::SInt, 2),
+        ];
+        let gap = [
+            input(0, 0, ScalarKind::Float, 1),
+            input(0, 2, ScalarKind::Float, 1),
+        ];
+        let offset = [input(0, 1, ScalarKind::Float, 3)];
+
+        for inputs in [&mismatched_kinds[..], &gap, &offset] {
+            assert_eq!(
+                PipelineReflection::default().add_vertex_inputs(inputs),
+                Err(SpirvError::UnsupportedPackedVertexInput(0))
+            );
+        }
+    }
+}
@@ -0,0 +1,950 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use super::{read_string, words_from_code, Instructions, SpirvError};
+use crate::{DescriptorType, Format, ShaderStageFlags};
+use std::collections::{HashMap, HashSet};
+
+/*
+   SPIR-V Constants
+*/
+
+mod op {
+    pub const NAME: u16 = 5;
+    pub const ENTRY_POINT: u16 = 15;
+    pub const TYPE_VOID: u16 = 19;
+    pub const TYPE_BOOL: u16 = 20;
+    pub const TYPE_INT: u16 = 21;
+    pub const TYPE_FLOAT: u16 = 22;
+    pub const TYPE_VECTOR: u16 = 23;
+    pub const TYPE_MATRIX: u16 = 24;
+    pub const TYPE_IMAGE: u16 = 25;
+    pub const TYPE_SAMPLER: u16 = 26;
+    pub const TYPE_SAMPLED_IMAGE: u16 = 27;
+    pub const TYPE_ARRAY: u16 = 28;
+    pub const TYPE_RUNTIME_ARRAY: u16 = 29;
+    pub const TYPE_STRUCT: u16 = 30;
+    pub const TYPE_POINTER: u16 = 32;
+    pub const CONSTANT: u16 = 43;
+    pub const SPEC_CONSTANT_TRUE: u16 = 48;
+    pub const SPEC_CONSTANT_FALSE: u16 = 49;
+    pub const SPEC_CONSTANT: u16 = 50;
+    pub const SPEC_CONSTANT_COMPOSITE: u16 = 51;
+    pub const SPEC_CONSTANT_OP: u16 = 52;
+    pub const VARIABLE: u16 = 59;
+    pub const DECORATE: u16 = 71;
+    pub const MEMBER_DECORATE: u16 = 72;
+    pub const TYPE_ACCELERATION_STRUCTURE: u16 = 5341;
+}
+
+mod decoration {
+    pub const BLOCK: u32 = 2;
+    pub const BUFFER_BLOCK: u32 = 3;
+    pub const ARRAY_STRIDE: u32 = 6;
+    pub const MATRIX_STRIDE: u32 = 7;
+    pub const BUILT_IN: u32 = 11;
+    pub const LOCATION: u32 = 30;
+    pub const COMPONENT: u32 = 31;
+    pub const BINDING: u32 = 33;
+    pub const DESCRIPTOR_SET: u32 = 34;
+    pub const OFFSET: u32 = 35;
+}
+
+mod storage_class {
+    pub const UNIFORM_CONSTANT: u32 = 0;
+    pub const INPUT: u32 = 1;
+    pub const UNIFORM: u32 = 2;
+    pub const OUTPUT: u32 = 3;
+    pub const PUSH_CONSTANT: u32 = 9;
+    pub const STORAGE_BUFFER: u32 = 12;
+}
+
+const DIM_BUFFER: u32 = 5;
+const DIM_SUBPASS_DATA: u32 = 6;
+
+/// Deepest type nesting followed before a module is treated as malformed, which also stops
+/// cyclic type references.
+const MAX_TYPE_DEPTH: u32 = 64;
+
+/*
+   Reflection Types
+*/
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExecutionModel {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    Compute,
+    Other(u32),
+}
+
+impl ExecutionModel {
+    fn from_raw(raw: u32) -> Self {
+        match raw {
+            0 => Self::Vertex,
+            1 => Self::TessellationControl,
+            2 => Self::TessellationEvaluation,
+            3 => Self::Geometry,
+            4 => Self::Fragment,
+            5 => Self::Compute,
+            other => Self::Other(other),
+        }
+    }
+
+    pub fn stage(&self) -> ShaderStageFlags {
+        match self {
+            Self::Vertex => ShaderStageFlags::VERTEX,
+            Self::TessellationControl => ShaderStageFlags::TESSELLATION_CONTROL,
+            Self::TessellationEvaluation => ShaderStageFlags::TESSELLATION_EVALUATION,
+            Self::Geometry => ShaderStageFlags::GEOMETRY,
+            Self::Fragment => ShaderStageFlags::FRAGMENT,
+            Self::Compute => ShaderStageFlags::COMPUTE,
+            Self::Other(_) => ShaderStageFlags::empty(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScalarKind {
+    Bool,
+    Float,
+    SInt,
+    UInt,
+}
+
+/// The type of a stage input or output variable.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InterfaceType {
+    pub kind: ScalarKind,
+    /// Width of a single component in bits.
+    pub width: u32,
+    pub components: u32,
+    /// Number of matrix columns, 1 for scalars and vectors.
+    pub columns: u32,
+    /// Number of array elements, 1 for non-arrays.
+    pub array_length: u32,
+}
+
+impl InterfaceType {
+    /// Number of consecutive locations the variable occupies.
+    pub fn location_count(&self) -> u32 {
+        let per_column = if self.width == 64 && self.components > 2 {
+            2
+        } else {
+            1
+        };
+
+        (per_column * self.columns).saturating_mul(self.array_length)
+    }
+
+    /// Size in bytes of a single column.
+    pub fn column_size(&self) -> u32 {
+        self.components * self.width / 8
+    }
+
+    /// The vertex format matching a single column of this type.
+    pub fn column_format(&self) -> Option<Format> {
+        let format = match (self.kind, self.width, self.components) {
+            (ScalarKind::Float, 32, 1) => Format::R32_SFLOAT,
+            (ScalarKind::Float, 32, 2) => Format::R32G32_SFLOAT,
+            (ScalarKind::Float, 32, 3) => Format::R32G32B32_SFLOAT,
+            (ScalarKind::Float, 32, 4) => Format::R32G32B32A32_SFLOAT,
+            (ScalarKind::Float, 16, 4) => Format::R16G16B16A16_SFLOAT,
+
+            (ScalarKind::SInt, 32, 1) => Format::R32_SINT,
+            (ScalarKind::SInt, 32, 2) => Format::R32G32_SINT,
+            (ScalarKind::SInt, 32, 3) => Format::R32G32B32_SINT,
+            (ScalarKind::SInt, 32, 4) => Format::R32G32B32A32_SINT,
+            (ScalarKind::SInt, 16, 1) => Format::R16_SINT,
+            (ScalarKind::SInt, 16, 2) => Format::R16G16_SINT,
+            (ScalarKind::SInt, 16, 4) => Format::R16G16B16A16_SINT,
+            (ScalarKind::SInt, 8, 1) => Format::R8_SINT,
+            (ScalarKind::SInt, 8, 2) => Format::R8G8_SINT,
+            (ScalarKind::SInt, 8, 4) => Format::R8G8B8A8_SINT,
+
+            (ScalarKind::UInt, 32, 1) => Format::R32_UINT,
+            (ScalarKind::UInt, 32, 2) => Format::R32G32_UINT,
+            (ScalarKind::UInt, 32, 3) => Format::R32G32B32_UINT,
+            (ScalarKind::UInt, 32, 4) => Format::R32G32B32A32_UINT,
+            (ScalarKind::UInt, 16, 1) => Format::R16_UINT,
+            (ScalarKind::UInt, 16, 2) => Format::R16G16_UINT,
+            (ScalarKind::UInt, 16, 4) => Format::R16G16B16A16_UINT,
+            (ScalarKind::UInt, 8, 1) => Format::R8_UINT,
+            (ScalarKind::UInt, 8, 2) => Format::R8G8_UINT,
+            (ScalarKind::UInt, 8, 4) => Format::R8G8B8A8_UINT,
+
+            _ => return None,
+        };
+
+        Some(format)
+    }
+}
+
+/// A user defined stage input or output, built-ins excluded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceVariable {
+    pub name: Option<String>,
+    pub location: u32,
+    pub component: u32,
+    pub ty: InterfaceType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPoint {
+    pub name: String,
+    pub execution_model: ExecutionModel,
+    pub inputs: Vec<InterfaceVariable>,
+    pub outputs: Vec<InterfaceVariable>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorBinding {
+    pub name: Option<String>,
+    pub set: u32,
+    pub binding: u32,
+    pub descriptor_type: DescriptorType,
+    /// Number of descriptors in the binding, 0 for runtime sized arrays.
+    pub count: u32,
+}
+
+/// Resources declared by a SPIR-V module.
+///
+/// Descriptor bindings and push constants are reported for the whole module rather than per entry
+/// point, so a module with several entry points reports the union of their resources.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShaderReflection {
+    pub entry_points: Vec<EntryPoint>,
+    pub descriptor_bindings: Vec<DescriptorBinding>,
+    /// Size in bytes of the push constant block, if the module declares one.
+    pub push_constant_size: Option<u32>,
+}
+
+impl ShaderReflection {
+    /// Reflects a module stored as bytes, in either byte order.
+    pub fn parse(code: &[u8]) -> Result<Self, SpirvError> {
+        Self::from_words(&words_from_code(code)?)
+    }
+
+    pub fn from_words(words: &[u32]) -> Result<Self, SpirvError> {
+        ModuleInfo::parse(words)?.reflect()
+    }
+
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPoint> {
+        self.entry_points.iter().find(|entry| entry.name == name)
+    }
+}
+
+/*
+   Module Parsing
+*/
+
+#[derive(Debug, Clone)]
+enum Type {
+    Bool,
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+    Vector { component: u32, count: u32 },
+    Matrix { column: u32, count: u32 },
+    Image { dim: u32, sampled: u32 },
+    Sampler,
+    SampledImage,
+    Array { element: u32, length: u32 },
+    RuntimeArray { element: u32 },
+    Struct { members: Vec<u32> },
+    Pointer { pointee: u32 },
+    AccelerationStructure,
+    Other,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Decorations {
+    block: bool,
+    buffer_block: bool,
+    built_in: bool,
+    array_stride: Option<u32>,
+    matrix_stride: Option<u32>,
+    location: Option<u32>,
+    component: Option<u32>,
+    binding: Option<u32>,
+    set: Option<u32>,
+    offset: Option<u32>,
+}
+
+impl Decorations {
+    fn apply(&mut self, decoration: u32, operands: &[u32]) {
+        let value = operands.first().copied();
+
+        match decoration {
+            decoration::BLOCK => self.block = true,
+            decoration::BUFFER_BLOCK => self.buffer_block = true,
+            decoration::BUILT_IN => self.built_in = true,
+            decoration::ARRAY_STRIDE => self.array_stride = value,
+            decoration::MATRIX_STRIDE => self.matrix_stride = value,
+            decoration::LOCATION => self.location = value,
+            decoration::COMPONENT => self.component = value,
+            decoration::BINDING => self.binding = value,
+            decoration::DESCRIPTOR_SET => self.set = value,
+            decoration::OFFSET => self.offset = value,
+            _ => {}
+        }
+    }
+}
+
+struct RawEntryPoint {
+    execution_model: u32,
+    name: String,
+    interface: Vec<u32>,
+}
+
+struct Variable {
+    id: u32,
+    pointer_type: u32,
+    storage_class: u32,
+}
+
+#[derive(Default)]
+struct ModuleInfo {
+    names: HashMap<u32, String>,
+    decorations: HashMap<u32, Decorations>,
+    member_decorations: HashMap<(u32, u32), Decorations>,
+    types: HashMap<u32, Type>,
+    constants: HashMap<u32, u32>,
+    spec_constants: HashSet<u32>,
+    variables: Vec<Variable>,
+    entry_points: Vec<RawEntryPoint>,
+}
+
+impl ModuleInfo {
+    fn parse(words: &[u32]) -> Result<Self, SpirvError> {
+        let mut info = Self::default();
+
+        for instruction in Instructions::new(words)? {
+            let instruction = instruction?;
+            let operands = instruction.operands;
+            let operand = |index: usize| operands.get(index).copied().unwrap_or_default();
+
+            match instruction.opcode {
+                op::NAME if !operands.is_empty() => {
+                    info.names.insert(operand(0), read_string(&operands[1..]).0);
+                }
+                op::ENTRY_POINT if operands.len() >= 2 => {
+                    let (name, name_words) = read_string(&operands[2..]);
+                    info.entry_points.push(RawEntryPoint {
+                        execution_model: operand(0),
+                        name,
+                        interface: operands[2 + name_words..].to_vec(),
+                    });
+                }
+                op::DECORATE if operands.len() >= 2 => {
+                    info.decorations
+                        .entry(operand(0))
+                        .or_default()
+                        .apply(operand(1), &operands[2..]);
+                }
+                op::MEMBER_DECORATE if operands.len() >= 3 => {
+                    info.member_decorations
+                        .entry((operand(0), operand(1)))
+                        .or_default()
+                        .apply(operand(2), &operands[3..]);
+                }
+                op::CONSTANT if operands.len() >= 3 => {
+                    info.constants.insert(operand(1), operand(2));
+                }
+                op::SPEC_CONSTANT_TRUE
+                | op::SPEC_CONSTANT_FALSE
+                | op::SPEC_CONSTANT
+                | op::SPEC_CONSTANT_COMPOSITE
+                | op::SPEC_CONSTANT_OP
+                    if operands.len() >= 2 =>
+                {
+                    info.spec_constants.insert(operand(1));
+                }
+                op::VARIABLE if operands.len() >= 3 => {
+                    info.variables.push(Variable {
+                        id: operand(1),
+                        pointer_type: operand(0),
+                        storage_class: operand(2),
+                    });
+                }
+                opcode => {
+                    let ty = match opcode {
+                        op::TYPE_VOID => Type::Other,
+                        op::TYPE_BOOL => Type::Bool,
+                        op::TYPE_INT => Type::Int {
+                            width: operand(1),
+                            signed: operand(2) != 0,
+                        },
+                        op::TYPE_FLOAT => Type::Float { width: operand(1) },
+                        op::TYPE_VECTOR => Type::Vector {
+                            component: operand(1),
+                            count: operand(2),
+                        },
+                        op::TYPE_MATRIX => Type::Matrix {
+                            column: operand(1),
+                            count: operand(2),
+                        },
+                        op::TYPE_IMAGE => Type::Image {
+                            dim: operand(2),
+                            sampled: operand(6),
+                        },
+                        op::TYPE_SAMPLER => Type::Sampler,
+                        op::TYPE_SAMPLED_IMAGE => Type::SampledImage,
+                        op::TYPE_ARRAY => Type::Array {
+                            element: operand(1),
+                            length: operand(2),
+                        },
+                        op::TYPE_RUNTIME_ARRAY => Type::RuntimeArray {
+                            element: operand(1),
+                        },
+                        op::TYPE_STRUCT => Type::Struct {
+                            members: operands.get(1..).unwrap_or_default().to_vec(),
+                        },
+                        op::TYPE_POINTER => Type::Pointer {
+                            pointee: operand(2),
+                        },
+                        op::TYPE_ACCELERATION_STRUCTURE => Type::AccelerationStructure,
+                        _ => continue,
+                    };
+
+                    info.types.insert(operand(0), ty);
+                }
+            }
+        }
+
+        Ok(info)
+    }
+
+    fn reflect(&self) -> Result<ShaderReflection, SpirvError> {
+        let mut reflection = ShaderReflection::default();
+
+        for variable in &self.variables {
+            let ty = self.pointee(variable.pointer_type);
+
+            match variable.storage_class {
+                storage_class::UNIFORM_CONSTANT
+                | storage_class::UNIFORM
+                | storage_class::STORAGE_BUFFER => {
+                    if let Some(binding) = self.descriptor_binding(variable, ty)? {
+                        reflection.descriptor_bindings.push(binding);
+                    }
+                }
+                storage_class::PUSH_CONSTANT => {
+                    let size = self.type_size(ty, 0)?;
+                    reflection.push_constant_size =
+                        Some(reflection.push_constant_size.unwrap_or(0).max(size));
+                }
+                _ => {}
+            }
+        }
+
+        reflection
+            .descriptor_bindings
+            .sort_by_key(|binding| (binding.set, binding.binding));
+
+        for entry in &self.entry_points {
+            let mut inputs = Vec::new();
+            let mut outputs = Vec::new();
+
+            for id in &entry.interface {
+                let Some(variable) = self.variables.iter().find(|variable| variable.id == *id)
+                else {
+                    continue;
+                };
+
+                let target = match variable.storage_class {
+                    storage_class::INPUT => &mut inputs,
+                    storage_class::OUTPUT => &mut outputs,
+                    _ => continue,
+                };
+
+                if let Some(interface) = self.interface_variable(variable)? {
+                    target.push(interface);
+                }
+            }
+
+            inputs.sort_by_key(|variable| (variable.location, variable.component));
+            outputs.sort_by_key(|variable| (variable.location, variable.component));
+
+            reflection.entry_points.push(EntryPoint {
+                name: entry.name.clone(),
+                execution_model: ExecutionModel::from_raw(entry.execution_model),
+                inputs,
+                outputs,
+            });
+        }
+
+        Ok(reflection)
+    }
+
+    fn decorations(&self, id: u32) -> Decorations {
+        self.decorations.get(&id).cloned().unwrap_or_default()
+    }
+
+    fn pointee(&self, pointer_type: u32) -> u32 {
+        match self.types.get(&pointer_type) {
+            Some(Type::Pointer { pointee }) => *pointee,
+            _ => pointer_type,
+        }
+    }
+
+    /// The length of array type `ty`, whose length operand is `length_id`.
+    fn array_length(&self, ty: u32, length_id: u32) -> Result<u32, SpirvError> {
+        if self.spec_constants.contains(&length_id) {
+            return Err(SpirvError::SpecConstantArrayLength(ty));
+        }
+
+        Ok(self.constants.get(&length_id).copied().unwrap_or(1))
+    }
+
+    /// Strips array wrappers from a type, returning the element type and total element count.
+    fn unwrap_arrays(&self, mut ty: u32) -> Result<(u32, u32), SpirvError> {
+        let array = ty;
+        let mut count = 1u32;
+
+        for _ in 0..MAX_TYPE_DEPTH {
+            match self.types.get(&ty) {
+                Some(Type::Array { element, length }) => {
+                    count = count
+                        .checked_mul(self.array_length(ty, *length)?)
+                        .ok_or(SpirvError::TypeSizeOverflow(array))?;
+                    ty = *element;
+                }
+                Some(Type::RuntimeArray { element }) => {
+                    count = 0;
+                    ty = *element;
+                }
+                _ => return Ok((ty, count)),
+            }
+        }
+
+        Err(SpirvError::TypeTooDeep(array))
+    }
+
+    fn descriptor_binding(
+        &self,
+        variable: &Variable,
+        ty: u32,
+    ) -> Result<Option<DescriptorBinding>, SpirvError> {
+        let decorations = self.decorations(variable.id);
+        let (element, count) = self.unwrap_arrays(ty)?;
+        let element_decorations = self.decorations(element);
+
+        let Some(element_type) = self.types.get(&element) else {
+            return Ok(None);
+        };
+
+        let descriptor_type = match (variable.storage_class, element_type) {
+            (storage_class::STORAGE_BUFFER, _) => DescriptorType::StorageBuffer,
+            (storage_class::UNIFORM, _) if element_decorations.buffer_block => {
+                DescriptorType::StorageBuffer
+            }
+            (storage_class::UNIFORM, _) => DescriptorType::UniformBuffer,
+            (_, Type::Sampler) => DescriptorType::Sampler,
+            (_, Type::SampledImage) => DescriptorType::CombinedImageSampler,
+            (_, Type::AccelerationStructure) => DescriptorType::AccelerationStructure,
+            (_, Type::Image { dim, sampled }) => match (*dim, *sampled) {
+                (DIM_SUBPASS_DATA, _) => DescriptorType::InputAttachment,
+                (DIM_BUFFER, 2) => DescriptorType::StorageTexelBuffer,
+                (DIM_BUFFER, _) => DescriptorType::UniformTexelBuffer,
+                (_, 2) => DescriptorType::StorageImage,
+                _ => DescriptorType::SampledImage,
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(DescriptorBinding {
+            name: self.names.get(&variable.id).cloned(),
+            set: decorations.set.unwrap_or(0),
+            binding: decorations.binding.unwrap_or(0),
+            descriptor_type,
+            count,
+        }))
+    }
+
+    fn interface_variable(
+        &self,
+        variable: &Variable,
+    ) -> Result<Option<InterfaceVariable>, SpirvError> {
+        let decorations = self.decorations(variable.id);
+
+        if decorations.built_in {
+            return Ok(None);
+        }
+
+        let ty = self.pointee(variable.pointer_type);
+        let (element, array_length) = self.unwrap_arrays(ty)?;
+
+        // blocks such as gl_PerVertex are made up of built-in members
+        if let Some(Type::Struct { .. }) = self.types.get(&element) {
+            return Ok(None);
+        }
+
+        Ok(self.interface_type(element).and_then(|ty| {
+            Some(InterfaceVariable {
+                name: self.names.get(&variable.id).cloned(),
+                location: decorations.location?,
+                component: decorations.component.unwrap_or(0),
+                ty: InterfaceType {
+                    array_length: array_length.max(1),
+                    ..ty
+                },
+            })
+        }))
+    }
+
+    /// The scalar, vector or matrix type of an interface variable, with an array length of 1.
+    fn interface_type(&self, element: u32) -> Option<InterfaceType> {
+        let (column, columns) = match self.types.get(&element)? {
+            Type::Matrix { column, count } => (*column, *count),
+            _ => (element, 1),
+        };
+
+        let (scalar, components) = match self.types.get(&column)? {
+            Type::Vector { component, count } => (*component, *count),
+            _ => (column, 1),
+        };
+
+        let (kind, width) = match self.types.get(&scalar)? {
+            Type::Bool => (ScalarKind::Bool, 32),
+            Type::Float { width } => (ScalarKind::Float, *width),
+            Type::Int {
+                width,
+                signed: true,
+            } => (ScalarKind::SInt, *width),
+            Type::Int {
+                width,
+                signed: false,
+            } => (ScalarKind::UInt, *width),
+            _ => return None,
+        };
+
+        Some(InterfaceType {
+            kind,
+            width,
+            components,
+            columns,
+            array_length: 1,
+        })
+    }
+
+    /// Size in bytes of a type, following at most `MAX_TYPE_DEPTH` nested types.
+    fn type_size(&self, ty: u32, depth: u32) -> Result<u32, SpirvError> {
+        if depth >= MAX_TYPE_DEPTH {
+            return Err(SpirvError::TypeTooDeep(ty));
+        }
+
+        let overflow = || SpirvError::TypeSizeOverflow(ty);
+
+        let size = match self.types.get(&ty) {
+            Some(Type::Bool) => 4,
+            Some(Type::Int { width, .. }) | Some(Type::Float { width }) => width / 8,
+            Some(Type::Vector { component, count }) => self
+                .type_size(*component, depth + 1)?
+                .checked_mul(*count)
+                .ok_or_else(overflow)?,
+            Some(Type::Matrix { column, count }) => self
+                .type_size(*column, depth + 1)?
+                .checked_mul(*count)
+                .ok_or_else(overflow)?,
+            Some(Type::Array { element, length }) => {
+                let stride = match self.decorations(ty).array_stride {
+                    Some(stride) => stride,
+                    None => self.type_size(*element, depth + 1)?,
+                };
+
+                stride
+                    .checked_mul(self.array_length(ty, *length)?)
+                    .ok_or_else(overflow)?
+            }
+            Some(Type::Struct { members }) => {
+                let mut size = 0;
+
+                for (index, member) in members.iter().enumerate() {
+                    let decorations = self
+                        .member_decorations
+                        .get(&(ty, index as u32))
+                        .cloned()
+                        .unwrap_or_default();
+
+                    let member_size = match (self.types.get(member), decorations.matrix_stride) {
+                        (Some(Type::Matrix { count, .. }), Some(stride)) => {
+                            stride.checked_mul(*count).ok_or_else(overflow)?
+                        }
+                        _ => self.type_size(*member, depth + 1)?,
+                    };
+
+                    let end = decorations
+                        .offset
+                        .unwrap_or(0)
+                        .checked_add(member_size)
+                        .ok_or_else(overflow)?;
+
+                    size = size.max(end);
+                }
+
+                size
+            }
+            _ => 0,
+        };
+
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLOAT4: InterfaceType = InterfaceType {
+        kind: ScalarKind::Float,
+        width: 32,
+        components: 4,
+        columns: 1,
+        array_length: 1,
+    };
+
+    /// Assembles a module from instructions written as `[opcode, operands...]`.
+    fn module(instructions: &[&[u32]]) -> Vec<u32> {
+        let mut words = vec![super::super::MAGIC_NUMBER, 0x0001_0000, 0, 64, 0];
+
+        for instruction in instructions {
+            let (opcode, operands) = instruction.split_first().unwrap();
+            words.push(((operands.len() as u32 + 1) << 16) | opcode);
+            words.extend_from_slice(operands);
+        }
+
+        words
+    }
+
+    /// A nul terminated literal string padded to whole words.
+    fn string(value: &str) -> Vec<u32> {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.resize(value.len() / 4 * 4 + 4, 0);
+
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect()
+    }
+
+    fn entry_point(execution_model: u32, name: &str, interface: &[u32]) -> Vec<u32> {
+        let mut words = vec![op::ENTRY_POINT as u32, execution_model, 1];
+        words.extend(string(name));
+        words.extend_from_slice(interface);
+        words
+    }
+
+    fn name(id: u32, name: &str) -> Vec<u32> {
+        let mut words = vec![op::NAME as u32, id];
+        words.extend(string(name));
+        words
+    }
+
+    #[test]
+    fn reflects_the_triangle_vertex_shader() {
+        let reflection =
+            ShaderReflection::parse(include_bytes!("../../shaders/triangle.vert.spv")).unwrap();
+
+        let entry = reflection.entry_point("main").unwrap();
+        assert_eq!(entry.execution_model, ExecutionModel::Vertex);
+        // gl_VertexIndex and gl_PerVertex are built-ins
+        assert!(entry.inputs.is_empty());
+        assert!(entry.outputs.is_empty());
+        assert!(reflection.descriptor_bindings.is_empty());
+        assert_eq!(reflection.push_constant_size, None);
+    }
+
+    #[test]
+    fn reflects_the_triangle_fragment_shader() {
+        let reflection =
+            ShaderReflection::parse(include_bytes!("../../shaders/triangle.frag.spv")).unwrap();
+
+        assert_eq!(reflection.entry_points.len(), 1);
+        let entry = &reflection.entry_points[0];
+        assert_eq!(entry.name, "main");
+        assert_eq!(entry.execution_model, ExecutionModel::Fragment);
+        assert!(entry.inputs.is_empty());
+        assert_eq!(
+            entry.outputs,
+            [InterfaceVariable {
+                // the checked-in module is stripped of debug names
+                name: None,
+                location: 0,
+                component: 0,
+                ty: FLOAT4,
+            }]
+        );
+    }
+
+    #[test]
+    fn reflects_descriptor_bindings_and_push_constants() {
+        let words = module(&[
+            &name(20, "textures"),
+            &[op::DECORATE as u32, 20, decoration::DESCRIPTOR_SET, 1],
+            &[op::DECORATE as u32, 20, decoration::BINDING, 3],
+            &[op::DECORATE as u32, 30, decoration::BLOCK],
+            &[op::MEMBER_DECORATE as u32, 30, 0, decoration::OFFSET, 0],
+            &[op::MEMBER_DECORATE as u32, 30, 1, decoration::OFFSET, 16],
+            &[op::TYPE_FLOAT as u32, 2, 32],
+            &[op::TYPE_VECTOR as u32, 3, 2, 4],
+            &[op::TYPE_INT as u32, 4, 32, 0],
+            &[op::CONSTANT as u32, 4, 5, 8],
+            &[op::TYPE_IMAGE as u32, 6, 2, 1, 0, 0, 0, 1, 0],
+            &[op::TYPE_SAMPLED_IMAGE as u32, 7, 6],
+            &[op::TYPE_ARRAY as u32, 8, 7, 5],
+            &[
+                op::TYPE_POINTER as u32,
+                9,
+                storage_class::UNIFORM_CONSTANT,
+                8,
+            ],
+            &[op::VARIABLE as u32, 9, 20, storage_class::UNIFORM_CONSTANT],
+            &[op::TYPE_STRUCT as u32, 30, 3, 2],
+            &[
+                op::TYPE_POINTER as u32,
+                31,
+                storage_class::PUSH_CONSTANT,
+                30,
+            ],
+            &[op::VARIABLE as u32, 31, 32, storage_class::PUSH_CONSTANT],
+        ]);
+
+        let reflection = ShaderReflection::from_words(&words).unwrap();
+
+        assert_eq!(
+            reflection.descriptor_bindings,
+            [DescriptorBinding {
+                name: Some("textures".into()),
+                set: 1,
+                binding: 3,
+                descriptor_type: DescriptorType::CombinedImageSampler,
+                count: 8,
+            }]
+        );
+        assert_eq!(reflection.push_constant_size, Some(20));
+    }
+
+    #[test]
+    fn reports_inputs_packed_into_one_location() {
+        let words = module(&[
+            &entry_point(0, "main", &[11, 10]),
+            &[op::DECORATE as u32, 10, decoration::LOCATION, 2],
+            &[op::DECORATE as u32, 11, decoration::LOCATION, 2],
+            &[op::DECORATE as u32, 11, decoration::COMPONENT, 3],
+            &[op::TYPE_FLOAT as u32, 2, 32],
+            &[op::TYPE_VECTOR as u32, 3, 2, 3],
+            &[op::TYPE_POINTER as u32, 4, storage_class::INPUT, 3],
+            &[op::TYPE_POINTER as u32, 5, storage_class::INPUT, 2],
+            &[op::VARIABLE as u32, 4, 10, storage_class::INPUT],
+            &[op::VARIABLE as u32, 5, 11, storage_class::INPUT],
+        ]);
+
+        let reflection = ShaderReflection::from_words(&words).unwrap();
+        let inputs = &reflection.entry_point("main").unwrap().inputs;
+
+        let placement: Vec<_> = inputs
+            .iter()
+            .map(|input| (input.location, input.component, input.ty.components))
+            .collect();
+        assert_eq!(placement, [(2, 0, 3), (2, 3, 1)]);
+    }
+
+    #[test]
+    fn rejects_truncated_modules() {
+        assert_eq!(
+            ShaderReflection::from_words(&[super::super::MAGIC_NUMBER, 0x0001_0000]),
+            Err(SpirvError::Truncated)
+        );
+        assert_eq!(
+            ShaderReflection::parse(&[0; 6]),
+            Err(SpirvError::InvalidLength(6))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_magic() {
+        let mut words = module(&[]);
+        words[0] = 0xdead_beef;
+
+        assert_eq!(
+            ShaderReflection::from_words(&words),
+            Err(SpirvError::InvalidMagic(0xdead_beef))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_instructions() {
+        let mut words = module(&[&[op::TYPE_BOOL as u32, 2]]);
+        words.push(0);
+        assert_eq!(
+            ShaderReflection::from_words(&words),
+            Err(SpirvError::MalformedInstruction(7))
+        );
+
+        // the last instruction claims more words than remain
+        let mut words = module(&[&[op::TYPE_FLOAT as u32, 2, 32]]);
+        words.pop();
+        assert_eq!(
+            ShaderReflection::from_words(&words),
+            Err(SpirvError::MalformedInstruction(5))
+        );
+    }
+
+    #[test]
+    fn rejects_cyclic_types() {
+        let words = module(&[
+            &[op::TYPE_INT as u32, 2, 32, 0],
+            &[op::CONSTANT as u32, 2, 3, 1],
+            &[op::TYPE_ARRAY as u32, 4, 4, 3],
+            &[op::TYPE_POINTER as u32, 5, storage_class::UNIFORM, 4],
+            &[op::VARIABLE as u32, 5, 6, storage_class::UNIFORM],
+        ]);
+
+        assert_eq!(
+            ShaderReflection::from_words(&words),
+            Err(SpirvError::TypeTooDeep(4))
+        );
+    }
+
+    #[test]
+    fn rejects_overflowing_type_sizes() {
+        let words = module(&[
+            &[op::TYPE_INT as u32, 2, 32, 0],
+            &[op::CONSTANT as u32, 2, 3, u32::MAX],
+            &[op::TYPE_ARRAY as u32, 4, 2, 3],
+            &[op::TYPE_STRUCT as u32, 5, 4],
+            &[op::TYPE_POINTER as u32, 6, storage_class::PUSH_CONSTANT, 5],
+            &[op::VARIABLE as u32, 6, 7, storage_class::PUSH_CONSTANT],
+        ]);
+
+        assert_eq!(
+            ShaderReflection::from_words(&words),
+            Err(SpirvError::TypeSizeOverflow(4))
+        );
+    }
+
+    #[test]
+    fn rejects_arrays_sized_by_spec_constants() {
+        let words = module(&[
+            &[op::TYPE_SAMPLER as u32, 2],
+            &[op::TYPE_INT as u32, 3, 32, 0],
+            &[op::SPEC_CONSTANT as u32, 3, 4, 2],
+            &[op::TYPE_ARRAY as u32, 5, 2, 4],
+            &[
+                op::TYPE_POINTER as u32,
+                6,
+                storage_class::UNIFORM_CONSTANT,
+                5,
+            ],
+            &[op::VARIABLE as u32, 6, 7, storage_class::UNIFORM_CONSTANT],
+        ]);
+
+        assert_eq!(
+            ShaderReflection::from_words(&words),
+            Err(SpirvError::SpecConstantArrayLength(5))
+        );
+    }
+}
@@ -0,0 +1,505 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use super::{Reader, Texture, TextureError};
+use crate::{Extent2D, Format};
+
+pub(super) const MAGIC: &[u8; 4] = b"DDS ";
+
+const HEADER_SIZE: u32 = 124;
+
+const DDSD_DEPTH: u32 = 0x800000;
+const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+const DDPF_LUMINANCE: u32 = 0x20000;
+
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_CUBEMAP_ALL_FACES: u32 = 0xFC00;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+
+const RESOURCE_DIMENSION_TEXTURE1D: u32 = 2;
+const RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+const RESOURCE_DIMENSION_TEXTURE3D: u32 = 4;
+
+const RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+
+/// Parses a DDS file, with or without the DX10 header extension. Layers hold their mip levels
+/// back to back, and cubes their faces as consecutive layers.
+pub(super) fn parse(bytes: &[u8]) -> Result<Texture, TextureError> {
+    let mut reader = Reader::new(bytes);
+
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err(TextureError::UnknownContainer);
+    }
+
+    if reader.u32()? != HEADER_SIZE {
+        return Err(TextureError::Invalid("unexpected DDS header size"));
+    }
+
+    let flags = reader.u32()?;
+    let height = reader.u32()?;
+    let width = reader.u32()?;
+    let _pitch_or_linear_size = reader.u32()?;
+    let depth = reader.u32()?;
+    let mip_map_count = reader.u32()?;
+    reader.skip(11 * 4)?;
+
+    let _pixel_format_size = reader.u32()?;
+    let pixel_format_flags = reader.u32()?;
+    let four_cc = reader.u32()?;
+    let rgb_bit_count = reader.u32()?;
+    let masks = [reader.u32()?, reader.u32()?, reader.u32()?, reader.u32()?];
+
+    let _caps = reader.u32()?;
+    let caps2 = reader.u32()?;
+    reader.skip(3 * 4)?;
+
+    let mip_levels = match flags & DDSD_MIPMAPCOUNT {
+        0 => 1,
+        _ => mip_map_count.max(1),
+    };
+
+    let (format, array_layers, cube) = if pixel_format_flags & DDPF_FOURCC != 0
+        && &four_cc.to_le_bytes() == b"DX10"
+    {
+        let dxgi_format = reader.u32()?;
+        let dimension = reader.u32()?;
+        let misc_flags = reader.u32()?;
+        let array_size = reader.u32()?;
+        let _misc_flags2 = reader.u32()?;
+
+        match dimension {
+            RESOURCE_DIMENSION_TEXTURE1D | RESOURCE_DIMENSION_TEXTURE2D => {}
+            RESOURCE_DIMENSION_TEXTURE3D => return Err(TextureError::Unsupported("3D textures")),
+            _ => return Err(TextureError::Invalid("unknown DX10 resource dimension")),
+        }
+
+        let format = format_from_dxgi(dxgi_format)
+            .ok_or_else(|| TextureError::UnsupportedFormat(format!("DXGI format {dxgi_format}")))?;
+
+        let cube = misc_flags & RESOURCE_MISC_TEXTURECUBE != 0;
+        let faces = if cube { 6 } else { 1 };
+
+        let array_layers = array_size
+            .max(1)
+            .checked_mul(faces)
+            .ok_or(TextureError::Invalid("too many array layers"))?;
+
+        (format, array_layers, cube)
+    } else {
+        if caps2 & DDSCAPS2_VOLUME != 0 || (flags & DDSD_DEPTH != 0 && depth > 1) {
+            return Err(TextureError::Unsupported("3D textures"));
+        }
+
+        let cube = caps2 & DDSCAPS2_CUBEMAP != 0;
+        if cube && caps2 & DDSCAPS2_CUBEMAP_ALL_FACES != DDSCAPS2_CUBEMAP_ALL_FACES {
+            return Err(TextureError::Unsupported("cube maps missing faces"));
+        }
+
+        let format = legacy_format(pixel_format_flags, four_cc, rgb_bit_count, masks)?;
+
+        (format, if cube { 6 } else { 1 }, cube)
+    };
+
+    let mut texture = Texture::new(
+        format,
+        Extent2D {
+            width,
+            height: height.max(1),
+        },
+        mip_levels,
+        array_layers,
+        cube,
+    )?;
+
+    let mut offset = reader.offset as u64;
+    for layer in 0..array_layers {
+        for level in 0..mip_levels {
+            offset += texture.push_subresource(bytes, offset, level, layer)?;
+        }
+    }
+
+    Ok(texture)
+}
+
+/// Maps the pixel formats of files without the DX10 header.
+fn legacy_format(
+    flags: u32,
+    four_cc: u32,
+    bit_count: u32,
+    [r, g, b, a]: [u32; 4],
+) -> Result<Format, TextureError> {
+    if flags & DDPF_FOURCC != 0 {
+        let format = match four_cc {
+            // D3DFORMAT values stored in place of a FourCC
+            36 => Some(Format::R16G16B16A16_UNORM),
+            110 => Some(Format::R16G16B16A16_SNORM),
+            113 => Some(Format::R16G16B16A16_SFLOAT),
+            114 => Some(Format::R32_SFLOAT),
+            115 => Some(Format::R32G32_SFLOAT),
+            116 => Some(Format::R32G32B32A32_SFLOAT),
+            _ => match &four_cc.to_le_bytes() {
+                b"DXT1" => Some(Format::BC1_RGBA_UNORM),
+                b"DXT2" | b"DXT3" => Some(Format::BC2_UNORM),
+                b"DXT4" | b"DXT5" => Some(Format::BC3_UNORM),
+                b"ATI1" | b"BC4U" => Some(Format::BC4_UNORM),
+                b"BC4S" => Some(Format::BC4_SNORM),
+                b"ATI2" | b"BC5U" => Some(Format::BC5_UNORM),
+                b"BC5S" => Some(Format::BC5_SNORM),
+                _ => None,
+            },
+        };
+
+        return format.ok_or_else(|| {
+            let code = four_cc.to_le_bytes();
+            TextureError::UnsupportedFormat(match code.iter().all(u8::is_ascii_graphic) {
+                true => format!("DDS FourCC '{}'", String::from_utf8_lossy(&code)),
+                false => format!("DDS FourCC {four_cc}"),
+            })
+        });
+    }
+
+    if flags & (DDPF_RGB | DDPF_LUMINANCE) == 0 {
+        return Err(TextureError::UnsupportedFormat(format!(
+            "DDS pixel format flags {flags:#x}"
+        )));
+    }
+
+    // the alpha mask is only meaningful with the flag
+    let a = if flags & DDPF_ALPHAPIXELS != 0 { a } else { 0 };
+
+    let format = match (bit_count, r, g, b, a) {
+        (32, 0xFF, 0xFF00, 0xFF0000, 0xFF000000) => Format::R8G8B8A8_UNORM,
+        (32, 0xFF0000, 0xFF00, 0xFF, 0xFF000000) => Format::B8G8R8A8_UNORM,
+        (32, 0x3FF, 0xFFC00, 0x3FF00000, 0xC0000000) => Format::R10G10B10A2_UNORM,
+        (32, 0xFFFF, 0xFFFF0000, 0, 0) => Format::R16G16_UNORM,
+        (16, 0xFF, 0xFF00, 0, 0) => Format::R8G8_UNORM,
+        (16, 0xFFFF, 0, 0, 0) => Format::R16_UNORM,
+        (8, 0xFF, 0, 0, 0) => Format::R8_UNORM,
+        _ => {
+            return Err(TextureError::UnsupportedFormat(format!(
+                "DDS {bit_count} bit pixels with masks {r:#x} {g:#x} {b:#x} {a:#x}"
+            )))
+        }
+    };
+
+    Ok(format)
+}
+
+/// Maps a `DXGI_FORMAT` value, which DDS uses whatever the backend.
+fn format_from_dxgi(dxgi_format: u32) -> Option<Format> {
+    Some(match dxgi_format {
+        2 => Format::R32G32B32A32_SFLOAT,
+        3 => Format::R32G32B32A32_UINT,
+        4 => Format::R32G32B32A32_SINT,
+        6 => Format::R32G32B32_SFLOAT,
+        7 => Format::R32G32B32_UINT,
+        8 => Format::R32G32B32_SINT,
+        10 => Format::R16G16B16A16_SFLOAT,
+        11 => Format::R16G16B16A16_UNORM,
+        12 => Format::R16G16B16A16_UINT,
+        13 => Format::R16G16B16A16_SNORM,
+        14 => Format::R16G16B16A16_SINT,
+        16 => Format::R32G32_SFLOAT,
+        17 => Format::R32G32_UINT,
+        18 => Format::R32G32_SINT,
+        24 => Format::R10G10B10A2_UNORM,
+        25 => Format::R10G10B10A2_UINT,
+        26 => Format::B10G11R11_UFLOAT,
+        28 => Format::R8G8B8A8_UNORM,
+        29 => Format::R8G8B8A8_UNORM_SRGB,
+        30 => Format::R8G8B8A8_UINT,
+        31 => Format::R8G8B8A8_SNORM,
+        32 => Format::R8G8B8A8_SINT,
+        35 => Format::R16G16_UNORM,
+        36 => Format::R16G16_UINT,
+        37 => Format::R16G16_SNORM,
+        38 => Format::R16G16_SINT,
+        41 => Format::R32_SFLOAT,
+        42 => Format::R32_UINT,
+        43 => Format::R32_SINT,
+        49 => Format::R8G8_UNORM,
+        50 => Format::R8G8_UINT,
+        51 => Format::R8G8_SNORM,
+        52 => Format::R8G8_SINT,
+        56 => Format::R16_UNORM,
+        57 => Format::R16_UINT,
+        58 => Format::R16_SNORM,
+        59 => Format::R16_SINT,
+        61 => Format::R8_UNORM,
+        62 => Format::R8_UINT,
+        63 => Format::R8_SNORM,
+        64 => Format::R8_SINT,
+        67 => Format::E5B9G9R9_UFLOAT,
+        71 => Format::BC1_RGBA_UNORM,
+        72 => Format::BC1_RGBA_UNORM_SRGB,
+        74 => Format::BC2_UNORM,
+        75 => Format::BC2_UNORM_SRGB,
+        77 => Format::BC3_UNORM,
+        78 => Format::BC3_UNORM_SRGB,
+        80 => Format::BC4_UNORM,
+        81 => Format::BC4_SNORM,
+        83 => Format::BC5_UNORM,
+        84 => Format::BC5_SNORM,
+        87 => Format::B8G8R8A8_UNORM,
+        91 => Format::B8G8R8A8_UNORM_SRGB,
+        95 => Format::BC6H_UFLOAT,
+        96 => Format::BC6H_SFLOAT,
+        98 => Format::BC7_UNORM,
+        99 => Format::BC7_UNORM_SRGB,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DDSD_CAPS: u32 = 0x1;
+    const DXGI_FORMAT_R8G8B8A8_UNORM: u32 = 28;
+
+    /// The fields of a DDS header that describe the image.
+    #[derive(Clone, Copy)]
+    struct Header {
+        flags: u32,
+        width: u32,
+        height: u32,
+        depth: u32,
+        mip_map_count: u32,
+        pixel_format_flags: u32,
+        four_cc: [u8; 4],
+        rgb_bit_count: u32,
+        masks: [u32; 4],
+        caps2: u32,
+    }
+
+    /// 32 bit RGBA pixels.
+    const HEADER: Header = Header {
+        flags: DDSD_CAPS,
+        width: 4,
+        height: 4,
+        depth: 0,
+        mip_map_count: 0,
+        pixel_format_flags: DDPF_RGB | DDPF_ALPHAPIXELS,
+        four_cc: [0; 4],
+        rgb_bit_count: 32,
+        masks: [0xFF, 0xFF00, 0xFF0000, 0xFF000000],
+        caps2: 0,
+    };
+
+    /// The DX10 header extension.
+    #[derive(Clone, Copy)]
+    struct Dx10 {
+        dxgi_format: u32,
+        dimension: u32,
+        misc_flags: u32,
+        array_size: u32,
+    }
+
+    const DX10: Dx10 = Dx10 {
+        dxgi_format: DXGI_FORMAT_R8G8B8A8_UNORM,
+        dimension: RESOURCE_DIMENSION_TEXTURE2D,
+        misc_flags: 0,
+        array_size: 1,
+    };
+
+    /// A file holding `data_size` bytes of data after its headers, each holding its index.
+    fn file(header: Header, dx10: Option<Dx10>, data_size: usize) -> Vec<u8> {
+        let mut fields = vec![
+            HEADER_SIZE,
+            header.flags,
+            header.height,
+            header.width,
+            0,
+            header.depth,
+            header.mip_map_count,
+        ];
+        fields.extend([0; 11]);
+        fields.extend([
+            32,
+            header.pixel_format_flags,
+            u32::from_le_bytes(header.four_cc),
+            header.rgb_bit_count,
+        ]);
+        fields.extend(header.masks);
+        fields.extend([0x1000, header.caps2, 0, 0, 0]);
+
+        if let Some(dx10) = dx10 {
+            fields.extend([
+                dx10.dxgi_format,
+                dx10.dimension,
+                dx10.misc_flags,
+                dx10.array_size,
+                0,
+            ]);
+        }
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(fields.iter().flat_map(|field| field.to_le_bytes()));
+        bytes.extend((0..data_size).map(|index| index as u8));
+        bytes
+    }
+
+    fn dx10_header() -> Header {
+        Header {
+            pixel_format_flags: DDPF_FOURCC,
+            four_cc: *b"DX10",
+            rgb_bit_count: 0,
+            masks: [0; 4],
+            ..HEADER
+        }
+    }
+
+    fn subresources(texture: &Texture) -> Vec<(u32, u32, u64)> {
+        texture
+            .regions
+            .iter()
+            .map(|region| {
+                (
+                    region.mip_level,
+                    region.base_array_layer,
+                    region.buffer_offset,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_legacy_cube_maps_with_mip_levels() {
+        let header = Header {
+            flags: DDSD_CAPS | DDSD_MIPMAPCOUNT,
+            mip_map_count: 3,
+            caps2: DDSCAPS2_CUBEMAP | DDSCAPS2_CUBEMAP_ALL_FACES,
+            ..HEADER
+        };
+        // 4x4, 2x2 and 1x1 per face
+        let bytes = file(header, None, 6 * (64 + 16 + 4));
+        let texture = parse(&bytes).unwrap();
+
+        assert_eq!(texture.format, Format::R8G8B8A8_UNORM);
+        assert_eq!((texture.mip_levels, texture.array_layers), (3, 6));
+        assert!(texture.cube);
+
+        // each face holds its mip levels back to back
+        let expected: Vec<_> = (0..6)
+            .flat_map(|face| {
+                let start = face as u64 * 84;
+                [
+                    (0, face, start),
+                    (1, face, start + 64),
+                    (2, face, start + 80),
+                ]
+            })
+            .collect();
+        assert_eq!(subresources(&texture), expected);
+        assert_eq!(texture.data[..], bytes[128..]);
+    }
+
+    #[test]
+    fn reads_legacy_four_cc_formats() {
+        let header = Header {
+            pixel_format_flags: DDPF_FOURCC,
+            four_cc: *b"DXT1",
+            ..HEADER
+        };
+        // a single 8 byte block
+        let texture = parse(&file(header, None, 8)).unwrap();
+
+        assert_eq!(texture.format, Format::BC1_RGBA_UNORM);
+        assert_eq!(texture.data.len(), 8);
+    }
+
+    #[test]
+    fn reads_dx10_arrays_and_cubes() {
+        let array = Dx10 {
+            array_size: 3,
+            ..DX10
+        };
+        let texture = parse(&file(dx10_header(), Some(array), 3 * 64)).unwrap();
+
+        assert_eq!(texture.array_layers, 3);
+        assert!(!texture.cube);
+        assert_eq!(subresources(&texture), [(0, 0, 0), (0, 1, 64), (0, 2, 128)]);
+
+        let cubes = Dx10 {
+            misc_flags: RESOURCE_MISC_TEXTURECUBE,
+            array_size: 2,
+            ..DX10
+        };
+        let texture = parse(&file(dx10_header(), Some(cubes), 12 * 64)).unwrap();
+
+        assert_eq!(texture.array_layers, 12);
+        assert!(texture.cube);
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let bytes = file(HEADER, None, 63);
+        assert_eq!(parse(&bytes).unwrap_err(), TextureError::Truncated);
+
+        assert_eq!(parse(&bytes[..100]).unwrap_err(), TextureError::Truncated);
+
+        let bytes = file(dx10_header(), Some(DX10), 64);
+        assert_eq!(parse(&bytes[..140]).unwrap_err(), TextureError::Truncated);
+    }
+
+    #[test]
+    fn rejects_3d_textures() {
+        let volume = Header {
+            caps2: DDSCAPS2_VOLUME,
+            ..HEADER
+        };
+        assert_eq!(
+            parse(&file(volume, None, 64)).unwrap_err(),
+            TextureError::Unsupported("3D textures")
+        );
+
+        let volume = Dx10 {
+            dimension: RESOURCE_DIMENSION_TEXTURE3D,
+            ..DX10
+        };
+        assert_eq!(
+            parse(&file(dx10_header(), Some(volume), 64)).unwrap_err(),
+            TextureError::Unsupported("3D textures")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        let unknown = Dx10 {
+            dxgi_format: 1000,
+            ..DX10
+        };
+        assert_eq!(
+            parse(&file(dx10_header(), Some(unknown), 64)).unwrap_err(),
+            TextureError::UnsupportedFormat("DXGI format 1000".to_owned())
+        );
+
+        let unknown = Header {
+            pixel_format_flags: DDPF_FOURCC,
+            four_cc: *b"ABCD",
+            ..HEADER
+        };
+        assert_eq!(
+            parse(&file(unknown, None, 64)).unwrap_err(),
+            TextureError::UnsupportedFormat("DDS FourCC 'ABCD'".to_owned())
+        );
+    }
+
+    #[test]
+    fn rejects_overflowing_layer_counts() {
+        let cubes = Dx10 {
+            misc_flags: RESOURCE_MISC_TEXTURECUBE,
+            array_size: u32::MAX,
+            ..DX10
+        };
+
+        assert_eq!(
+            parse(&file(dx10_header(), Some(cubes), 64)).unwrap_err(),
+            TextureError::Invalid("too many array layers")
+        );
+    }
+}
@@ -0,0 +1,383 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use super::{Reader, Texture, TextureError};
+use crate::{Extent2D, Format};
+
+pub(super) const IDENTIFIER: [u8; 12] = [
+    0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+];
+
+/// Parses a KTX2 file. Mip levels hold their layers, and each layer its faces, back to back.
+pub(super) fn parse(bytes: &[u8]) -> Result<Texture, TextureError> {
+    let mut reader = Reader::new(bytes);
+
+    if reader.bytes(IDENTIFIER.len())? != IDENTIFIER {
+        return Err(TextureError::UnknownContainer);
+    }
+
+    let vk_format = reader.u32()?;
+    let _type_size = reader.u32()?;
+    let width = reader.u32()?;
+    let height = reader.u32()?;
+    let depth = reader.u32()?;
+    let layer_count = reader.u32()?;
+    let face_count = reader.u32()?;
+    let level_count = reader.u32()?;
+    let supercompression = reader.u32()?;
+
+    // data format descriptor, key/value data and supercompression global data
+    reader.skip(4 * 4 + 8 * 2)?;
+
+    match supercompression {
+        0 => {}
+        1 => return Err(TextureError::Unsupported("BasisLZ supercompression")),
+        2 => return Err(TextureError::Unsupported("Zstandard supercompression")),
+        3 => return Err(TextureError::Unsupported("ZLIB supercompression")),
+        _ => return Err(TextureError::Unsupported("unknown supercompression scheme")),
+    }
+
+    if vk_format == 0 {
+        return Err(TextureError::UnsupportedFormat(
+            "KTX2 without a vkFormat, e.g. Basis Universal".to_owned(),
+        ));
+    }
+
+    if depth > 1 {
+        return Err(TextureError::Unsupported("3D textures"));
+    }
+
+    let cube = match face_count {
+        1 => false,
+        6 => true,
+        _ => return Err(TextureError::Invalid("face count must be 1 or 6")),
+    };
+
+    let format = format_from_vk(vk_format)
+        .ok_or_else(|| TextureError::UnsupportedFormat(format!("vkFormat {vk_format}")))?;
+
+    // 0 asks for mips to be generated, which isn't supported, so only the base level is loaded
+    let mip_levels = level_count.max(1);
+    let array_layers = layer_count
+        .max(1)
+        .checked_mul(face_count)
+        .ok_or(TextureError::Invalid("too many array layers"))?;
+
+    let mut texture = Texture::new(
+        format,
+        Extent2D {
+            width,
+            // 1D textures have no height
+            height: height.max(1),
+        },
+        mip_levels,
+        array_layers,
+        cube,
+    )?;
+
+    let levels = (0..mip_levels)
+        .map(|_| {
+            let offset = reader.u64()?;
+            let length = reader.u64()?;
+            let _uncompressed_length = reader.u64()?;
+            Ok((offset, length))
+        })
+        .collect::<Result<Vec<_>, TextureError>>()?;
+
+    for (level, (offset, length)) in (0..mip_levels).zip(levels) {
+        let size = texture.subresource_size(level);
+
+        if size.checked_mul(array_layers as u64) != Some(length) {
+            return Err(TextureError::Invalid(
+                "a mip level's size doesn't match its format and extent",
+            ));
+        }
+
+        for layer in 0..array_layers {
+            // the length check bounds layer * size, but not the offset
+            let offset = offset
+                .checked_add(layer as u64 * size)
+                .ok_or(TextureError::Invalid("mip level offset out of range"))?;
+
+            texture.push_subresource(bytes, offset, level, layer)?;
+        }
+    }
+
+    Ok(texture)
+}
+
+/// Maps a `VkFormat` value, which KTX2 uses whatever the backend.
+fn format_from_vk(vk_format: u32) -> Option<Format> {
+    Some(match vk_format {
+        9 => Format::R8_UNORM,
+        10 => Format::R8_SNORM,
+        13 => Format::R8_UINT,
+        14 => Format::R8_SINT,
+        16 => Format::R8G8_UNORM,
+        17 => Format::R8G8_SNORM,
+        20 => Format::R8G8_UINT,
+        21 => Format::R8G8_SINT,
+        37 => Format::R8G8B8A8_UNORM,
+        38 => Format::R8G8B8A8_SNORM,
+        41 => Format::R8G8B8A8_UINT,
+        42 => Format::R8G8B8A8_SINT,
+        43 => Format::R8G8B8A8_UNORM_SRGB,
+        44 => Format::B8G8R8A8_UNORM,
+        50 => Format::B8G8R8A8_UNORM_SRGB,
+        64 => Format::R10G10B10A2_UNORM,
+        68 => Format::R10G10B10A2_UINT,
+        70 => Format::R16_UNORM,
+        71 => Format::R16_SNORM,
+        74 => Format::R16_UINT,
+        75 => Format::R16_SINT,
+        77 => Format::R16G16_UNORM,
+        78 => Format::R16G16_SNORM,
+        81 => Format::R16G16_UINT,
+        82 => Format::R16G16_SINT,
+        91 => Format::R16G16B16A16_UNORM,
+        92 => Format::R16G16B16A16_SNORM,
+        95 => Format::R16G16B16A16_UINT,
+        96 => Format::R16G16B16A16_SINT,
+        97 => Format::R16G16B16A16_SFLOAT,
+        98 => Format::R32_UINT,
+        99 => Format::R32_SINT,
+        100 => Format::R32_SFLOAT,
+        101 => Format::R32G32_UINT,
+        102 => Format::R32G32_SINT,
+        103 => Format::R32G32_SFLOAT,
+        104 => Format::R32G32B32_UINT,
+        105 => Format::R32G32B32_SINT,
+        106 => Format::R32G32B32_SFLOAT,
+        107 => Format::R32G32B32A32_UINT,
+        108 => Format::R32G32B32A32_SINT,
+        109 => Format::R32G32B32A32_SFLOAT,
+        122 => Format::B10G11R11_UFLOAT,
+        123 => Format::E5B9G9R9_UFLOAT,
+        133 => Format::BC1_RGBA_UNORM,
+        134 => Format::BC1_RGBA_UNORM_SRGB,
+        135 => Format::BC2_UNORM,
+        136 => Format::BC2_UNORM_SRGB,
+        137 => Format::BC3_UNORM,
+        138 => Format::BC3_UNORM_SRGB,
+        139 => Format::BC4_UNORM,
+        140 => Format::BC4_SNORM,
+        141 => Format::BC5_UNORM,
+        142 => Format::BC5_SNORM,
+        143 => Format::BC6H_UFLOAT,
+        144 => Format::BC6H_SFLOAT,
+        145 => Format::BC7_UNORM,
+        146 => Format::BC7_UNORM_SRGB,
+        147 => Format::ETC2_R8G8B8_UNORM,
+        148 => Format::ETC2_R8G8B8_UNORM_SRGB,
+        149 => Format::ETC2_R8G8B8A1_UNORM,
+        150 => Format::ETC2_R8G8B8A1_UNORM_SRGB,
+        151 => Format::ETC2_R8G8B8A8_UNORM,
+        152 => Format::ETC2_R8G8B8A8_UNORM_SRGB,
+        153 => Format::EAC_R11_UNORM,
+        154 => Format::EAC_R11_SNORM,
+        155 => Format::EAC_R11G11_UNORM,
+        156 => Format::EAC_R11G11_SNORM,
+        157 => Format::ASTC_4x4_UNORM,
+        158 => Format::ASTC_4x4_UNORM_SRGB,
+        159 => Format::ASTC_5x4_UNORM,
+        160 => Format::ASTC_5x4_UNORM_SRGB,
+        161 => Format::ASTC_5x5_UNORM,
+        162 => Format::ASTC_5x5_UNORM_SRGB,
+        163 => Format::ASTC_6x5_UNORM,
+        164 => Format::ASTC_6x5_UNORM_SRGB,
+        165 => Format::ASTC_6x6_UNORM,
+        166 => Format::ASTC_6x6_UNORM_SRGB,
+        167 => Format::ASTC_8x5_UNORM,
+        168 => Format::ASTC_8x5_UNORM_SRGB,
+        169 => Format::ASTC_8x6_UNORM,
+        170 => Format::ASTC_8x6_UNORM_SRGB,
+        171 => Format::ASTC_8x8_UNORM,
+        172 => Format::ASTC_8x8_UNORM_SRGB,
+        173 => Format::ASTC_10x5_UNORM,
+        174 => Format::ASTC_10x5_UNORM_SRGB,
+        175 => Format::ASTC_10x6_UNORM,
+        176 => Format::ASTC_10x6_UNORM_SRGB,
+        177 => Format::ASTC_10x8_UNORM,
+        178 => Format::ASTC_10x8_UNORM_SRGB,
+        179 => Format::ASTC_10x10_UNORM,
+        180 => Format::ASTC_10x10_UNORM_SRGB,
+        181 => Format::ASTC_12x10_UNORM,
+        182 => Format::ASTC_12x10_UNORM_SRGB,
+        183 => Format::ASTC_12x12_UNORM,
+        184 => Format::ASTC_12x12_UNORM_SRGB,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RGBA8: u32 = 37;
+
+    /// The fields of a KTX2 header that describe the image.
+    #[derive(Clone, Copy)]
+    struct Header {
+        vk_format: u32,
+        width: u32,
+        height: u32,
+        depth: u32,
+        layers: u32,
+        faces: u32,
+        levels: u32,
+        supercompression: u32,
+    }
+
+    const HEADER: Header = Header {
+        vk_format: RGBA8,
+        width: 4,
+        height: 4,
+        depth: 0,
+        layers: 0,
+        faces: 1,
+        levels: 1,
+        supercompression: 0,
+    };
+
+    /// A file with the level index and data for `header`, each byte of the data holding its
+    /// index.
+    fn file(header: Header) -> Vec<u8> {
+        let mut bytes = IDENTIFIER.to_vec();
+
+        for value in [
+            header.vk_format,
+            1,
+            header.width,
+            header.height,
+            header.depth,
+            header.layers,
+            header.faces,
+            header.levels,
+            header.supercompression,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.resize(bytes.len() + 4 * 4 + 8 * 2, 0);
+
+        let levels = header.levels.max(1);
+        let layers = header.layers.max(1) * header.faces;
+        let lengths: Vec<u64> = (0..levels)
+            .map(|level| {
+                let width = (header.width >> level).max(1) as u64;
+                let height = (header.height.max(1) >> level).max(1) as u64;
+                width * height * 4 * layers as u64
+            })
+            .collect();
+
+        let mut offset = (bytes.len() + 24 * levels as usize) as u64;
+        for length in &lengths {
+            for value in [offset, *length, *length] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            offset += length;
+        }
+
+        let data_size: u64 = lengths.iter().sum();
+        bytes.extend((0..data_size).map(|index| index as u8));
+        bytes
+    }
+
+    #[test]
+    fn reads_mip_levels_of_cube_faces() {
+        let bytes = file(Header {
+            levels: 2,
+            faces: 6,
+            ..HEADER
+        });
+        let texture = parse(&bytes).unwrap();
+
+        assert_eq!(texture.format, Format::R8G8B8A8_UNORM);
+        assert_eq!((texture.mip_levels, texture.array_layers), (2, 6));
+        assert!(texture.cube);
+
+        // each level holds its faces back to back
+        let subresources: Vec<_> = texture
+            .regions
+            .iter()
+            .map(|region| {
+                (
+                    region.mip_level,
+                    region.base_array_layer,
+                    region.buffer_offset,
+                )
+            })
+            .collect();
+        let expected: Vec<_> = (0..6)
+            .map(|face| (0, face, face as u64 * 64))
+            .chain((0..6).map(|face| (1, face, 6 * 64 + face as u64 * 16)))
+            .collect();
+        assert_eq!(subresources, expected);
+
+        let extent = texture.regions[7].image_extent;
+        assert_eq!((extent.width, extent.height), (2, 2));
+        assert_eq!(texture.data.len(), 6 * (64 + 16));
+        assert_eq!(texture.data[..], bytes[bytes.len() - texture.data.len()..]);
+    }
+
+    #[test]
+    fn reads_array_layers() {
+        let texture = parse(&file(Header {
+            layers: 3,
+            ..HEADER
+        }))
+        .unwrap();
+
+        assert_eq!(texture.array_layers, 3);
+        assert!(!texture.cube);
+        assert_eq!(texture.regions.len(), 3);
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let mut bytes = file(HEADER);
+        bytes.pop();
+        assert_eq!(parse(&bytes).unwrap_err(), TextureError::Truncated);
+
+        assert_eq!(parse(&bytes[..40]).unwrap_err(), TextureError::Truncated);
+    }
+
+    #[test]
+    fn rejects_supercompression_and_3d_textures() {
+        let zstd = file(Header {
+            supercompression: 2,
+            ..HEADER
+        });
+        assert!(matches!(parse(&zstd), Err(TextureError::Unsupported(_))));
+
+        let volume = file(Header { depth: 4, ..HEADER });
+        assert_eq!(
+            parse(&volume).unwrap_err(),
+            TextureError::Unsupported("3D textures")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        let bytes = file(Header {
+            vk_format: 1_000_000,
+            ..HEADER
+        });
+
+        assert!(matches!(
+            parse(&bytes),
+            Err(TextureError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_overflowing_layer_counts() {
+        let mut bytes = file(Header { faces: 6, ..HEADER });
+        // the layer count field
+        bytes[32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert_eq!(
+            parse(&bytes).unwrap_err(),
+            TextureError::Invalid("too many array layers")
+        );
+    }
+}
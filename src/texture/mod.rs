@@ -0,0 +1,287 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+mod dds;
+mod ktx2;
+
+use crate::prelude::*;
+use crate::upload::{UploadDestination, UploadManager};
+use crate::{
+    BufferImageCopy, Context, Extent2D, Format, Image, ImageAspectFlags, ImageCreateInfo,
+    ImageLayout, ImageUsageFlags, MemoryLocation, Offset2D,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TextureError {
+    #[error("not a KTX2 or DDS file")]
+    UnknownContainer,
+
+    #[error("file ends before the data its header describes")]
+    Truncated,
+
+    #[error("malformed file: {0}")]
+    Invalid(&'static str),
+
+    #[error("unsupported format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("unsupported texture: {0}")]
+    Unsupported(&'static str),
+}
+
+/// A 2D, array or cube texture parsed from a container file.
+#[derive(Debug, Clone)]
+pub struct Texture {
+    pub format: Format,
+    pub extent: Extent2D,
+    pub mip_levels: u32,
+    /// Array layers, with six consecutive layers per cube.
+    pub array_layers: u32,
+    pub cube: bool,
+    /// Texels of every subresource.
+    pub data: Vec<u8>,
+    /// A copy per subresource, with `buffer_offset` indexing `data`.
+    pub regions: Vec<BufferImageCopy>,
+}
+
+impl Texture {
+    /// Parses a KTX2 or DDS file, detected by its magic bytes.
+    pub fn parse(bytes: &[u8]) -> Result<Self, TextureError> {
+        if bytes.starts_with(&ktx2::IDENTIFIER) {
+            ktx2::parse(bytes)
+        } else if bytes.starts_with(dds::MAGIC) {
+            dds::parse(bytes)
+        } else {
+            Err(TextureError::UnknownContainer)
+        }
+    }
+
+    pub fn load(path: impl AsRef<std::path::Path>) -> crate::Result<Self> {
+        Ok(Self::parse(&std::fs::read(path)?)?)
+    }
+
+    /// Creates a sampled image for the texture and queues uploads of every subresource, which
+    /// leave it in `ImageLayout::ShaderReadOnly`.
+    ///
+    /// The image can't be used until the uploads are flushed and complete.
+    pub fn create_image(
+        &self,
+        context: &Context,
+        uploads: &mut UploadManager,
+        label: Option<&str>,
+    ) -> crate::Result<Image> {
+        if !self.format.is_supported() {
+            return Err(TextureError::UnsupportedFormat(format!(
+                "{:?} can't be represented by the backend",
+                self.format
+            ))
+            .into());
+        }
+
+        let image = Image::new(
+            context.clone(),
+            ImageCreateInfo {
+                format: self.format,
+                extent: self.extent,
+                mip_levels: self.mip_levels,
+                array_layers: self.array_layers,
+                cube: self.cube,
+                usage: ImageUsageFlags::SAMPLED | ImageUsageFlags::TRANSFER_DST,
+                location: MemoryLocation::DeviceLocal,
+                label,
+            },
+        )?;
+
+        uploads.upload(
+            &self.data,
+            UploadDestination::Image {
+                image: &image,
+                regions: &self.regions,
+                final_layout: ImageLayout::ShaderReadOnly,
+            },
+        )?;
+
+        Ok(image)
+    }
+
+    pub fn mip_extent(&self, level: u32) -> Extent2D {
+        Extent2D {
+            width: (self.extent.width >> level).max(1),
+            height: (self.extent.height >> level).max(1),
+        }
+    }
+
+    /// Size in bytes of one subresource of mip level `level`.
+    pub fn subresource_size(&self, level: u32) -> u64 {
+        self.format.required_bytes(self.mip_extent(level), 1, 1)
+    }
+
+    fn new(
+        format: Format,
+        extent: Extent2D,
+        mip_levels: u32,
+        array_layers: u32,
+        cube: bool,
+    ) -> Result<Self, TextureError> {
+        if extent.width == 0 || extent.height == 0 {
+            return Err(TextureError::Invalid("zero sized texture"));
+        }
+
+        if format.has_depth() || format.has_stencil() {
+            return Err(TextureError::UnsupportedFormat(format!(
+                "{format:?} isn't a color format"
+            )));
+        }
+
+        let max_levels = 32 - extent.width.max(extent.height).leading_zeros();
+        if mip_levels > max_levels {
+            return Err(TextureError::Invalid(
+                "more mip levels than the extent allows",
+            ));
+        }
+
+        Ok(Self {
+            format,
+            extent,
+            mip_levels,
+            array_layers,
+            cube,
+            data: Vec::new(),
+            regions: Vec::new(),
+        })
+    }
+
+    /// Appends the subresource stored at `offset` in `file`, returning its size.
+    fn push_subresource(
+        &mut self,
+        file: &[u8],
+        offset: u64,
+        level: u32,
+        layer: u32,
+    ) -> Result<u64, TextureError> {
+        let size = self.subresource_size(level);
+
+        let bytes = usize::try_from(offset)
+            .ok()
+            .and_then(|start| file.get(start..start.checked_add(size as usize)?))
+            .ok_or(TextureError::Truncated)?;
+
+        self.regions.push(BufferImageCopy {
+            buffer_offset: self.data.len() as u64,
+            aspect: ImageAspectFlags::COLOR,
+            mip_level: level,
+            base_array_layer: layer,
+            layer_count: 1,
+            image_offset: Offset2D { x: 0, y: 0 },
+            image_extent: self.mip_extent(level),
+        });
+        self.data.extend_from_slice(bytes);
+
+        Ok(size)
+    }
+}
+
+/// Reads little endian header fields.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], TextureError> {
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset + len)
+            .ok_or(TextureError::Truncated)?;
+
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, TextureError> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, TextureError> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), TextureError> {
+        self.bytes(len).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extent(width: u32, height: u32) -> Extent2D {
+        Extent2D { width, height }
+    }
+
+    #[test]
+    fn rejects_unknown_containers() {
+        assert_eq!(
+            Texture::parse(b"\x89PNG\r\n\x1a\n").unwrap_err(),
+            TextureError::UnknownContainer
+        );
+        assert_eq!(
+            Texture::parse(&[]).unwrap_err(),
+            TextureError::UnknownContainer
+        );
+    }
+
+    #[test]
+    fn sizes_mip_levels() {
+        let texture = Texture::new(Format::BC1_RGBA_UNORM, extent(10, 3), 4, 1, false).unwrap();
+
+        let extents: Vec<_> = (0..4)
+            .map(|level| {
+                let extent = texture.mip_extent(level);
+                (extent.width, extent.height)
+            })
+            .collect();
+        assert_eq!(extents, [(10, 3), (5, 1), (2, 1), (1, 1)]);
+
+        // 3x1, 2x1 and 1x1 blocks of 8 bytes
+        let sizes: Vec<_> = (0..3)
+            .map(|level| texture.subresource_size(level))
+            .collect();
+        assert_eq!(sizes, [24, 16, 8]);
+    }
+
+    #[test]
+    fn rejects_invalid_descriptions() {
+        assert_eq!(
+            Texture::new(Format::R8_UNORM, extent(0, 4), 1, 1, false).unwrap_err(),
+            TextureError::Invalid("zero sized texture")
+        );
+        assert_eq!(
+            Texture::new(Format::R8_UNORM, extent(4, 4), 4, 1, false).unwrap_err(),
+            TextureError::Invalid("more mip levels than the extent allows")
+        );
+        assert!(matches!(
+            Texture::new(Format::D32_SFLOAT, extent(4, 4), 1, 1, false),
+            Err(TextureError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_subresources_past_the_end_of_the_file() {
+        let mut texture = Texture::new(Format::R8_UNORM, extent(2, 2), 1, 1, false).unwrap();
+
+        assert_eq!(texture.push_subresource(&[0; 6], 2, 0, 0), Ok(4));
+        assert_eq!(
+            texture.push_subresource(&[0; 6], 3, 0, 0),
+            Err(TextureError::Truncated)
+        );
+        assert_eq!(
+            texture.push_subresource(&[0; 6], u64::MAX, 0, 0),
+            Err(TextureError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,857 @@
+// Copyright (c) 2024 Jacob R. Green
+// All rights reserved.
+
+use crate::prelude::*;
+use crate::{
+    AccessFlags, Buffer, BufferBarrier, BufferCopy, BufferCreateInfo, BufferImageCopy,
+    BufferUsageFlags, CommandBuffer, CommandBufferAllocateInfo, CommandPool, CommandPoolCreateInfo,
+    Context, DependencyInfo, Fence, FenceCreateInfo, Image, ImageBarrier, ImageLayout,
+    ImageSubresourceRange, MemoryLocation, Offset2D, PipelineStageFlags, Queue, QueueFlags,
+    Semaphore, SemaphoreCreateInfo, SubmitInfo,
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct UploadManagerCreateInfo {
+    /// Size in bytes of the host visible staging ring. Larger uploads are split.
+    pub ring_size: u64,
+}
+
+impl Default for UploadManagerCreateInfo {
+    fn default() -> Self {
+        Self {
+            ring_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Where uploaded data is copied to.
+#[derive(Debug, Clone)]
+pub enum UploadDestination<'a> {
+    /// Each region's `src_offset` is an offset into the uploaded data.
+    Buffer {
+        buffer: &'a Buffer,
+        regions: &'a [BufferCopy],
+    },
+    /// Each region's `buffer_offset` is an offset into the uploaded data, which holds the
+    /// region's layers one after another, tightly packed.
+    ///
+    /// Every mip level and layer a region copies to has its previous contents discarded and is
+    /// left in `final_layout`. The rest of the image is left untouched.
+    Image {
+        image: &'a Image,
+        regions: &'a [BufferImageCopy],
+        final_layout: ImageLayout,
+    },
+}
+
+/// Uploads flushed together, which can be waited on or polled.
+#[derive(Debug, Clone)]
+pub struct UploadHandle {
+    /// Fences of every batch in flight when the handle was created.
+    fences: Vec<Fence>,
+}
+
+impl UploadHandle {
+    /// Blocks until the uploads complete or `timeout` elapses, returning whether they completed.
+    pub fn wait(&self, timeout: Option<Duration>) -> crate::Result<bool> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        for fence in &self.fences {
+            let remaining =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+            if !fence.wait(remaining)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub fn is_complete(&self) -> crate::Result<bool> {
+        self.wait(Some(Duration::ZERO))
+    }
+}
+
+/// Records the acquiring half of ownership transfers on the graphics queue.
+#[derive(Debug)]
+struct AcquireCommands {
+    // keeps the command buffer's pool alive
+    _command_pool: CommandPool,
+    command_buffer: CommandBuffer,
+    /// Signaled by the transfer submission, and waited on by the acquire submission.
+    semaphore: Semaphore,
+}
+
+/// Objects recording one batch, reused once the batch completes.
+#[derive(Debug)]
+struct BatchCommands {
+    // keeps the command buffer's pool alive
+    _command_pool: CommandPool,
+    command_buffer: CommandBuffer,
+    /// Set when the transfer queue is in another family than the graphics queue.
+    acquire: Option<AcquireCommands>,
+}
+
+/// A destination whose uploads are complete, made available to the graphics queue when its
+/// batch is submitted.
+#[derive(Debug)]
+enum Destination {
+    Buffer(Buffer),
+    Image {
+        image: Image,
+        /// The subresources the upload copied to.
+        ranges: Vec<ImageSubresourceRange>,
+        final_layout: ImageLayout,
+    },
+}
+
+#[derive(Debug)]
+struct Recording {
+    commands: BatchCommands,
+    /// Whether any of the ring is used by the batch.
+    allocated: bool,
+    destinations: Vec<Destination>,
+}
+
+#[derive(Debug)]
+struct InFlight {
+    commands: BatchCommands,
+    fence: Fence,
+    /// The ring head when the batch was submitted, freeing the ring up to it once complete.
+    end: u64,
+}
+
+/// Copies data to buffers and images through a host visible staging ring.
+///
+/// Uploads are batched into one submission until flushed, or until the ring runs out of space.
+/// The copies run on a transfer queue without graphics support when the context has one, and
+/// ownership of the destinations is then transferred to the graphics queue.
+///
+/// Destinations must not be used by the device until their upload completes, and each can only
+/// be uploaded to once per batch.
+#[derive(Debug)]
+pub struct UploadManager {
+    context: Context,
+    staging: Buffer,
+    graphics_queue: Queue,
+    transfer_queue: Queue,
+    /// Where the next allocation starts.
+    head: u64,
+    /// Where the oldest allocation still in use starts.
+    tail: u64,
+    recording: Option<Recording>,
+    in_flight: VecDeque<InFlight>,
+    free: Vec<BatchCommands>,
+}
+
+impl UploadManager {
+    pub fn new(context: Context, create_info: UploadManagerCreateInfo) -> crate::Result<Self> {
+        assert!(create_info.ring_size > 0);
+
+        let staging = Buffer::new(
+            context.clone(),
+            BufferCreateInfo {
+                size: create_info.ring_size,
+                usage: BufferUsageFlags::TRANSFER_SRC,
+                location: MemoryLocation::Upload,
+                label: Some("upload ring"),
+            },
+        )?;
+
+        let graphics_queue = context.queues()[0].clone();
+        let transfer_queue = context
+            .queues()
+            .iter()
+            .find(|queue| {
+                queue.flags().contains(QueueFlags::TRANSFER)
+                    && !queue.flags().contains(QueueFlags::GRAPHICS)
+            })
+            .unwrap_or(&graphics_queue)
+            .clone();
+
+        Ok(Self {
+            context,
+            staging,
+            graphics_queue,
+            transfer_queue,
+            head: 0,
+            tail: 0,
+            recording: None,
+            in_flight: VecDeque::new(),
+            free: Vec::new(),
+        })
+    }
+
+    pub fn ring_size(&self) -> u64 {
+        self.staging.size()
+    }
+
+    /// The queue the copies are submitted to.
+    pub fn transfer_queue(&self) -> &Queue {
+        &self.transfer_queue
+    }
+
+    /// Stages `data` and records its copies into the current batch.
+    ///
+    /// Submits the batch early, and waits for earlier batches, when the ring runs out of space.
+    /// Regions larger than the ring are split, images by rows of texel blocks.
+    ///
+    /// Fails with `Error::InvalidArgument` if the current batch already uploads to the
+    /// destination, which must be flushed first.
+    pub fn upload(&mut self, data: &[u8], destination: UploadDestination) -> crate::Result<()> {
+        if self.is_pending(&destination) {
+            return Err(crate::Error::InvalidArgument(
+                "the destination is already uploaded to by the current batch",
+            ));
+        }
+
+        let destination = match destination {
+            UploadDestination::Buffer { buffer, regions } => {
+                if regions.is_empty() {
+                    return Ok(());
+                }
+
+                for region in regions {
+                    check_range(region.src_offset, region.size, data)?;
+                }
+
+                for region in regions {
+                    self.upload_buffer_region(data, buffer, region)?;
+                }
+
+                Destination::Buffer(buffer.clone())
+            }
+            UploadDestination::Image {
+                image,
+                regions,
+                final_layout,
+            } => {
+                if regions.is_empty() {
+                    return Ok(());
+                }
+
+                for region in regions {
+                    let size = image_region_size(image, region)?;
+                    check_range(region.buffer_offset, size, data)?;
+                }
+
+                let ranges = subresource_ranges(regions);
+                let barriers: Vec<_> = ranges
+                    .iter()
+                    .map(|&subresource_range| ImageBarrier {
+                        image,
+                        src_stages: PipelineStageFlags::TOP_OF_PIPE,
+                        src_access: AccessFlags::empty(),
+                        dst_stages: PipelineStageFlags::TRANSFER,
+                        dst_access: AccessFlags::TRANSFER_WRITE,
+                        old_layout: ImageLayout::Undefined,
+                        new_layout: ImageLayout::TransferDst,
+                        subresource_range,
+                        queue_transfer: None,
+                    })
+                    .collect();
+
+                self.command_buffer()?.pipeline_barrier(&DependencyInfo {
+                    image_barriers: &barriers,
+                    ..Default::default()
+                })?;
+
+                for region in regions {
+                    self.upload_image_region(data, image, region)?;
+                }
+
+                Destination::Image {
+                    image: image.clone(),
+                    ranges,
+                    final_layout,
+                }
+            }
+        };
+
+        self.command_buffer()?;
+        self.recording
+            .as_mut()
+            .unwrap()
+            .destinations
+            .push(destination);
+
+        Ok(())
+    }
+
+    /// Uploads a typed slice, with region offsets in bytes.
+    ///
+    /// # Safety
+    ///
+    /// `T` must have no padding bytes, which would be read uninitialized, e.g. by being
+    /// `#[repr(C)]` with fields that leave no gaps.
+    pub unsafe fn upload_slice<T: Copy>(
+        &mut self,
+        data: &[T],
+        destination: UploadDestination,
+    ) -> crate::Result<()> {
+        // SAFETY: every byte of `data` is initialized, as the caller guarantees
+        let bytes = unsafe {
+            std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+        };
+
+        self.upload(bytes, destination)
+    }
+
+    /// Submits the current batch, returning a handle that completes with every upload so far.
+    pub fn flush(&mut self) -> crate::Result<UploadHandle> {
+        if let Some(recording) = self.recording.take() {
+            self.submit(recording)?;
+        }
+
+        self.retire(false)?;
+
+        Ok(UploadHandle {
+            fences: self
+                .in_flight
+                .iter()
+                .map(|batch| batch.fence.clone())
+                .collect(),
+        })
+    }
+
+    fn upload_buffer_region(
+        &mut self,
+        data: &[u8],
+        buffer: &Buffer,
+        region: &BufferCopy,
+    ) -> crate::Result<()> {
+        let mut copied = 0;
+
+        while copied < region.size {
+            let size = (region.size - copied).min(self.ring_size());
+            let src = (region.src_offset + copied) as usize;
+
+            let offset = self.stage(&data[src..src + size as usize], 4)?;
+
+            self.command_buffer()?.copy_buffer(
+                &self.staging,
+                buffer,
+                &[BufferCopy {
+                    src_offset: offset,
+                    dst_offset: region.dst_offset + copied,
+                    size,
+                }],
+            )?;
+
+            copied += size;
+        }
+
+        Ok(())
+    }
+
+    fn upload_image_region(
+        &mut self,
+        data: &[u8],
+        image: &Image,
+        region: &BufferImageCopy,
+    ) -> crate::Result<()> {
+        let format = image.format();
+        let block = format.block_extent();
+        let block_size = format.block_size() as u64;
+
+        // the format and the region's bounds were checked by upload
+        let row_pitch = region.image_extent.width.div_ceil(block.width) as u64 * block_size;
+        let rows = region.image_extent.height.div_ceil(block.height);
+        let layer_size = row_pitch * rows as u64;
+        let size = layer_size * region.layer_count as u64;
+
+        // copy offsets must be multiples of both the block size and 4
+        let alignment = match block_size % 4 {
+            0 => block_size,
+            2 => block_size * 2,
+            _ => block_size * 4,
+        };
+
+        if size <= self.ring_size() {
+            let src = region.buffer_offset as usize;
+            return self.copy_to_image(&data[src..src + size as usize], alignment, image, *region);
+        }
+
+        let rows_per_copy = (self.ring_size() / row_pitch) as u32;
+        if rows_per_copy == 0 {
+            return Err(crate::Error::Unsupported(
+                "an image row is larger than the upload ring",
+            ));
+        }
+
+        for layer in 0..region.layer_count {
+            let mut row = 0;
+
+            while row < rows {
+                let count = (rows - row).min(rows_per_copy);
+                let src = (region.buffer_offset
+                    + layer as u64 * layer_size
+                    + row as u64 * row_pitch) as usize;
+
+                let y = row * block.height;
+                let height = (count * block.height).min(region.image_extent.height - y);
+
+                self.copy_to_image(
+                    &data[src..src + (count as u64 * row_pitch) as usize],
+                    alignment,
+                    image,
+                    BufferImageCopy {
+                        base_array_layer: region.base_array_layer + layer,
+                        layer_count: 1,
+                        image_offset: Offset2D {
+                            x: region.image_offset.x,
+                            y: region.image_offset.y + y as i32,
+                        },
+                        image_extent: crate::Extent2D {
+                            width: region.image_extent.width,
+                            height,
+                        },
+                        ..*region
+                    },
+                )?;
+
+                row += count;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn copy_to_image(
+        &mut self,
+        bytes: &[u8],
+        alignment: u64,
+        image: &Image,
+        region: BufferImageCopy,
+    ) -> crate::Result<()> {
+        let offset = self.stage(bytes, alignment)?;
+
+        self.command_buffer()?.copy_buffer_to_image(
+            &self.staging,
+            image,
+            ImageLayout::TransferDst,
+            &[BufferImageCopy {
+                buffer_offset: offset,
+                ..region
+            }],
+        )?;
+
+        Ok(())
+    }
+
+    /// Whether the current batch already uploads to the destination.
+    fn is_pending(&self, destination: &UploadDestination) -> bool {
+        let Some(recording) = &self.recording else {
+            return false;
+        };
+
+        recording
+            .destinations
+            .iter()
+            .any(|pending| match (pending, destination) {
+                (Destination::Buffer(pending), UploadDestination::Buffer { buffer, .. }) => {
+                    pending == *buffer
+                }
+                (
+                    Destination::Image { image: pending, .. },
+                    UploadDestination::Image { image, .. },
+                ) => pending == *image,
+                _ => false,
+            })
+    }
+
+    /// Copies `bytes` into the ring, returning their offset in the staging buffer.
+    fn stage(&mut self, bytes: &[u8], alignment: u64) -> crate::Result<u64> {
+        let offset = self.allocate(bytes.len() as u64, alignment)?;
+        self.staging.write(offset, bytes)?;
+
+        self.command_buffer()?;
+        if let Some(recording) = &mut self.recording {
+            recording.allocated = true;
+        }
+
+        Ok(offset)
+    }
+
+    fn allocate(&mut self, size: u64, alignment: u64) -> crate::Result<u64> {
+        debug_assert!(size <= self.ring_size());
+
+        self.retire(false)?;
+
+        loop {
+            if let Some(offset) = self.try_allocate(size, alignment) {
+                return Ok(offset);
+            }
+
+            match self.recording.take() {
+                Some(recording) if recording.allocated => self.submit(recording)?,
+                recording => {
+                    self.recording = recording;
+                    self.retire(true)?;
+                }
+            }
+        }
+    }
+
+    fn try_allocate(&mut self, size: u64, alignment: u64) -> Option<u64> {
+        let in_use = !self.is_empty();
+        let ring_size = self.ring_size();
+
+        let aligned = self.head.next_multiple_of(alignment);
+
+        let offset = if in_use && self.head == self.tail {
+            // full
+            return None;
+        } else if self.head >= self.tail {
+            if aligned + size <= ring_size {
+                aligned
+            } else if size <= self.tail {
+                // wrap around, leaving the end of the ring unused until the tail passes it
+                0
+            } else {
+                return None;
+            }
+        } else if aligned + size <= self.tail {
+            aligned
+        } else {
+            return None;
+        };
+
+        self.head = offset + size;
+        Some(offset)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.in_flight.is_empty()
+            && self
+                .recording
+                .as_ref()
+                .map_or(true, |recording| !recording.allocated)
+    }
+
+    /// Frees the ring used by completed batches, waiting for the oldest one if `block` is set.
+    fn retire(&mut self, mut block: bool) -> crate::Result<()> {
+        while let Some(batch) = self.in_flight.front() {
+            let timeout = (!block).then_some(Duration::ZERO);
+            if !batch.fence.wait(timeout)? {
+                break;
+            }
+
+            let batch = self.in_flight.pop_front().unwrap();
+            self.tail = batch.end;
+            self.free.push(batch.commands);
+            block = false;
+        }
+
+        if self.is_empty() {
+            self.head = 0;
+            self.tail = 0;
+        }
+
+        Ok(())
+    }
+
+    fn submit(&mut self, recording: Recording) -> crate::Result<()> {
+        let commands = recording.commands;
+        let destinations = recording.destinations;
+
+        // makes the destinations available to the graphics queue once their copies complete,
+        // releasing and acquiring ownership when they run on another queue family
+        match &commands.acquire {
+            Some(acquire) => {
+                let queue_transfer = Some((
+                    self.transfer_queue.family_index(),
+                    self.graphics_queue.family_index(),
+                ));
+
+                // the release's destination scope and the acquire's source scope are ignored
+                record_barriers(
+                    &commands.command_buffer,
+                    &destinations,
+                    (PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE),
+                    (PipelineStageFlags::BOTTOM_OF_PIPE, AccessFlags::empty()),
+                    queue_transfer,
+                )?;
+                record_barriers(
+                    &acquire.command_buffer,
+                    &destinations,
+                    (PipelineStageFlags::TOP_OF_PIPE, AccessFlags::empty()),
+                    (PipelineStageFlags::ALL_COMMANDS, AccessFlags::MEMORY_READ),
+                    queue_transfer,
+                )?;
+            }
+            None => record_barriers(
+                &commands.command_buffer,
+                &destinations,
+                (PipelineStageFlags::TRANSFER, AccessFlags::TRANSFER_WRITE),
+                (PipelineStageFlags::ALL_COMMANDS, AccessFlags::MEMORY_READ),
+                None,
+            )?,
+        }
+
+        commands.command_buffer.end()?;
+
+        let fence = Fence::new(
+            self.context.clone(),
+            FenceCreateInfo {
+                signaled: false,
+                label: None,
+            },
+        )?;
+
+        match &commands.acquire {
+            Some(acquire) if !destinations.is_empty() => {
+                acquire.command_buffer.end()?;
+
+                self.transfer_queue.submit(
+                    &SubmitInfo {
+                        command_buffers: &[&commands.command_buffer],
+                        signal_semaphores: &[&acquire.semaphore],
+                        ..Default::default()
+                    },
+                    None,
+                )?;
+
+                self.graphics_queue.submit(
+                    &SubmitInfo {
+                        wait_semaphores: &[(&acquire.semaphore, PipelineStageFlags::ALL_COMMANDS)],
+                        command_buffers: &[&acquire.command_buffer],
+                        ..Default::default()
+                    },
+                    Some(&fence),
+                )?;
+            }
+            acquire => {
+                if let Some(acquire) = acquire {
+                    acquire.command_buffer.end()?;
+                }
+
+                self.transfer_queue.submit(
+                    &SubmitInfo {
+                        command_buffers: &[&commands.command_buffer],
+                        ..Default::default()
+                    },
+                    Some(&fence),
+                )?;
+            }
+        }
+
+        self.in_flight.push_back(InFlight {
+            commands,
+            fence,
+            end: self.head,
+        });
+
+        Ok(())
+    }
+
+    /// The command buffer of the current batch, which is begun if there's none.
+    fn command_buffer(&mut self) -> crate::Result<CommandBuffer> {
+        if self.recording.is_none() {
+            let commands = match self.free.pop() {
+                Some(commands) => commands,
+                None => self.create_commands()?,
+            };
+
+            commands.command_buffer.begin()?;
+            if let Some(acquire) = &commands.acquire {
+                acquire.command_buffer.begin()?;
+            }
+
+            self.recording = Some(Recording {
+                commands,
+                allocated: false,
+                destinations: Vec::new(),
+            });
+        }
+
+        Ok(self
+            .recording
+            .as_ref()
+            .unwrap()
+            .commands
+            .command_buffer
+            .clone())
+    }
+
+    fn create_commands(&self) -> crate::Result<BatchCommands> {
+        let transfer_family = self.transfer_queue.family_index();
+        let graphics_family = self.graphics_queue.family_index();
+
+        let (command_pool, command_buffer) = create_command_buffer(&self.context, transfer_family)?;
+
+        let acquire = if transfer_family != graphics_family {
+            let (command_pool, command_buffer) =
+                create_command_buffer(&self.context, graphics_family)?;
+
+            Some(AcquireCommands {
+                _command_pool: command_pool,
+                command_buffer,
+                semaphore: Semaphore::new(self.context.clone(), SemaphoreCreateInfo::default())?,
+            })
+        } else {
+            None
+        };
+
+        Ok(BatchCommands {
+            _command_pool: command_pool,
+            command_buffer,
+            acquire,
+        })
+    }
+}
+
+impl Drop for UploadManager {
+    fn drop(&mut self) {
+        // the staging ring can't be destroyed while copies read from it
+        for batch in &self.in_flight {
+            let _ = batch.fence.wait(None);
+        }
+    }
+}
+
+fn create_command_buffer(
+    context: &Context,
+    queue_family_index: u32,
+) -> crate::Result<(CommandPool, CommandBuffer)> {
+    let command_pool = CommandPool::new(
+        context.clone(),
+        CommandPoolCreateInfo {
+            queue_family_index,
+            transient: true,
+            reset: true,
+            label: None,
+        },
+    )?;
+
+    let command_buffer =
+        CommandBuffer::allocate(command_pool.clone(), CommandBufferAllocateInfo::default())?;
+
+    Ok((command_pool, command_buffer))
+}
+
+/// Records barriers covering every destination, from the `src` stages and accesses to the
+/// `dst` ones.
+fn record_barriers(
+    command_buffer: &CommandBuffer,
+    destinations: &[Destination],
+    (src_stages, src_access): (PipelineStageFlags, AccessFlags),
+    (dst_stages, dst_access): (PipelineStageFlags, AccessFlags),
+    queue_transfer: Option<(u32, u32)>,
+) -> crate::Result<()> {
+    let mut buffer_barriers = Vec::new();
+    let mut image_barriers = Vec::new();
+
+    for destination in destinations {
+        match destination {
+            Destination::Buffer(buffer) => buffer_barriers.push(BufferBarrier {
+                buffer,
+                src_stages,
+                src_access,
+                dst_stages,
+                dst_access,
+                offset: 0,
+                size: None,
+                queue_transfer,
+            }),
+            Destination::Image {
+                image,
+                ranges,
+                final_layout,
+            } => image_barriers.extend(ranges.iter().map(|&subresource_range| ImageBarrier {
+                image,
+                src_stages,
+                src_access,
+                dst_stages,
+                dst_access,
+                old_layout: ImageLayout::TransferDst,
+                new_layout: *final_layout,
+                subresource_range,
+                queue_transfer,
+            })),
+        }
+    }
+
+    if buffer_barriers.is_empty() && image_barriers.is_empty() {
+        return Ok(());
+    }
+
+    command_buffer.pipeline_barrier(&DependencyInfo {
+        buffer_barriers: &buffer_barriers,
+        image_barriers: &image_barriers,
+        ..Default::default()
+    })
+}
+
+/// The subresources the regions copy to, as ranges of one aspect and mip level that cover each
+/// subresource once, so none is moved out of the undefined layout after being written.
+fn subresource_ranges(regions: &[BufferImageCopy]) -> Vec<ImageSubresourceRange> {
+    let mut ranges: Vec<ImageSubresourceRange> = Vec::new();
+
+    for region in regions {
+        let covered = |ranges: &[ImageSubresourceRange], layer: u32| {
+            ranges.iter().any(|range| {
+                range.aspect == region.aspect
+                    && range.base_mip_level == region.mip_level
+                    && (range.base_array_layer..range.base_array_layer + range.layer_count)
+                        .contains(&layer)
+            })
+        };
+
+        let end = region.base_array_layer.saturating_add(region.layer_count);
+        let mut layer = region.base_array_layer;
+
+        while layer < end {
+            if covered(&ranges, layer) {
+                layer += 1;
+                continue;
+            }
+
+            let base = layer;
+            while layer < end && !covered(&ranges, layer) {
+                layer += 1;
+            }
+
+            ranges.push(ImageSubresourceRange {
+                aspect: region.aspect,
+                base_mip_level: region.mip_level,
+                level_count: 1,
+                base_array_layer: base,
+                layer_count: layer - base,
+            });
+        }
+    }
+
+    ranges
+}
+
+/// Size in bytes of the data a region uploads, with its layers tightly packed.
+fn image_region_size(image: &Image, region: &BufferImageCopy) -> crate::Result<u64> {
+    let format = image.format();
+    let block = format.block_extent();
+    let block_size = format.block_size() as u64;
+
+    if block_size == 0 {
+        return Err(crate::Error::Unsupported(
+            "can't upload to an image of unknown format",
+        ));
+    }
+
+    let row_pitch = region.image_extent.width.div_ceil(block.width) as u64 * block_size;
+    let rows = region.image_extent.height.div_ceil(block.height) as u64;
+
+    Ok(row_pitch * rows * region.layer_count as u64)
+}
+
+/// Fails unless `size` bytes at `offset` lie within `data`.
+fn check_range(offset: u64, size: u64, data: &[u8]) -> crate::Result<()> {
+    match offset.checked_add(size) {
+        Some(end) if end <= data.len() as u64 => Ok(()),
+        _ => Err(crate::Error::InvalidArgument(
+            "an upload region runs past the end of the data",
+        )),
+    }
+}